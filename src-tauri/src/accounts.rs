@@ -0,0 +1,175 @@
+// Account quick-switch. Pinning an account disables every other one through the
+// engine's toggle API; going back to rotation re-enables exactly the accounts the
+// pin disabled, so accounts the user turned off by hand stay off.
+use std::{fs, path::PathBuf, sync::Mutex, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    api::{self, AccountLimits},
+    audit, lock, quota, sidecar, telemetry, tray,
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const PIN_FILE: &str = "account-pin.json";
+
+#[derive(Clone, PartialEq)]
+pub struct AccountEntry {
+    pub email: String,
+    pub enabled: bool,
+}
+
+// Survives restarts, otherwise a pin would leave accounts disabled for good
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PinRecord {
+    pinned: Option<String>,
+    parked: Vec<String>,
+}
+
+#[derive(Default)]
+struct AccountsInner {
+    accounts: Vec<AccountEntry>,
+    pin: Option<PinRecord>,
+}
+
+#[derive(Default)]
+pub struct AccountsState(Mutex<AccountsInner>);
+
+/// Known accounts and the pinned one, if any
+pub fn snapshot(app: &AppHandle) -> (Vec<AccountEntry>, Option<String>) {
+    let state = app.state::<AccountsState>();
+    let mut inner = state.0.lock().unwrap();
+    let pinned = inner
+        .pin
+        .get_or_insert_with(|| load_record(app))
+        .pinned
+        .clone();
+    (inner.accounts.clone(), pinned)
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh(&app).await;
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+/// Re-read the account list from the engine and update the tray if it changed
+pub async fn refresh(app: &AppHandle) {
+    if app.state::<sidecar::SidecarState>().pid().is_none() {
+        return;
+    }
+    let Ok(raw) = api::get::<Value>(sidecar::current_port(app), "/account-limits").await else {
+        return;
+    };
+    quota::update(app, &raw);
+    let Ok(limits) = serde_json::from_value::<AccountLimits>(raw) else {
+        return;
+    };
+    let accounts: Vec<AccountEntry> = limits
+        .accounts
+        .into_iter()
+        .map(|a| AccountEntry {
+            email: a.email,
+            enabled: a.enabled,
+        })
+        .collect();
+
+    let changed = {
+        let state = app.state::<AccountsState>();
+        let mut inner = state.0.lock().unwrap();
+        let changed = inner.accounts != accounts;
+        inner.accounts = accounts;
+        changed
+    };
+    if changed {
+        tray::refresh(app);
+    }
+}
+
+/// Send all traffic to one account, or back to automatic rotation with `None`
+#[tauri::command]
+pub async fn pin_account(app: AppHandle, email: Option<String>) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    telemetry::count(&app, "feature.pinAccount");
+    let port = sidecar::current_port(&app);
+    let mut record = load_record(&app);
+
+    // Undo the previous pin first. The record is saved after every toggle, so a
+    // failure part way leaves exactly the still-disabled accounts parked.
+    while let Some(parked) = record.parked.last().cloned() {
+        toggle(port, &parked, true).await?;
+        record.parked.pop();
+        save_record(&app, &record)?;
+    }
+    record.pinned = None;
+
+    if let Some(email) = &email {
+        let limits = api::get::<AccountLimits>(port, "/account-limits").await?;
+        if !limits.accounts.iter().any(|a| &a.email == email) {
+            return Err(format!("Unknown account: {email}"));
+        }
+        toggle(port, email, true).await?;
+        for account in limits
+            .accounts
+            .iter()
+            .filter(|a| a.enabled && &a.email != email)
+        {
+            toggle(port, &account.email, false).await?;
+            record.parked.push(account.email.clone());
+            save_record(&app, &record)?;
+        }
+        record.pinned = Some(email.clone());
+    }
+
+    save_record(&app, &record)?;
+    log::info!(
+        "account routing: {}",
+        email.as_deref().unwrap_or("automatic rotation")
+    );
+    audit::record(&app, "account.pin", json!({ "account": email }));
+    app.state::<AccountsState>().0.lock().unwrap().pin = Some(record);
+    refresh(&app).await;
+    tray::refresh(&app);
+    Ok(())
+}
+
+async fn toggle(port: u16, email: &str, enabled: bool) -> Result<(), String> {
+    api::post::<serde_json::Value>(
+        port,
+        &api::account_path(email, Some("toggle")),
+        &json!({ "enabled": enabled }),
+    )
+    .await
+    .map(|_| ())
+}
+
+fn record_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(PIN_FILE))
+}
+
+fn load_record(app: &AppHandle) -> PinRecord {
+    record_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_record(app: &AppHandle, record: &PinRecord) -> Result<(), String> {
+    let path = record_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
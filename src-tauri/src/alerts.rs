@@ -0,0 +1,107 @@
+// Native notifications for problems that happen while the window is hidden
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::{email, i18n, traffic::ErrorClass};
+
+const BURST_WINDOW: Duration = Duration::from_secs(60);
+const BURST_THRESHOLD: usize = 5;
+const COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Default)]
+struct AlertInner {
+    failures: VecDeque<(Instant, ErrorClass)>,
+    last_alert: Option<Instant>,
+    muted_until: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct AlertState(Mutex<AlertInner>);
+
+/// Count a failed request and notify once failures pile up
+pub fn on_failure(app: &AppHandle, class: ErrorClass) {
+    let state = app.state::<AlertState>();
+    let mut inner = state.0.lock().unwrap();
+    let now = Instant::now();
+
+    inner.failures.push_back((now, class));
+    while inner
+        .failures
+        .front()
+        .is_some_and(|(at, _)| now.duration_since(*at) > BURST_WINDOW)
+    {
+        inner.failures.pop_front();
+    }
+
+    let cooling_down = inner
+        .last_alert
+        .is_some_and(|at| now.duration_since(at) < COOLDOWN);
+    let muted = inner.muted_until.is_some_and(|until| now < until);
+    if inner.failures.len() < BURST_THRESHOLD || cooling_down || muted {
+        return;
+    }
+    inner.last_alert = Some(now);
+
+    let mut by_class: HashMap<ErrorClass, usize> = HashMap::new();
+    for (_, class) in &inner.failures {
+        *by_class.entry(*class).or_insert(0) += 1;
+    }
+    let dominant = by_class
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(class, _)| class)
+        .unwrap_or(class);
+    let count = inner.failures.len();
+    drop(inner);
+
+    log::warn!("{count} failed requests in the last minute");
+    let title = i18n::t(app, "alerts.failingTitle");
+    let summary = i18n::tf(
+        app,
+        "alerts.failingSummary",
+        &[
+            ("count", count.to_string()),
+            ("class", i18n::t(app, describe(dominant))),
+        ],
+    );
+    // Desktop notifications can't carry buttons, so the actions live in the tray menu
+    notify(
+        app,
+        &title,
+        &format!("{summary} {}", i18n::t(app, "alerts.failingHint")),
+    );
+    email::alert(app, &title, &summary);
+}
+
+/// Hold back failure alerts for a while, e.g. while the network comes back after sleep
+pub fn mute(app: &AppHandle, duration: Duration) {
+    let state = app.state::<AlertState>();
+    let mut inner = state.0.lock().unwrap();
+    inner.muted_until = Some(Instant::now() + duration);
+    inner.failures.clear();
+}
+
+pub fn notify(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        log::warn!("failed to show notification: {e}");
+    }
+}
+
+// Translation key for a class
+fn describe(class: ErrorClass) -> &'static str {
+    match class {
+        ErrorClass::Auth => "errorClass.auth",
+        ErrorClass::RateLimit => "errorClass.rateLimit",
+        ErrorClass::Upstream5xx => "errorClass.upstream5xx",
+        ErrorClass::Network => "errorClass.network",
+        ErrorClass::Translation => "errorClass.translation",
+        ErrorClass::ClientAbort => "errorClass.clientAbort",
+        ErrorClass::Other => "errorClass.other",
+    }
+}
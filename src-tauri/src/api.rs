@@ -0,0 +1,138 @@
+// Client for the proxy engine's local HTTP API
+use std::{net::IpAddr, sync::RwLock, time::Duration};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use tauri_plugin_http::reqwest;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+// Host the app reaches the engine on; empty until the engine is started
+static CONNECT_HOST: RwLock<String> = RwLock::new(String::new());
+
+#[derive(Deserialize)]
+pub struct AccountLimits {
+    pub accounts: Vec<Account>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub email: String,
+    pub status: String,
+    #[serde(default)]
+    pub enabled: bool,
+    pub error: Option<String>,
+}
+
+pub fn base_url(port: u16) -> String {
+    let host = CONNECT_HOST.read().unwrap();
+    let host = if host.is_empty() { "localhost" } else { &host };
+    format!("http://{host}:{port}")
+}
+
+/// Follow the engine to the address it was told to listen on, so an engine bound
+/// to a single (possibly IPv6-only) address stays reachable
+pub fn set_bind_address(bind: Option<&str>) {
+    *CONNECT_HOST.write().unwrap() = url_host(bind.unwrap_or_default());
+}
+
+/// Listen address as written in the settings, without URL brackets ("[::1]" -> "::1")
+pub fn listen_host(bind: &str) -> &str {
+    let bind = bind.trim();
+    bind.strip_prefix('[')
+        .and_then(|b| b.strip_suffix(']'))
+        .unwrap_or(bind)
+}
+
+/// Host part of a URL for a listen address. Wildcards (0.0.0.0, ::) are reached
+/// through localhost, which resolves to both loopback families; IPv6 literals
+/// are bracketed.
+pub fn url_host(bind: &str) -> String {
+    let bind = listen_host(bind);
+    match bind.parse::<IpAddr>() {
+        Ok(ip) if ip.is_unspecified() => "localhost".into(),
+        Ok(IpAddr::V6(ip)) => format!("[{ip}]"),
+        Ok(IpAddr::V4(ip)) => ip.to_string(),
+        Err(_) if bind.is_empty() => "localhost".into(),
+        Err(_) => bind.to_string(),
+    }
+}
+
+pub fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .build()
+        .unwrap_or_default()
+}
+
+pub async fn get<T: DeserializeOwned>(port: u16, path: &str) -> Result<T, String> {
+    let request = client().get(format!("{}{path}", base_url(port)));
+    send(request).await
+}
+
+pub async fn post<T: DeserializeOwned>(
+    port: u16,
+    path: &str,
+    body: &serde_json::Value,
+) -> Result<T, String> {
+    let request = client()
+        .post(format!("{}{path}", base_url(port)))
+        .header("Content-Type", "application/json")
+        .body(body.to_string());
+    send(request).await
+}
+
+/// `/api/accounts/<email>[/<action>]` with the email percent-encoded
+pub fn account_path(email: &str, action: Option<&str>) -> String {
+    let mut url = reqwest::Url::parse("http://localhost/api/accounts/").expect("valid base url");
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.pop_if_empty().push(email);
+        if let Some(action) = action {
+            segments.push(action);
+        }
+    }
+    url.path().to_string()
+}
+
+async fn send<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T, String> {
+    let body = request
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listen_host_drops_brackets() {
+        assert_eq!(listen_host("::"), "::");
+        assert_eq!(listen_host("[::]"), "::");
+        assert_eq!(listen_host("::1"), "::1");
+        assert_eq!(listen_host(" [::1] "), "::1");
+        assert_eq!(listen_host("0.0.0.0"), "0.0.0.0");
+        assert_eq!(listen_host("127.0.0.1"), "127.0.0.1");
+        assert_eq!(listen_host("[::1"), "[::1");
+    }
+
+    #[test]
+    fn url_host_brackets_ipv6_and_maps_wildcards_to_localhost() {
+        assert_eq!(url_host("::"), "localhost");
+        assert_eq!(url_host("[::]"), "localhost");
+        assert_eq!(url_host("0.0.0.0"), "localhost");
+        assert_eq!(url_host("::1"), "[::1]");
+        assert_eq!(url_host("[::1]"), "[::1]");
+        assert_eq!(url_host("fe80::1"), "[fe80::1]");
+        assert_eq!(url_host("127.0.0.1"), "127.0.0.1");
+        assert_eq!(url_host("192.168.1.20"), "192.168.1.20");
+        assert_eq!(url_host("my-host.local"), "my-host.local");
+        assert_eq!(url_host(""), "localhost");
+    }
+}
@@ -0,0 +1,274 @@
+// Optional audit log of what was changed, by whom and when: config changes,
+// account switches, engine updates. Each entry carries the hash of the one before
+// it, so editing or deleting a line breaks the chain from there on. Cutting
+// entries off the end can only be caught by comparing against a head hash that
+// was kept elsewhere, which is why verification reports it.
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+
+use crate::{lock, settings, traffic};
+
+const AUDIT_FILE: &str = "audit.log";
+// prev of the first entry
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+// Appends must not interleave, or two entries would claim the same predecessor
+static APPEND: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditEntry {
+    seq: u64,
+    timestamp: u64,
+    user: String,
+    action: String,
+    detail: Value,
+    prev: String,
+    hash: String,
+}
+
+impl AuditEntry {
+    fn digest(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in [
+            self.seq.to_string(),
+            self.timestamp.to_string(),
+            self.user.clone(),
+            self.action.clone(),
+            self.detail.to_string(),
+            self.prev.clone(),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditVerification {
+    entries: u64,
+    valid: bool,
+    /// Sequence number of the first entry that doesn't check out
+    broken_at: Option<u64>,
+    problem: Option<String>,
+    /// Hash of the last entry; keep a copy to detect entries removed from the end
+    head_hash: Option<String>,
+}
+
+/// Append an entry when the audit log is enabled. Failures are logged, never
+/// returned, so auditing can't block the action itself.
+pub fn record(app: &AppHandle, action: &str, detail: Value) {
+    if !enabled(app) {
+        return;
+    }
+    if let Err(e) = append(app, action, detail) {
+        log::error!("audit log: could not record {action}: {e}");
+    }
+}
+
+/// Entry point for changes made from the frontend (settings saves)
+#[tauri::command]
+pub fn audit_event(app: AppHandle, action: String, detail: Option<Value>) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    record(&app, &action, detail.unwrap_or(Value::Null));
+    Ok(())
+}
+
+/// Walk the chain and report the first entry that was altered, reordered or removed
+#[tauri::command]
+pub fn verify_audit_log(app: AppHandle) -> Result<AuditVerification, String> {
+    let content = match fs::read_to_string(audit_path(&app)?) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.to_string()),
+    };
+    Ok(verify(&content))
+}
+
+fn verify(content: &str) -> AuditVerification {
+    let mut prev = GENESIS.to_string();
+    let mut entries = 0;
+    for (seq, line) in content.lines().enumerate() {
+        let seq = seq as u64;
+        let problem = match serde_json::from_str::<AuditEntry>(line) {
+            Err(e) => Some(format!("unreadable entry: {e}")),
+            Ok(entry) if entry.seq != seq => {
+                Some(format!("expected entry {seq}, found {}", entry.seq))
+            }
+            Ok(entry) if entry.prev != prev => Some("does not follow the previous entry".into()),
+            Ok(entry) if entry.digest() != entry.hash => Some("contents were modified".into()),
+            Ok(entry) => {
+                prev = entry.hash;
+                None
+            }
+        };
+        if let Some(problem) = problem {
+            return AuditVerification {
+                entries: content.lines().count() as u64,
+                valid: false,
+                broken_at: Some(seq),
+                problem: Some(problem),
+                head_hash: None,
+            };
+        }
+        entries += 1;
+    }
+
+    AuditVerification {
+        entries,
+        valid: true,
+        broken_at: None,
+        problem: None,
+        head_hash: (entries > 0).then_some(prev),
+    }
+}
+
+fn append(app: &AppHandle, action: &str, detail: Value) -> Result<(), String> {
+    let _guard = APPEND.lock().unwrap();
+    let path = audit_path(app)?;
+    let last = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| content.lines().last().map(String::from))
+        .and_then(|line| serde_json::from_str::<AuditEntry>(&line).ok());
+
+    let mut entry = AuditEntry {
+        seq: last.as_ref().map_or(0, |e| e.seq + 1),
+        timestamp: traffic::now_ms(),
+        user: os_user(),
+        action: action.to_string(),
+        detail,
+        prev: last.map_or(GENESIS.to_string(), |e| e.hash),
+        hash: String::new(),
+    };
+    entry.hash = entry.digest();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| e.to_string())?;
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+    writeln!(file, "{line}").map_err(|e| e.to_string())
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    settings::load(app)
+        .get("auditLog")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+fn os_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".into())
+}
+
+fn audit_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(AUDIT_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // A log of `actions.len()` entries, chained the way `append` writes them
+    fn log_of(actions: &[&str]) -> Vec<String> {
+        let mut prev = GENESIS.to_string();
+        actions
+            .iter()
+            .enumerate()
+            .map(|(seq, action)| {
+                let mut entry = AuditEntry {
+                    seq: seq as u64,
+                    timestamp: 1_750_000_000_000 + seq as u64,
+                    user: "alice".into(),
+                    action: action.to_string(),
+                    detail: json!({ "preset": "fast" }),
+                    prev: prev.clone(),
+                    hash: String::new(),
+                };
+                entry.hash = entry.digest();
+                prev = entry.hash.clone();
+                serde_json::to_string(&entry).unwrap()
+            })
+            .collect()
+    }
+
+    const ACTIONS: [&str; 4] = ["proxy.start", "mapping.save", "mapping.apply", "proxy.stop"];
+
+    #[test]
+    fn intact_log_verifies() {
+        let lines = log_of(&ACTIONS);
+        let report = verify(&lines.join("\n"));
+        assert!(report.valid);
+        assert_eq!(report.entries, 4);
+        let last: AuditEntry = serde_json::from_str(&lines[3]).unwrap();
+        assert_eq!(report.head_hash, Some(last.hash));
+    }
+
+    #[test]
+    fn edited_entry_is_detected() {
+        let mut lines = log_of(&ACTIONS);
+        lines[1] = lines[1].replace("\"fast\"", "\"best\"");
+        let report = verify(&lines.join("\n"));
+        assert!(!report.valid);
+        assert_eq!(report.broken_at, Some(1));
+        assert_eq!(report.problem.as_deref(), Some("contents were modified"));
+    }
+
+    #[test]
+    fn rehashed_entry_breaks_the_next_link() {
+        // Recomputing the edited entry's own hash doesn't help: its successor
+        // still names the old one
+        let mut lines = log_of(&ACTIONS);
+        let mut entry: AuditEntry = serde_json::from_str(&lines[1]).unwrap();
+        entry.action = "mapping.delete".into();
+        entry.hash = entry.digest();
+        lines[1] = serde_json::to_string(&entry).unwrap();
+        let report = verify(&lines.join("\n"));
+        assert_eq!(report.broken_at, Some(2));
+        assert_eq!(
+            report.problem.as_deref(),
+            Some("does not follow the previous entry")
+        );
+    }
+
+    #[test]
+    fn removed_entry_is_detected() {
+        let mut lines = log_of(&ACTIONS);
+        lines.remove(2);
+        let report = verify(&lines.join("\n"));
+        assert!(!report.valid);
+        assert_eq!(report.broken_at, Some(2));
+        assert_eq!(report.problem.as_deref(), Some("expected entry 2, found 3"));
+        assert_eq!(report.entries, 3);
+    }
+
+    #[test]
+    fn empty_log_is_valid_without_a_head() {
+        let report = verify("");
+        assert!(report.valid);
+        assert_eq!(report.entries, 0);
+        assert_eq!(report.head_hash, None);
+    }
+}
@@ -0,0 +1,55 @@
+// In-flight request count on the app icon: the dock badge on macOS (and Linux
+// desktops that support one), a numbered overlay on the Windows taskbar button.
+// Fed from the engine's log via traffic.rs; "activityBadge": false turns it off.
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::Value;
+use tauri::{image::Image, include_image, AppHandle, Manager};
+
+use crate::{settings, traffic};
+
+// Windows overlays are fixed images, so counts above nine share one
+const OVERLAYS: [Image<'static>; 10] = [
+    include_image!("icons/badge/1.png"),
+    include_image!("icons/badge/2.png"),
+    include_image!("icons/badge/3.png"),
+    include_image!("icons/badge/4.png"),
+    include_image!("icons/badge/5.png"),
+    include_image!("icons/badge/6.png"),
+    include_image!("icons/badge/7.png"),
+    include_image!("icons/badge/8.png"),
+    include_image!("icons/badge/9.png"),
+    include_image!("icons/badge/9plus.png"),
+];
+
+// Last count seen, so log lines that don't change it cost nothing
+static SEEN: AtomicUsize = AtomicUsize::new(0);
+
+/// Show the current in-flight count if it changed
+pub fn refresh(app: &AppHandle) {
+    let count = traffic::in_flight(app);
+    if SEEN.swap(count, Ordering::Relaxed) == count {
+        return;
+    }
+    let count = if enabled(app) { count } else { 0 };
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    let result = if cfg!(windows) {
+        let overlay = count.checked_sub(1).map(|i| OVERLAYS[i.min(9)].clone());
+        window.set_overlay_icon(overlay)
+    } else {
+        window.set_badge_count((count > 0).then_some(count as i64))
+    };
+    if let Err(e) = result {
+        log::debug!("failed to update the activity badge: {e}");
+    }
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    settings::load(app)
+        .get("activityBadge")
+        .and_then(Value::as_bool)
+        .unwrap_or(true)
+}
@@ -0,0 +1,151 @@
+// Synthetic load through the running proxy, for tuning queue and retry settings
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri_plugin_http::reqwest;
+
+use crate::api;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const MAX_CONCURRENCY: usize = 64;
+
+#[derive(Deserialize)]
+struct ModelList {
+    data: Vec<ModelEntry>,
+}
+
+#[derive(Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    model: String,
+    requests: usize,
+    succeeded: usize,
+    failed: usize,
+    /// Failure counts keyed by HTTP status, or "network" when no response came back
+    errors: BTreeMap<String, usize>,
+    duration_ms: u64,
+    throughput_rps: f64,
+    p50_ms: u64,
+    p95_ms: u64,
+    p99_ms: u64,
+}
+
+/// Send `n` requests with `concurrency` in flight, each carrying roughly
+/// `prompt_size` tokens of filler. Uses the first listed model unless one is given.
+#[tauri::command]
+pub async fn run_benchmark(
+    port: u16,
+    concurrency: usize,
+    n: usize,
+    prompt_size: usize,
+    model: Option<String>,
+) -> Result<BenchmarkReport, String> {
+    let model = match model {
+        Some(model) => model,
+        None => api::get::<ModelList>(port, "/v1/models")
+            .await?
+            .data
+            .into_iter()
+            .next()
+            .map(|m| m.id)
+            .ok_or("Proxy reports no models")?,
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("{}/v1/messages", api::base_url(port));
+    // ~4 characters per token
+    let body = json!({
+        "model": model,
+        "max_tokens": 16,
+        "messages": [{ "role": "user", "content": "lorem ".repeat(prompt_size.max(1) * 4 / 6 + 1) }],
+    })
+    .to_string();
+
+    log::info!("benchmark: {n} requests, concurrency {concurrency}, model {model}");
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+
+    let workers: Vec<_> = (0..concurrency.clamp(1, MAX_CONCURRENCY))
+        .map(|_| {
+            let (client, url, body, next) =
+                (client.clone(), url.clone(), body.clone(), next.clone());
+            tauri::async_runtime::spawn(async move {
+                let mut results = Vec::new();
+                while next.fetch_add(1, Ordering::Relaxed) < n {
+                    let sent = Instant::now();
+                    let outcome = client
+                        .post(&url)
+                        .header("Content-Type", "application/json")
+                        .header("anthropic-version", "2023-06-01")
+                        .body(body.clone())
+                        .send()
+                        .await;
+                    let error = match outcome {
+                        Ok(r) if r.status().is_success() => {
+                            // Latency includes reading the full body
+                            let _ = r.bytes().await;
+                            None
+                        }
+                        Ok(r) => Some(r.status().as_u16().to_string()),
+                        Err(_) => Some("network".to_string()),
+                    };
+                    results.push((sent.elapsed().as_millis() as u64, error));
+                }
+                results
+            })
+        })
+        .collect();
+
+    let mut latencies = Vec::with_capacity(n);
+    let mut errors = BTreeMap::new();
+    for worker in workers {
+        for (latency, error) in worker.await.map_err(|e| e.to_string())? {
+            match error {
+                None => latencies.push(latency),
+                Some(key) => *errors.entry(key).or_insert(0) += 1,
+            }
+        }
+    }
+
+    let elapsed = started.elapsed();
+    latencies.sort_unstable();
+    let succeeded = latencies.len();
+
+    Ok(BenchmarkReport {
+        model,
+        requests: n,
+        succeeded,
+        failed: n - succeeded,
+        errors,
+        duration_ms: elapsed.as_millis() as u64,
+        throughput_rps: succeeded as f64 / elapsed.as_secs_f64().max(0.001),
+        p50_ms: percentile(&latencies, 50.0),
+        p95_ms: percentile(&latencies, 95.0),
+        p99_ms: percentile(&latencies, 99.0),
+    })
+}
+
+// Nearest-rank percentile over an already sorted slice
+pub fn percentile(sorted: &[u64], p: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -0,0 +1,353 @@
+// Saved request/response pairs and a structured diff between two of them, to see
+// what changed between a working request and a failing one. Captures currently
+// come from the cURL replay tool.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::{privacy, redact, settings, telemetry, traffic::now_ms, vault};
+
+const CAPTURES_DIR: &str = "captures";
+const PLAIN_EXT: &str = "json";
+const ENCRYPTED_EXT: &str = "enc";
+// A request sent with this header set to "true" or "1" keeps its bodies out of
+// the capture; status, headers and timing are still saved
+const NO_LOG_HEADER: &str = "x-proxy-no-log";
+// Vary between otherwise identical responses
+const VOLATILE_HEADERS: [&str; 3] = ["date", "x-request-id", "request-id"];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturedResponse {
+    pub status: u16,
+    pub duration_ms: u64,
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capture {
+    pub id: String,
+    pub timestamp: u64,
+    pub request: CapturedRequest,
+    pub response: CapturedResponse,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureSummary {
+    id: String,
+    timestamp: u64,
+    method: String,
+    path: String,
+    status: u16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference; `path` is a JSON pointer into the body, or the header name
+#[derive(Serialize)]
+pub struct Change {
+    path: String,
+    kind: ChangeKind,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureDiff {
+    request_line: Vec<Change>,
+    request_headers: Vec<Change>,
+    request_body: Vec<Change>,
+    response_status: Vec<Change>,
+    response_headers: Vec<Change>,
+    response_body: Vec<Change>,
+}
+
+pub fn save(
+    app: &AppHandle,
+    mut request: CapturedRequest,
+    mut response: CapturedResponse,
+) -> Result<String, String> {
+    if !privacy::store_bodies(app) || opted_out(&request.headers) {
+        request.body = None;
+        response.body.clear();
+    }
+    // Replay sends the live headers, so the stored copy doesn't need credentials
+    mask_credentials(&mut request.headers);
+    mask_credentials(&mut response.headers);
+    let timestamp = now_ms();
+    let dir = captures_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    // Millisecond ids, bumped on the rare collision
+    let mut id = timestamp.to_string();
+    let mut n = 1;
+    while [PLAIN_EXT, ENCRYPTED_EXT]
+        .iter()
+        .any(|ext| dir.join(format!("{id}.{ext}")).exists())
+    {
+        id = format!("{timestamp}-{n}");
+        n += 1;
+    }
+
+    let capture = Capture {
+        id: id.clone(),
+        timestamp,
+        request,
+        response,
+    };
+    let json = serde_json::to_string_pretty(&capture).map_err(|e| e.to_string())?;
+    let encrypt = settings::load(app)
+        .get("encryptCaptures")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if encrypt {
+        let data = vault::encrypt(json.as_bytes())?;
+        fs::write(dir.join(format!("{id}.{ENCRYPTED_EXT}")), data)
+    } else {
+        fs::write(dir.join(format!("{id}.{PLAIN_EXT}")), json)
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(id)
+}
+
+// Authorization, x-api-key, Cookie, Set-Cookie and the like
+fn mask_credentials(headers: &mut BTreeMap<String, String>) {
+    for (name, value) in headers.iter_mut() {
+        if redact::is_secret_key(name) {
+            *value = redact::REDACTED.to_string();
+        }
+    }
+}
+
+fn opted_out(headers: &BTreeMap<String, String>) -> bool {
+    headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case(NO_LOG_HEADER)
+            && matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "1")
+    })
+}
+
+// Plain and encrypted captures can sit side by side after the setting changes
+fn read_capture(path: &Path) -> Result<Capture, String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let json = match path.extension().and_then(|e| e.to_str()) {
+        Some(ENCRYPTED_EXT) => vault::decrypt(&data)?,
+        _ => data,
+    };
+    serde_json::from_slice(&json).map_err(|e| e.to_string())
+}
+
+pub fn load(app: &AppHandle, id: &str) -> Result<Capture, String> {
+    if id.contains(['/', '\\', '.']) {
+        return Err(format!("Invalid capture id: {id}"));
+    }
+    let dir = captures_dir(app)?;
+    [PLAIN_EXT, ENCRYPTED_EXT]
+        .iter()
+        .map(|ext| dir.join(format!("{id}.{ext}")))
+        .find(|path| path.exists())
+        .ok_or(format!("Capture not found: {id}"))
+        .and_then(|path| read_capture(&path))
+}
+
+/// Saved captures, newest first
+#[tauri::command]
+pub fn list_captures(app: AppHandle) -> Result<Vec<CaptureSummary>, String> {
+    let Ok(entries) = fs::read_dir(captures_dir(&app)?) else {
+        return Ok(Vec::new());
+    };
+    let mut summaries: Vec<CaptureSummary> = entries
+        .flatten()
+        .filter_map(|entry| read_capture(&entry.path()).ok())
+        .map(|c| CaptureSummary {
+            id: c.id,
+            timestamp: c.timestamp,
+            method: c.request.method,
+            path: c.request.path,
+            status: c.response.status,
+        })
+        .collect();
+    summaries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(summaries)
+}
+
+/// Structured diff of two captures: headers, JSON bodies field by field, and responses
+#[tauri::command]
+pub fn diff_captures(app: AppHandle, a: String, b: String) -> Result<CaptureDiff, String> {
+    telemetry::count(&app, "feature.diffCaptures");
+    let (a, b) = (load(&app, &a)?, load(&app, &b)?);
+
+    let mut request_line = Vec::new();
+    diff_values(
+        "/method",
+        &Value::String(a.request.method),
+        &Value::String(b.request.method),
+        &mut request_line,
+    );
+    diff_values(
+        "/path",
+        &Value::String(a.request.path),
+        &Value::String(b.request.path),
+        &mut request_line,
+    );
+
+    let mut response_status = Vec::new();
+    diff_values(
+        "/status",
+        &a.response.status.into(),
+        &b.response.status.into(),
+        &mut response_status,
+    );
+
+    Ok(CaptureDiff {
+        request_line,
+        request_headers: diff_headers(&a.request.headers, &b.request.headers),
+        request_body: diff_bodies(
+            a.request.body.as_deref().unwrap_or_default(),
+            b.request.body.as_deref().unwrap_or_default(),
+        ),
+        response_status,
+        response_headers: diff_headers(&a.response.headers, &b.response.headers),
+        response_body: diff_bodies(&a.response.body, &b.response.body),
+    })
+}
+
+fn diff_headers(a: &BTreeMap<String, String>, b: &BTreeMap<String, String>) -> Vec<Change> {
+    let normalize = |headers: &BTreeMap<String, String>| -> serde_json::Map<String, Value> {
+        headers
+            .iter()
+            .map(|(k, v)| (k.to_ascii_lowercase(), Value::String(v.clone())))
+            .filter(|(k, _)| !VOLATILE_HEADERS.contains(&k.as_str()))
+            .collect()
+    };
+    let mut changes = Vec::new();
+    diff_values(
+        "",
+        &Value::Object(normalize(a)),
+        &Value::Object(normalize(b)),
+        &mut changes,
+    );
+    // Header names read better without the pointer slash
+    for change in &mut changes {
+        change.path = change.path.trim_start_matches('/').to_string();
+    }
+    changes
+}
+
+fn diff_bodies(a: &str, b: &str) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_values("", &parse_body(a), &parse_body(b), &mut changes);
+    changes
+}
+
+// JSON as-is, SSE streams as an array of their event payloads, anything else as text
+fn parse_body(body: &str) -> Value {
+    if body.trim().is_empty() {
+        return Value::Null;
+    }
+    if let Ok(json) = serde_json::from_str(body) {
+        return json;
+    }
+    let events: Vec<Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| serde_json::from_str(data.trim()).unwrap_or_else(|_| data.trim().into()))
+        .collect();
+    if events.is_empty() {
+        Value::String(body.to_string())
+    } else {
+        Value::Array(events)
+    }
+}
+
+fn diff_values(path: &str, a: &Value, b: &Value, out: &mut Vec<Change>) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, av) in a {
+                let child = format!("{path}/{}", escape_pointer(key));
+                match b.get(key) {
+                    Some(bv) => diff_values(&child, av, bv, out),
+                    None => out.push(Change {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        before: Some(av.clone()),
+                        after: None,
+                    }),
+                }
+            }
+            for (key, bv) in b.iter().filter(|(k, _)| !a.contains_key(*k)) {
+                out.push(Change {
+                    path: format!("{path}/{}", escape_pointer(key)),
+                    kind: ChangeKind::Added,
+                    before: None,
+                    after: Some(bv.clone()),
+                });
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let child = format!("{path}/{i}");
+                match (a.get(i), b.get(i)) {
+                    (Some(av), Some(bv)) => diff_values(&child, av, bv, out),
+                    (Some(av), None) => out.push(Change {
+                        path: child,
+                        kind: ChangeKind::Removed,
+                        before: Some(av.clone()),
+                        after: None,
+                    }),
+                    (None, Some(bv)) => out.push(Change {
+                        path: child,
+                        kind: ChangeKind::Added,
+                        before: None,
+                        after: Some(bv.clone()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if a != b => out.push(Change {
+            path: path.to_string(),
+            kind: ChangeKind::Changed,
+            before: Some(a.clone()),
+            after: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+// RFC 6901
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+pub fn captures_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(CAPTURES_DIR))
+}
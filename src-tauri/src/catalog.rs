@@ -0,0 +1,246 @@
+// Upstream model catalog. The engine reports per-account quota for every model an
+// account can use; syncing that list lets us spot mappings that point at a model
+// no account offers any more, before requests routed to it start failing.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::PathBuf,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{alerts, api, i18n, mappings, sidecar, traffic};
+
+const CATALOG_FILE: &str = "model-catalog.json";
+const SYNC_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelEntry {
+    /// Accounts that currently report quota for the model
+    accounts: BTreeSet<String>,
+    first_seen: u64,
+    last_seen: u64,
+    /// Set once a model that used to be offered is gone from every account
+    retired: bool,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleMapping {
+    /// Mapping preset name, or None for the engine's live config
+    preset: Option<String>,
+    model: String,
+    target: String,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCatalog {
+    synced_at: Option<u64>,
+    models: BTreeMap<String, ModelEntry>,
+    #[serde(skip_deserializing)]
+    stale_mappings: Vec<StaleMapping>,
+}
+
+impl ModelCatalog {
+    fn offers(&self, model: &str) -> bool {
+        self.models.get(model).is_some_and(|m| !m.retired)
+    }
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Err(e) = sync(&app).await {
+                log::debug!("model catalog sync skipped: {e}");
+            }
+            tokio::time::sleep(SYNC_INTERVAL).await;
+        }
+    });
+}
+
+/// Models known per account, with mappings that point at models no account offers.
+/// Syncs with the engine first when `refresh` is set.
+#[tauri::command]
+pub async fn list_upstream_models(
+    app: AppHandle,
+    refresh: Option<bool>,
+) -> Result<ModelCatalog, String> {
+    if refresh.unwrap_or(false) {
+        sync(&app).await?;
+    }
+    let mut catalog = load(&app);
+    catalog.stale_mappings = stale_mappings(&app, &catalog).await;
+    Ok(catalog)
+}
+
+async fn sync(app: &AppHandle) -> Result<(), String> {
+    let port = sidecar::current_port(app);
+    let limits: Value = api::get(port, "/account-limits").await?;
+    let accounts = limits["accounts"].as_array().ok_or("no account list")?;
+
+    let mut offered: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    // Expired tokens and failed quota reads say nothing about what is offered
+    let mut unreadable = BTreeSet::new();
+    for account in accounts {
+        let Some(email) = account["email"].as_str() else {
+            continue;
+        };
+        let limits = account["limits"].as_object();
+        if limits.is_none() || !account["error"].is_null() {
+            unreadable.insert(email.to_string());
+            continue;
+        }
+        for model in limits.into_iter().flat_map(|l| l.keys()) {
+            offered
+                .entry(model.clone())
+                .or_default()
+                .insert(email.to_string());
+        }
+    }
+    if offered.is_empty() {
+        return Err("no account reported model quota".into());
+    }
+
+    let now = traffic::now_ms();
+    let mut catalog = load(app);
+    let mut newly_retired = Vec::new();
+    for (model, entry) in catalog.models.iter_mut() {
+        match offered.remove(model) {
+            Some(mut accounts) => {
+                // Unreadable accounts keep offering what they offered last time
+                accounts.extend(
+                    entry
+                        .accounts
+                        .iter()
+                        .filter(|a| unreadable.contains(*a))
+                        .cloned(),
+                );
+                entry.accounts = accounts;
+                entry.last_seen = now;
+                entry.retired = false;
+            }
+            // Only unreadable accounts had it; wait until one of them answers again
+            None if !entry.accounts.is_empty()
+                && entry.accounts.iter().all(|a| unreadable.contains(a)) => {}
+            None => {
+                entry.accounts.clear();
+                if !entry.retired {
+                    entry.retired = true;
+                    newly_retired.push(model.clone());
+                }
+            }
+        }
+    }
+    for (model, accounts) in offered {
+        catalog.models.insert(
+            model,
+            ModelEntry {
+                accounts,
+                first_seen: now,
+                last_seen: now,
+                retired: false,
+            },
+        );
+    }
+    catalog.synced_at = Some(now);
+    save(app, &catalog)?;
+
+    catalog.stale_mappings = stale_mappings(app, &catalog).await;
+    let newly_stale: Vec<&StaleMapping> = catalog
+        .stale_mappings
+        .iter()
+        .filter(|m| newly_retired.contains(&m.target))
+        .collect();
+    if !newly_retired.is_empty() {
+        log::warn!("models no longer offered by any account: {newly_retired:?}");
+    }
+    if let Some(first) = newly_stale.first() {
+        let (key, more) = match newly_stale.len() {
+            1 => ("catalog.retiredBody", 0),
+            n => ("catalog.retiredBodyMore", n - 1),
+        };
+        let body = i18n::tf(
+            app,
+            key,
+            &[
+                ("model", first.model.clone()),
+                ("target", first.target.clone()),
+                ("more", more.to_string()),
+            ],
+        );
+        alerts::notify(app, &i18n::t(app, "catalog.retiredTitle"), &body);
+    }
+    let _ = app.emit("model-catalog", &catalog);
+    Ok(())
+}
+
+// Targets in the engine's live config and in every saved preset that the catalog
+// doesn't offer. Unknown targets only count once a sync has happened.
+async fn stale_mappings(app: &AppHandle, catalog: &ModelCatalog) -> Vec<StaleMapping> {
+    if catalog.synced_at.is_none() {
+        return Vec::new();
+    }
+    let mut stale = Vec::new();
+
+    let port = sidecar::current_port(app);
+    if let Ok(config) = api::get::<Value>(port, "/api/config").await {
+        for (model, entry) in config["config"]["modelMapping"]
+            .as_object()
+            .into_iter()
+            .flatten()
+        {
+            if let Some(target) = entry["mapping"].as_str().filter(|t| !t.is_empty()) {
+                if !catalog.offers(target) {
+                    stale.push(StaleMapping {
+                        preset: None,
+                        model: model.clone(),
+                        target: target.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    for (preset, mapping) in mappings::load(app).mappings() {
+        for (model, target) in mapping {
+            if !target.is_empty() && !catalog.offers(target) {
+                stale.push(StaleMapping {
+                    preset: Some(preset.clone()),
+                    model: model.clone(),
+                    target: target.clone(),
+                });
+            }
+        }
+    }
+    stale
+}
+
+fn catalog_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(CATALOG_FILE))
+}
+
+fn load(app: &AppHandle) -> ModelCatalog {
+    catalog_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, catalog: &ModelCatalog) -> Result<(), String> {
+    let path = catalog_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(catalog).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
@@ -0,0 +1,229 @@
+// A/B comparison of two mappings. A captured request is sent twice at the same
+// time, once per target model, and both responses are recorded side by side with
+// latency and token usage for judging which mapping actually does better. The
+// reply text stays in the captures, under their encryption and retention, and is
+// only read back from there when comparisons are listed.
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::{captures, replay, telemetry, traffic};
+
+const COMPARISONS_FILE: &str = "comparisons.json";
+const MAX_COMPARISONS: usize = 100;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArmResult {
+    model: String,
+    status: u16,
+    duration_ms: u64,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    /// Concatenated text blocks of the reply; not saved, see `with_text`
+    #[serde(default)]
+    text: String,
+    capture_id: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    id: String,
+    timestamp: u64,
+    /// Capture the request was taken from
+    source_capture: String,
+    a: ArmResult,
+    b: ArmResult,
+}
+
+/// Send the request of `capture_id` to `model_a` and `model_b` in parallel and record both
+#[tauri::command]
+pub async fn compare_mappings(
+    app: AppHandle,
+    capture_id: String,
+    model_a: String,
+    model_b: String,
+) -> Result<Comparison, String> {
+    telemetry::count(&app, "feature.compareMappings");
+    let source = captures::load(&app, &capture_id)?;
+    let body = source
+        .request
+        .body
+        .as_deref()
+        .ok_or("The capture has no request body (bodies are not being stored)")?;
+    let body: Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    if !body.is_object() {
+        return Err("The captured request body is not a JSON object".into());
+    }
+
+    log::info!("comparing {model_a} and {model_b} on capture {capture_id}");
+    let (a, b) = tokio::join!(
+        run_arm(&app, &source.request, &body, &model_a),
+        run_arm(&app, &source.request, &body, &model_b),
+    );
+
+    let timestamp = traffic::now_ms();
+    let comparison = Comparison {
+        id: timestamp.to_string(),
+        timestamp,
+        source_capture: capture_id,
+        a,
+        b,
+    };
+    let mut all = load(&app);
+    all.insert(0, comparison.clone());
+    all.truncate(MAX_COMPARISONS);
+    save(&app, &all)?;
+    Ok(comparison)
+}
+
+/// Recorded comparisons, newest first
+#[tauri::command]
+pub fn list_comparisons(app: AppHandle) -> Vec<Comparison> {
+    load(&app)
+        .into_iter()
+        .map(|mut c| {
+            c.a = with_text(&app, c.a);
+            c.b = with_text(&app, c.b);
+            c
+        })
+        .collect()
+}
+
+// Empty once the capture is deleted, expired or saved without bodies
+fn with_text(app: &AppHandle, mut arm: ArmResult) -> ArmResult {
+    arm.text = arm
+        .capture_id
+        .as_deref()
+        .and_then(|id| captures::load(app, id).ok())
+        .map(|capture| read_reply(&capture.response.body).2)
+        .unwrap_or_default();
+    arm
+}
+
+async fn run_arm(
+    app: &AppHandle,
+    original: &captures::CapturedRequest,
+    body: &Value,
+    model: &str,
+) -> ArmResult {
+    let mut body = body.clone();
+    body["model"] = model.into();
+    // The body changes length, and the rest describes the original connection
+    let request = captures::CapturedRequest {
+        method: original.method.clone(),
+        path: original.path.clone(),
+        headers: original
+            .headers
+            .iter()
+            .filter(|(name, _)| {
+                !replay::DROPPED_HEADERS.contains(&name.to_ascii_lowercase().as_str())
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+        body: Some(body.to_string()),
+    };
+
+    match replay::send(app, request).await {
+        Ok((response, capture_id)) => {
+            let (input_tokens, output_tokens, text) = read_reply(&response.body);
+            ArmResult {
+                model: model.to_string(),
+                status: response.status,
+                duration_ms: response.duration_ms,
+                input_tokens,
+                output_tokens,
+                text,
+                capture_id,
+                error: None,
+            }
+        }
+        Err(e) => ArmResult {
+            model: model.to_string(),
+            error: Some(e),
+            ..Default::default()
+        },
+    }
+}
+
+/// Usage and text from a plain JSON reply or from the events of a streamed one
+pub fn read_reply(body: &str) -> (Option<u64>, Option<u64>, String) {
+    if let Ok(message) = serde_json::from_str::<Value>(body) {
+        return (
+            message
+                .pointer("/usage/input_tokens")
+                .and_then(Value::as_u64),
+            message
+                .pointer("/usage/output_tokens")
+                .and_then(Value::as_u64),
+            message["content"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|block| block["text"].as_str())
+                .collect(),
+        );
+    }
+
+    let (mut input, mut output, mut text) = (None, None, String::new());
+    let events = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok());
+    for event in events {
+        match event["type"].as_str() {
+            Some("message_start") => {
+                input = event
+                    .pointer("/message/usage/input_tokens")
+                    .and_then(Value::as_u64);
+            }
+            Some("content_block_delta") => {
+                text.extend(event.pointer("/delta/text").and_then(Value::as_str));
+            }
+            // Carries the running output total; the last one wins
+            Some("message_delta") => {
+                output = event
+                    .pointer("/usage/output_tokens")
+                    .and_then(Value::as_u64)
+                    .or(output);
+            }
+            _ => {}
+        }
+    }
+    (input, output, text)
+}
+
+fn comparisons_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(COMPARISONS_FILE))
+}
+
+fn load(app: &AppHandle) -> Vec<Comparison> {
+    comparisons_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, comparisons: &[Comparison]) -> Result<(), String> {
+    let path = comparisons_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    // Files written before the text moved out still carry it
+    let mut comparisons = comparisons.to_vec();
+    for c in &mut comparisons {
+        c.a.text.clear();
+        c.b.text.clear();
+    }
+    let json = serde_json::to_string_pretty(&comparisons).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
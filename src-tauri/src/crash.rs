@@ -0,0 +1,214 @@
+// Opt-in crash reports. A panic hook writes a scrubbed report to app data; nothing
+// leaves the machine unless the user opens the prefilled issue and attaches it.
+use std::{
+    backtrace::Backtrace,
+    fs,
+    panic::{self, PanicHookInfo},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::{alerts, i18n, lock, redact, settings, traffic};
+
+const CRASH_DIR: &str = "crashes";
+// Written next to a new report, removed once the user has been told about it
+const UNSEEN_MARKER: &str = "unseen";
+const MAX_LOG_TAIL_BYTES: usize = 32 * 1024;
+const ISSUE_URL: &str = "https://github.com/StRonKEA/Antigravity-Claude-Proxy-GUI/issues/new";
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    id: String,
+    timestamp: u64,
+    app_version: String,
+    os: String,
+    arch: String,
+    thread: String,
+    message: String,
+    location: Option<String>,
+    backtrace: String,
+    log_tail: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashSummary {
+    id: String,
+    timestamp: u64,
+    message: String,
+    location: Option<String>,
+}
+
+fn crash_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(CRASH_DIR))
+}
+
+fn consented(app: &AppHandle) -> bool {
+    settings::load(app)
+        .get("crashReports")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Chain a hook in front of the default one; it only writes when crash reports are enabled
+pub fn install(app: &AppHandle) {
+    let app = app.clone();
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if consented(&app) {
+            match write_report(&app, info) {
+                Ok(path) => log::error!("crash report written to {}", path.display()),
+                Err(e) => log::error!("failed to write crash report: {e}"),
+            }
+        }
+        previous(info);
+    }));
+}
+
+fn write_report(app: &AppHandle, info: &PanicHookInfo) -> Result<PathBuf, String> {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic without a message".into());
+    let timestamp = traffic::now_ms();
+    let id = format!("crash-{timestamp}");
+
+    let report = CrashReport {
+        id: id.clone(),
+        timestamp,
+        app_version: app.package_info().version.to_string(),
+        os: std::env::consts::OS.into(),
+        arch: std::env::consts::ARCH.into(),
+        thread: std::thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .to_string(),
+        message: redact::scrub_text(&message),
+        location: info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+        backtrace: Backtrace::force_capture().to_string(),
+        log_tail: log_tail(app),
+    };
+
+    let dir = crash_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("{id}.json"));
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    let _ = fs::write(dir.join(UNSEEN_MARKER), &id);
+    Ok(path)
+}
+
+// End of the newest log file, which usually shows what led up to the panic
+fn log_tail(app: &AppHandle) -> String {
+    let newest = app
+        .path()
+        .app_log_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "log"))
+        .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok());
+    let Some(content) = newest.and_then(|e| fs::read(e.path()).ok()) else {
+        return String::new();
+    };
+    let tail = &content[content.len().saturating_sub(MAX_LOG_TAIL_BYTES)..];
+    redact::scrub_text(&String::from_utf8_lossy(tail))
+}
+
+/// Tell the user about a report left behind by the previous run
+pub fn notify_unseen(app: &AppHandle) {
+    let Ok(marker) = crash_dir(app).map(|d| d.join(UNSEEN_MARKER)) else {
+        return;
+    };
+    if fs::remove_file(&marker).is_ok() {
+        alerts::notify(
+            app,
+            &i18n::t(app, "crash.title"),
+            &i18n::t(app, "crash.body"),
+        );
+    }
+}
+
+fn read_report(app: &AppHandle, id: &str) -> Result<CrashReport, String> {
+    if id.contains(['/', '\\']) || id.contains("..") {
+        return Err("Invalid crash report id".into());
+    }
+    let path = crash_dir(app)?.join(format!("{id}.json"));
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// Saved crash reports, newest first
+#[tauri::command]
+pub fn list_crash_reports(app: AppHandle) -> Result<Vec<CrashSummary>, String> {
+    let Ok(entries) = fs::read_dir(crash_dir(&app)?) else {
+        return Ok(Vec::new());
+    };
+    let mut reports: Vec<CrashSummary> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            let report = read_report(&app, name.strip_suffix(".json")?).ok()?;
+            Some(CrashSummary {
+                id: report.id,
+                timestamp: report.timestamp,
+                message: report.message,
+                location: report.location,
+            })
+        })
+        .collect();
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(reports)
+}
+
+/// Open a prefilled GitHub issue and show the report file so it can be attached
+#[tauri::command]
+pub fn report_crash(app: AppHandle, id: String) -> Result<(), String> {
+    let report = read_report(&app, &id)?;
+    let body = format!(
+        "**What were you doing when the app closed?**\n\n\n\n---\n- App: {} ({}/{})\n- Panic: `{}`\n- At: `{}`\n\nPlease attach `{id}.json` from the folder that just opened.",
+        report.app_version,
+        report.os,
+        report.arch,
+        report.message,
+        report.location.as_deref().unwrap_or("unknown"),
+    );
+
+    let mut url = reqwest::Url::parse(ISSUE_URL).expect("valid issue url");
+    url.query_pairs_mut()
+        .append_pair("title", &format!("Crash: {}", report.message))
+        .append_pair("body", &body);
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| e.to_string())?;
+
+    let path = crash_dir(&app)?.join(format!("{id}.json"));
+    app.opener()
+        .reveal_item_in_dir(path)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_crash_reports(app: AppHandle) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    match fs::remove_dir_all(crash_dir(&app)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
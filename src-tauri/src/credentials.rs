@@ -0,0 +1,165 @@
+// Credential expiry tracking per account, with notification and tray warning ahead of time
+use std::{collections::HashSet, fs, sync::Mutex, time::Duration};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    alerts,
+    api::{self, AccountLimits},
+    i18n, settings, sidecar, traffic, tray,
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+// Written by the engine when accounts are added
+const ACCOUNTS_FILE: &str = ".config/antigravity-proxy/accounts.json";
+const DEFAULT_WARNING_HOURS: u64 = 24;
+const HOUR_MS: u64 = 60 * 60 * 1000;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialWarning {
+    email: String,
+    /// Unix ms; None when the credential is already rejected upstream
+    expires_at: Option<u64>,
+    expired: bool,
+}
+
+#[derive(Default)]
+struct CredentialInner {
+    warnings: Vec<CredentialWarning>,
+    notified: HashSet<String>,
+}
+
+#[derive(Default)]
+pub struct CredentialState(Mutex<CredentialInner>);
+
+pub fn warnings(app: &AppHandle) -> Vec<CredentialWarning> {
+    app.state::<CredentialState>()
+        .0
+        .lock()
+        .unwrap()
+        .warnings
+        .clone()
+}
+
+#[tauri::command]
+pub fn get_credential_warnings(app: AppHandle) -> Vec<CredentialWarning> {
+    warnings(&app)
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            check(&app).await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+pub async fn check(app: &AppHandle) {
+    let settings = settings::load(app);
+    // Refresh tokens only expire when an org enforces re-authentication, so the
+    // lifetime is opt-in; explicit expiry data from the engine always wins
+    let lifetime_ms =
+        settings::get_u64(&settings, "credentialLifetimeDays").map(|d| d * 24 * HOUR_MS);
+    let warn_ms = settings::get_u64(&settings, "credentialWarningHours")
+        .unwrap_or(DEFAULT_WARNING_HOURS)
+        * HOUR_MS;
+    let now = traffic::now_ms();
+
+    let mut found: Vec<CredentialWarning> = read_accounts(app)
+        .into_iter()
+        .filter_map(|account| {
+            let email = account.get("email")?.as_str()?.to_string();
+            let expires_at = timestamp(account.get("expiresAt"))
+                .or_else(|| Some(timestamp(account.get("addedAt"))? + lifetime_ms?))?;
+            (expires_at <= now + warn_ms).then_some(CredentialWarning {
+                email,
+                expires_at: Some(expires_at),
+                expired: expires_at <= now,
+            })
+        })
+        .collect();
+
+    // Accounts the engine already can't refresh
+    if let Ok(limits) =
+        api::get::<AccountLimits>(sidecar::current_port(app), "/account-limits").await
+    {
+        for account in limits.accounts {
+            let rejected = account.status == "invalid"
+                || account
+                    .error
+                    .as_deref()
+                    .is_some_and(|e| e.contains("invalid_grant"));
+            if rejected && !found.iter().any(|w| w.email == account.email) {
+                found.push(CredentialWarning {
+                    email: account.email,
+                    expires_at: None,
+                    expired: true,
+                });
+            }
+        }
+    }
+
+    let fresh: Vec<CredentialWarning> = {
+        let state = app.state::<CredentialState>();
+        let mut inner = state.0.lock().unwrap();
+        inner
+            .notified
+            .retain(|email| found.iter().any(|w| &w.email == email));
+        let fresh = found
+            .iter()
+            .filter(|w| !inner.notified.contains(&w.email))
+            .cloned()
+            .collect();
+        for w in &found {
+            inner.notified.insert(w.email.clone());
+        }
+        inner.warnings = found.clone();
+        fresh
+    };
+
+    if !fresh.is_empty() {
+        let names: Vec<&str> = fresh.iter().map(|w| w.email.as_str()).collect();
+        alerts::notify(
+            app,
+            &i18n::t(app, "credentials.title"),
+            &i18n::tf(app, "credentials.body", &[("accounts", names.join(", "))]),
+        );
+    }
+
+    let _ = app.emit("credential-expiry", &found);
+    tray::refresh(app);
+}
+
+fn read_accounts(app: &AppHandle) -> Vec<serde_json::Map<String, Value>> {
+    let Ok(home) = app.path().home_dir() else {
+        return Vec::new();
+    };
+    let content = fs::read_to_string(home.join(ACCOUNTS_FILE)).unwrap_or_default();
+    let parsed: Value = serde_json::from_str(&content).unwrap_or(Value::Null);
+
+    // Either { "accounts": [...] } or a bare array depending on engine version
+    let list = parsed.get("accounts").unwrap_or(&parsed);
+    list.as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_object().cloned())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Engine timestamps are either unix ms or RFC 3339 strings
+fn timestamp(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .and_then(|d| u64::try_from(d.timestamp_millis()).ok()),
+        _ => None,
+    }
+}
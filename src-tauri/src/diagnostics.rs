@@ -0,0 +1,447 @@
+// Self-diagnostics: the checks behind "is it me, my network or the backend?"
+use std::{
+    fs,
+    io::Write,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+    api::{self, AccountLimits},
+    engine, redact, settings, telemetry,
+};
+
+// Hosts the engine talks to for completions and token refresh
+const UPSTREAM_HOSTS: [&str; 2] = ["cloudcode-pa.googleapis.com", "oauth2.googleapis.com"];
+const NET_TIMEOUT: Duration = Duration::from_secs(5);
+// Token signatures start failing well before this, but it's a safe line to warn on
+const MAX_CLOCK_SKEW_SECS: u64 = 60;
+
+// Only the tail of each log file goes into a bundle
+const MAX_LOG_BYTES: usize = 1024 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    id: &'static str,
+    status: CheckStatus,
+    detail: String,
+    duration_ms: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    passed: bool,
+    checks: Vec<CheckResult>,
+}
+
+/// Run every check and return a pass/fail checklist
+#[tauri::command]
+pub async fn run_diagnostics(port: u16) -> DiagnosticsReport {
+    let mut checks = Vec::new();
+
+    let (result, addrs) = timed("dns", resolve_upstreams).await;
+    checks.push(result);
+
+    if addrs.is_empty() {
+        for id in ["reachability", "tls", "clock"] {
+            checks.push(skipped(id, "DNS resolution failed"));
+        }
+    } else {
+        checks.push(
+            timed("reachability", move || (check_reachability(&addrs), ()))
+                .await
+                .0,
+        );
+        let (tls, server_date) = check_tls().await;
+        checks.push(tls);
+        checks.push(check_clock(server_date));
+    }
+
+    checks.push(check_port(port).await);
+    checks.push(check_auth(port).await);
+
+    DiagnosticsReport {
+        passed: checks.iter().all(|c| c.status != CheckStatus::Fail),
+        checks,
+    }
+}
+
+// Run a blocking check off the async runtime and stamp how long it took
+async fn timed<T, F>(id: &'static str, f: F) -> (CheckResult, T)
+where
+    T: Send + 'static,
+    F: FnOnce() -> ((CheckStatus, String), T) + Send + 'static,
+{
+    let started = Instant::now();
+    let ((status, detail), value) = tauri::async_runtime::spawn_blocking(f)
+        .await
+        .expect("diagnostic check panicked");
+
+    (
+        CheckResult {
+            id,
+            status,
+            detail,
+            duration_ms: started.elapsed().as_millis() as u64,
+        },
+        value,
+    )
+}
+
+fn skipped(id: &'static str, reason: &str) -> CheckResult {
+    CheckResult {
+        id,
+        status: CheckStatus::Skip,
+        detail: reason.to_string(),
+        duration_ms: 0,
+    }
+}
+
+pub fn resolve_upstreams() -> ((CheckStatus, String), Vec<SocketAddr>) {
+    let mut addrs = Vec::new();
+    let mut failed = Vec::new();
+
+    for host in UPSTREAM_HOSTS {
+        match (host, 443).to_socket_addrs() {
+            Ok(resolved) => addrs.extend(resolved.take(1)),
+            Err(e) => failed.push(format!("{host}: {e}")),
+        }
+    }
+
+    let result = if failed.is_empty() {
+        (
+            CheckStatus::Pass,
+            format!("Resolved {}", UPSTREAM_HOSTS.join(", ")),
+        )
+    } else {
+        (CheckStatus::Fail, failed.join("; "))
+    };
+    (result, addrs)
+}
+
+fn check_reachability(addrs: &[SocketAddr]) -> (CheckStatus, String) {
+    let unreachable: Vec<String> = addrs
+        .iter()
+        .filter_map(|addr| {
+            TcpStream::connect_timeout(addr, NET_TIMEOUT)
+                .err()
+                .map(|e| format!("{addr}: {e}"))
+        })
+        .collect();
+
+    if unreachable.is_empty() {
+        (
+            CheckStatus::Pass,
+            "Upstream accepts connections on 443".into(),
+        )
+    } else {
+        (CheckStatus::Fail, unreachable.join("; "))
+    }
+}
+
+// Any HTTP response means the handshake went through; keep the Date header for the clock check
+async fn check_tls() -> (CheckResult, Option<SystemTime>) {
+    let started = Instant::now();
+    let client = reqwest::Client::builder()
+        .timeout(NET_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+
+    let (status, detail, date) = match client
+        .head(format!("https://{}/", UPSTREAM_HOSTS[0]))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            let date = response
+                .headers()
+                .get("date")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| httpdate::parse_http_date(v).ok());
+            (
+                CheckStatus::Pass,
+                format!("HTTP {}", response.status()),
+                date,
+            )
+        }
+        Err(e) => (CheckStatus::Fail, e.to_string(), None),
+    };
+
+    (
+        CheckResult {
+            id: "tls",
+            status,
+            detail,
+            duration_ms: started.elapsed().as_millis() as u64,
+        },
+        date,
+    )
+}
+
+fn check_clock(server_date: Option<SystemTime>) -> CheckResult {
+    let Some(server_date) = server_date else {
+        return skipped("clock", "Upstream did not send a Date header");
+    };
+
+    let now = SystemTime::now();
+    let skew = now
+        .duration_since(server_date)
+        .or_else(|_| server_date.duration_since(now))
+        .unwrap_or_default()
+        .as_secs();
+
+    CheckResult {
+        id: "clock",
+        status: if skew > MAX_CLOCK_SKEW_SECS {
+            CheckStatus::Fail
+        } else {
+            CheckStatus::Pass
+        },
+        detail: format!("System clock is {skew}s off upstream time"),
+        duration_ms: 0,
+    }
+}
+
+// The port is fine if our engine answers on it, or if nothing else holds it
+async fn check_port(port: u16) -> CheckResult {
+    let started = Instant::now();
+    let engine_up = api::get::<serde_json::Value>(port, "/health").await.is_ok();
+
+    let (status, detail) = if engine_up {
+        (
+            CheckStatus::Pass,
+            format!("Proxy is answering on port {port}"),
+        )
+    } else {
+        match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(_) => (CheckStatus::Pass, format!("Port {port} is free")),
+            Err(e) => (
+                CheckStatus::Fail,
+                format!("Port {port} is held by another process: {e}"),
+            ),
+        }
+    };
+
+    CheckResult {
+        id: "port",
+        status,
+        detail,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+async fn check_auth(port: u16) -> CheckResult {
+    let started = Instant::now();
+    let (status, detail) = match api::get::<AccountLimits>(port, "/account-limits").await {
+        Err(_) => (CheckStatus::Skip, "Proxy is not running".to_string()),
+        Ok(limits) => {
+            let broken: Vec<String> = limits
+                .accounts
+                .iter()
+                .filter(|a| a.enabled && matches!(a.status.as_str(), "error" | "invalid"))
+                .map(|a| match &a.error {
+                    Some(e) => format!("{}: {e}", a.email),
+                    None => a.email.clone(),
+                })
+                .collect();
+
+            if limits.accounts.iter().all(|a| !a.enabled) {
+                (CheckStatus::Fail, "No enabled accounts".to_string())
+            } else if broken.is_empty() {
+                (
+                    CheckStatus::Pass,
+                    format!("{} account(s) valid", limits.accounts.len()),
+                )
+            } else {
+                (CheckStatus::Warn, broken.join("; "))
+            }
+        }
+    };
+
+    CheckResult {
+        id: "auth",
+        status,
+        detail,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Zip scrubbed logs, settings, version info and a fresh diagnostics report into
+/// the Downloads folder, returning the bundle path
+#[tauri::command]
+pub async fn export_diagnostics(app: AppHandle, port: u16) -> Result<String, String> {
+    telemetry::count(&app, "feature.exportDiagnostics");
+    let report = run_diagnostics(port).await;
+    let versions = json!({
+        "app": app.package_info().version.to_string(),
+        "engine": engine::installed_version(&app).await,
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+    });
+
+    let mut app_settings = fs::read_to_string(settings::settings_path(&app)?)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or(Value::Null);
+    redact::scrub_json(&mut app_settings);
+
+    let mut engine_config = api::get::<Value>(port, "/api/config")
+        .await
+        .unwrap_or(Value::Null);
+    redact::scrub_json(&mut engine_config);
+
+    let mut entries = vec![
+        ("report.json".to_string(), to_pretty(&report)?),
+        ("versions.json".to_string(), to_pretty(&versions)?),
+        ("settings.json".to_string(), to_pretty(&app_settings)?),
+        ("engine-config.json".to_string(), to_pretty(&engine_config)?),
+    ];
+    entries.extend(collect_logs(&app)?);
+
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = app
+        .path()
+        .download_dir()
+        .map_err(|e| e.to_string())?
+        .join(format!("acp-diagnostics-{stamp}.zip"));
+
+    write_zip(&path, &entries).map_err(|e| e.to_string())?;
+    log::info!("diagnostics bundle written to {}", path.display());
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn to_pretty<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+fn collect_logs(app: &AppHandle) -> Result<Vec<(String, String)>, String> {
+    let dir = app.path().app_log_dir().map_err(|e| e.to_string())?;
+    let Ok(files) = fs::read_dir(dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut logs = Vec::new();
+    for file in files.flatten() {
+        let path = file.path();
+        if path.extension().is_none_or(|ext| ext != "log") {
+            continue;
+        }
+        let Ok(content) = fs::read(&path) else {
+            continue;
+        };
+
+        let tail = &content[content.len().saturating_sub(MAX_LOG_BYTES)..];
+        let name = format!("logs/{}", file.file_name().to_string_lossy());
+        logs.push((name, redact::scrub_text(&String::from_utf8_lossy(tail))));
+    }
+    Ok(logs)
+}
+
+fn write_zip(path: &Path, entries: &[(String, String)]) -> zip::result::ZipResult<()> {
+    let mut zip = ZipWriter::new(fs::File::create(path)?);
+    for (name, content) in entries {
+        zip.start_file(name.as_str(), SimpleFileOptions::default())?;
+        zip.write_all(content.as_bytes())?;
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPing {
+    email: String,
+    latency_ms: u64,
+    ok: bool,
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingReport {
+    /// GUI -> local proxy round trip
+    proxy_ms: Option<u64>,
+    /// GUI -> upstream host round trip, bypassing the proxy
+    network_ms: Option<u64>,
+    accounts: Vec<AccountPing>,
+}
+
+/// Measure proxy, network and per-account upstream round trips. The per-account
+/// request is a token refresh, the smallest call the engine makes on an account's behalf.
+#[tauri::command]
+pub async fn ping_upstream(port: u16, account: Option<String>) -> Result<PingReport, String> {
+    let started = Instant::now();
+    let proxy_ms = api::get::<Value>(port, "/health")
+        .await
+        .ok()
+        .map(|_| started.elapsed().as_millis() as u64);
+
+    let (tls, _) = check_tls().await;
+    let network_ms = (tls.status == CheckStatus::Pass).then_some(tls.duration_ms);
+
+    let emails = match account {
+        Some(email) => vec![email],
+        None => api::get::<AccountLimits>(port, "/account-limits")
+            .await?
+            .accounts
+            .into_iter()
+            .filter(|a| a.enabled)
+            .map(|a| a.email)
+            .collect(),
+    };
+
+    let mut accounts = Vec::with_capacity(emails.len());
+    for email in emails {
+        let started = Instant::now();
+        let result: Result<Value, String> = api::post(
+            port,
+            &api::account_path(&email, Some("refresh")),
+            &json!({}),
+        )
+        .await;
+        let error = match result {
+            Ok(body) if body["status"] == "ok" => None,
+            Ok(body) => Some(
+                body["error"]
+                    .as_str()
+                    .unwrap_or("refresh rejected")
+                    .to_string(),
+            ),
+            Err(e) => Some(e),
+        };
+
+        accounts.push(AccountPing {
+            email,
+            latency_ms: started.elapsed().as_millis() as u64,
+            ok: error.is_none(),
+            error,
+        });
+    }
+
+    Ok(PingReport {
+        proxy_ms,
+        network_ms,
+        accounts,
+    })
+}
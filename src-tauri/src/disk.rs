@@ -0,0 +1,225 @@
+// Disk usage of the app's own files and free space on the drive holding them.
+// A debug-level engine log can grow by gigabytes in a day, so both are checked
+// periodically and warned about once per crossing ("disk-warning").
+use std::{
+    fs::{self, OpenOptions},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+use sysinfo::Disks;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{alerts, captures, i18n, idle, lock, scheduler, settings};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_WARN_MB: u64 = 1024;
+const DEFAULT_MIN_FREE_MB: u64 = 1024;
+const MB: u64 = 1024 * 1024;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    /// "logs", "captures", "reports", "cache" or "other"
+    name: &'static str,
+    path: String,
+    bytes: u64,
+    /// Whether clean_app_data can empty it
+    cleanable: bool,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    locations: Vec<Location>,
+    total_bytes: u64,
+    /// Free and total space of the drive holding app data, when it could be found
+    free_bytes: Option<u64>,
+    disk_bytes: Option<u64>,
+    /// Set when over the size limit or under the free space minimum
+    warning: Option<String>,
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || {
+        let mut warned = false;
+        loop {
+            let usage = usage(&app);
+            match (&usage.warning, warned) {
+                (Some(warning), false) => {
+                    warned = true;
+                    log::warn!("{warning}");
+                    alerts::notify(&app, &i18n::t(&app, "disk.title"), warning);
+                    let _ = app.emit("disk-warning", usage);
+                }
+                (None, true) => warned = false,
+                _ => {}
+            }
+            idle::pause(&app, CHECK_INTERVAL);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_disk_usage(app: AppHandle) -> DiskUsage {
+    usage(&app)
+}
+
+/// Empty the named locations (all cleanable ones when `names` is empty) and
+/// return the usage afterwards. The current log is truncated rather than deleted,
+/// since the logger keeps writing to it.
+#[tauri::command]
+pub fn clean_app_data(app: AppHandle, names: Vec<String>) -> Result<DiskUsage, String> {
+    lock::ensure_unlocked(&app)?;
+    let before = usage(&app);
+    for location in before.locations.iter().filter(|l| l.cleanable) {
+        if names.is_empty() || names.iter().any(|n| n == location.name) {
+            let dir = Path::new(&location.path);
+            if location.name == "logs" {
+                truncate_newest(dir);
+            }
+            empty_dir(dir);
+        }
+    }
+    let after = usage(&app);
+    log::info!(
+        "cleaned app data: {} MiB freed",
+        before.total_bytes.saturating_sub(after.total_bytes) / MB
+    );
+    Ok(after)
+}
+
+fn usage(app: &AppHandle) -> DiskUsage {
+    let path = app.path();
+    let data_dir = path.app_data_dir().ok();
+    let mut locations = Vec::new();
+    let mut add = |name, dir: Result<PathBuf, String>| {
+        if let Ok(dir) = dir {
+            locations.push(Location {
+                name,
+                bytes: dir_size(&dir),
+                path: dir.display().to_string(),
+                cleanable: true,
+            });
+        }
+    };
+    add("logs", path.app_log_dir().map_err(|e| e.to_string()));
+    add("captures", captures::captures_dir(app));
+    add("reports", scheduler::reports_dir(app));
+    add("cache", path.app_cache_dir().map_err(|e| e.to_string()));
+
+    // Everything else under app data: engine backups, app state, crash reports
+    if let Some(data_dir) = &data_dir {
+        let nested: u64 = locations
+            .iter()
+            .filter(|l| Path::new(&l.path).starts_with(data_dir))
+            .map(|l| l.bytes)
+            .sum();
+        locations.push(Location {
+            name: "other",
+            path: data_dir.display().to_string(),
+            bytes: dir_size(data_dir).saturating_sub(nested),
+            cleanable: false,
+        });
+    }
+
+    let total_bytes = locations.iter().map(|l| l.bytes).sum();
+    let disk = data_dir.as_deref().and_then(disk_for);
+    let free_bytes = disk.map(|(free, _)| free);
+
+    let settings = settings::load(app);
+    let warn_mb = settings::get_u64(&settings, "diskWarnMb").unwrap_or(DEFAULT_WARN_MB);
+    let min_free_mb = settings::get_u64(&settings, "diskMinFreeMb").unwrap_or(DEFAULT_MIN_FREE_MB);
+    let warning = if warn_mb > 0 && total_bytes > warn_mb * MB {
+        Some(i18n::tf(
+            app,
+            "disk.tooLarge",
+            &[("size", (total_bytes / MB).to_string())],
+        ))
+    } else if free_bytes.is_some_and(|free| free < min_free_mb * MB) {
+        Some(i18n::tf(
+            app,
+            "disk.lowSpace",
+            &[("free", (free_bytes.unwrap_or_default() / MB).to_string())],
+        ))
+    } else {
+        None
+    };
+
+    DiskUsage {
+        locations,
+        total_bytes,
+        free_bytes,
+        disk_bytes: disk.map(|(_, total)| total),
+        warning,
+    }
+}
+
+// (available, total) of the mount with the longest prefix of `path`
+fn disk_for(path: &Path) -> Option<(u64, u64)> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| (d.available_space(), d.total_space()))
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(m) if m.is_dir() => dir_size(&entry.path()),
+            Ok(m) => m.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+// Removes everything except empty files, which are left for whoever holds them open
+fn empty_dir(path: &Path) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.metadata() {
+            Ok(m) if m.is_dir() => {
+                empty_dir(&path);
+                let _ = fs::remove_dir(&path);
+            }
+            Ok(m) if m.len() == 0 => {}
+            _ => {
+                if fs::remove_file(&path).is_err() {
+                    // Still open elsewhere on Windows; an empty file frees the space too
+                    truncate(&path);
+                }
+            }
+        }
+    }
+}
+
+fn truncate_newest(dir: &Path) {
+    let newest = fs::read_dir(dir).ok().and_then(|entries| {
+        entries
+            .flatten()
+            .filter(|e| e.path().is_file())
+            .max_by_key(|e| e.metadata().and_then(|m| m.modified()).ok())
+    });
+    if let Some(entry) = newest {
+        truncate(&entry.path());
+    }
+}
+
+fn truncate(path: &Path) {
+    let _ = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|f| f.set_len(0));
+}
@@ -0,0 +1,220 @@
+// Alert emails over SMTP, for teams whose on-call flow only watches a mailbox.
+// Sent for events someone has to act on: the engine staying down for several
+// minutes (and coming back), and bursts of failing requests. Server settings live
+// in settings.json (smtpHost, smtpPort, smtpSecurity, smtpUsername, smtpFrom,
+// smtpTo); the password is kept in the OS keychain.
+use std::time::{Duration, Instant};
+
+use lettre::{
+    message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::{api, i18n, lock, settings, sidecar::SidecarState, vault};
+
+const KEYCHAIN_USER: &str = "smtp-password";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const DOWN_ALERT_AFTER: Duration = Duration::from_secs(5 * 60);
+const SEND_TIMEOUT: Duration = Duration::from_secs(20);
+
+enum Security {
+    /// Implicit TLS, usually port 465
+    Tls,
+    /// Plain connection upgraded with STARTTLS, usually port 587
+    StartTls,
+    /// No encryption, e.g. a relay on the local network
+    None,
+}
+
+struct SmtpConfig {
+    host: String,
+    port: Option<u16>,
+    security: Security,
+    username: Option<String>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+/// Email an alert in the background if SMTP is set up. Failures are only logged.
+pub fn alert(app: &AppHandle, subject: &str, body: &str) {
+    let (app, subject, body) = (app.clone(), subject.to_string(), body.to_string());
+    tauri::async_runtime::spawn(async move {
+        match send(&app, &subject, &body).await {
+            Ok(true) => log::info!("alert email sent: {subject}"),
+            Ok(false) => {}
+            Err(e) => log::error!("failed to send alert email: {e}"),
+        }
+    });
+}
+
+/// Store the SMTP password in the keychain, or remove it with `None`
+#[tauri::command]
+pub fn set_smtp_password(app: AppHandle, password: Option<String>) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    match password.filter(|p| !p.is_empty()) {
+        Some(password) => entry()?.set_password(&password).map_err(|e| e.to_string()),
+        None => delete_smtp_password(),
+    }
+}
+
+/// Remove the SMTP password from the keychain
+pub fn delete_smtp_password() -> Result<(), String> {
+    match entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn send_test_email(app: AppHandle) -> Result<(), String> {
+    let sent = send(
+        &app,
+        &i18n::t(&app, "email.testTitle"),
+        &i18n::t(&app, "email.testBody"),
+    )
+    .await?;
+    if sent {
+        Ok(())
+    } else {
+        Err("Email alerts are not set up (smtpHost and smtpTo are required)".into())
+    }
+}
+
+/// Watch engine health and email when it has been down for a while
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut down_since: Option<Instant> = None;
+        let mut alerted = false;
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            // Only an engine that is supposed to be running can be down
+            let Some(launch) = app.state::<SidecarState>().launch() else {
+                (down_since, alerted) = (None, false);
+                continue;
+            };
+
+            if api::get::<Value>(launch.port, "/health").await.is_ok() {
+                if alerted {
+                    alert(
+                        &app,
+                        &i18n::t(&app, "email.upTitle"),
+                        &i18n::tf(&app, "email.upBody", &[("port", launch.port.to_string())]),
+                    );
+                }
+                (down_since, alerted) = (None, false);
+                continue;
+            }
+
+            let since = *down_since.get_or_insert_with(Instant::now);
+            if !alerted && since.elapsed() >= DOWN_ALERT_AFTER {
+                alerted = true;
+                alert(
+                    &app,
+                    &i18n::t(&app, "email.downTitle"),
+                    &i18n::tf(
+                        &app,
+                        "email.downBody",
+                        &[
+                            ("port", launch.port.to_string()),
+                            ("minutes", (since.elapsed().as_secs() / 60).to_string()),
+                        ],
+                    ),
+                );
+            }
+        }
+    });
+}
+
+// Ok(false) when SMTP isn't configured
+async fn send(app: &AppHandle, subject: &str, body: &str) -> Result<bool, String> {
+    let Some(config) = config(app)? else {
+        return Ok(false);
+    };
+
+    let mut transport = match config.security {
+        Security::Tls => {
+            AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host).map_err(|e| e.to_string())?
+        }
+        Security::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+            .map_err(|e| e.to_string())?,
+        Security::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+    }
+    .timeout(Some(SEND_TIMEOUT));
+    if let Some(port) = config.port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = config.username {
+        let password = match entry()?.get_password() {
+            Ok(password) => password,
+            Err(keyring::Error::NoEntry) => String::new(),
+            Err(e) => return Err(e.to_string()),
+        };
+        transport = transport.credentials(Credentials::new(username, password));
+    }
+
+    let mut message = Message::builder().from(config.from).subject(subject);
+    for to in config.to {
+        message = message.to(to);
+    }
+    let message = message.body(body.to_string()).map_err(|e| e.to_string())?;
+    transport
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+fn config(app: &AppHandle) -> Result<Option<SmtpConfig>, String> {
+    let settings = settings::load(app);
+    let text = |key: &str| {
+        settings
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    let (Some(host), Some(to)) = (text("smtpHost"), text("smtpTo")) else {
+        return Ok(None);
+    };
+
+    let parse = |address: &str| {
+        address
+            .trim()
+            .parse::<Mailbox>()
+            .map_err(|e| format!("Invalid email address {address:?}: {e}"))
+    };
+    let to = to
+        .split(',')
+        .filter(|a| !a.trim().is_empty())
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    let username = text("smtpUsername");
+    // Most servers only accept mail from the account that logs in
+    let from = match text("smtpFrom").or_else(|| username.clone().filter(|u| u.contains('@'))) {
+        Some(from) => parse(&from)?,
+        None => to.first().cloned().ok_or("smtpTo has no addresses")?,
+    };
+    let security = match text("smtpSecurity").as_deref() {
+        None | Some("starttls") => Security::StartTls,
+        Some("tls") => Security::Tls,
+        Some("none") => Security::None,
+        Some(other) => return Err(format!("Unknown smtpSecurity: {other}")),
+    };
+
+    Ok(Some(SmtpConfig {
+        host,
+        port: settings::get_u64(&settings, "smtpPort").and_then(|p| u16::try_from(p).ok()),
+        security,
+        username,
+        from,
+        to,
+    }))
+}
+
+fn entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(vault::KEYCHAIN_SERVICE, KEYCHAIN_USER).map_err(|e| e.to_string())
+}
@@ -0,0 +1,486 @@
+// Proxy engine version management: check, download, verify and swap in with rollback
+use std::{collections::BTreeMap, path::PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, FixedOffset};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use tauri_plugin_shell::ShellExt;
+
+use crate::{audit, lock, sidecar, telemetry};
+
+const PACKAGE_NAME: &str = "antigravity-claude-proxy";
+const REGISTRY_URL: &str = "https://registry.npmjs.org";
+const RECORD_FILE: &str = "engine.json";
+
+#[cfg(windows)]
+pub const NPM_BIN: &str = "npm.cmd";
+#[cfg(not(windows))]
+pub const NPM_BIN: &str = "npm";
+
+#[derive(Deserialize)]
+struct RegistryVersion {
+    version: String,
+    dist: RegistryDist,
+}
+
+#[derive(Deserialize)]
+struct RegistryDist {
+    tarball: String,
+    integrity: String,
+    #[serde(default)]
+    signatures: Vec<RegistrySignature>,
+}
+
+#[derive(Deserialize)]
+struct RegistrySignature {
+    keyid: String,
+    sig: String,
+}
+
+// Only the part of the full package document we read
+#[derive(Deserialize)]
+struct RegistryPackage {
+    /// Version -> RFC 3339 publish time
+    #[serde(default)]
+    time: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct RegistryKeys {
+    keys: Vec<RegistryKey>,
+}
+
+#[derive(Deserialize)]
+struct RegistryKey {
+    keyid: String,
+    key: String,
+    /// RFC 3339 time after which npm no longer signs with the key; null while current
+    expires: Option<String>,
+}
+
+impl RegistryKey {
+    // As npm has it, an expired key still vouches for releases published before
+    // it expired. A key whose expiry can't be read counts as expired.
+    fn valid_for(&self, published: DateTime<FixedOffset>) -> bool {
+        self.expires.as_deref().is_none_or(|expires| {
+            DateTime::parse_from_rfc3339(expires).is_ok_and(|t| published < t)
+        })
+    }
+}
+
+// What we remember between updates, so a bad engine can be swapped back out
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EngineRecord {
+    previous_version: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineVersionInfo {
+    installed: Option<String>,
+    latest: Option<String>,
+    previous: Option<String>,
+    update_available: bool,
+}
+
+/// Installed, latest and previously installed engine versions
+#[tauri::command]
+pub async fn get_engine_version(app: AppHandle) -> Result<EngineVersionInfo, String> {
+    let installed = installed_version(&app).await;
+    let latest = fetch_version("latest").await.ok().map(|v| v.version);
+    let update_available = match (&installed, &latest) {
+        (Some(installed), Some(latest)) => compare_versions(latest, installed).is_gt(),
+        _ => false,
+    };
+
+    Ok(EngineVersionInfo {
+        installed,
+        latest,
+        previous: load_record(&app).previous_version,
+        update_available,
+    })
+}
+
+/// Download, verify and install an engine release (latest when no version is given).
+/// A running engine is stopped first and started again afterwards; the previous
+/// version is put back if the install does not take.
+#[tauri::command]
+pub async fn update_engine(
+    app: AppHandle,
+    version: Option<String>,
+) -> Result<EngineVersionInfo, String> {
+    lock::ensure_unlocked(&app)?;
+    telemetry::count(&app, "feature.updateEngine");
+    let target = fetch_version(version.as_deref().unwrap_or("latest")).await?;
+    let current = installed_version(&app).await;
+    if current.as_deref() == Some(target.version.as_str()) {
+        return get_engine_version(app).await;
+    }
+
+    let tarball = download_verified(&app, &target).await?;
+    // Node, not just its launcher, has to let go of the package files
+    let running = sidecar::stop_for_update(&app).await;
+    log::info!("installing engine {} (was {:?})", target.version, current);
+
+    let installed = match npm_install(&app, &tarball.to_string_lossy()).await {
+        Ok(()) => verify_installed(&app, &target.version).await,
+        Err(e) => Err(e),
+    };
+    let installed = match (installed, &current) {
+        (Err(e), Some(previous)) => {
+            log::error!("engine {} install failed: {e}", target.version);
+            log::warn!("rolling engine back to {previous}");
+            match install_release(&app, previous).await {
+                Ok(()) => Err(e),
+                Err(rollback) => {
+                    log::error!("engine rollback to {previous} failed: {rollback}");
+                    Err(format!(
+                        "{e} (rolling back to {previous} also failed: {rollback})"
+                    ))
+                }
+            }
+        }
+        (installed, _) => installed,
+    };
+    restart_after_install(&app, running);
+    installed?;
+
+    save_record(
+        &app,
+        &EngineRecord {
+            previous_version: current.clone(),
+        },
+    )?;
+    // The new and the previous release stay cached so a rollback works offline
+    prune_tarballs(&app, &[Some(target.version.as_str()), current.as_deref()]);
+    audit::record(
+        &app,
+        "engine.update",
+        serde_json::json!({ "from": current, "to": target.version }),
+    );
+
+    get_engine_version(app).await
+}
+
+/// Reinstall the version that was replaced by the last update, e.g. when a new
+/// release breaks model mapping. Uses the tarball kept from that update when there
+/// is one, otherwise downloads and verifies the release again.
+#[tauri::command]
+pub async fn rollback_update(app: AppHandle) -> Result<EngineVersionInfo, String> {
+    lock::ensure_unlocked(&app)?;
+    telemetry::count(&app, "feature.rollbackEngine");
+    let previous = load_record(&app)
+        .previous_version
+        .ok_or("There is no previous engine version to roll back to")?;
+    let current = installed_version(&app).await;
+    if current.as_deref() == Some(previous.as_str()) {
+        return get_engine_version(app).await;
+    }
+
+    let tarball = match cached_tarball(&app, &previous) {
+        Some(path) => path,
+        None => download_verified(&app, &fetch_version(&previous).await?).await?,
+    };
+    let running = sidecar::stop_for_update(&app).await;
+    log::warn!("rolling engine back from {current:?} to {previous}");
+
+    let installed = match npm_install(&app, &tarball.to_string_lossy()).await {
+        Ok(()) => verify_installed(&app, &previous).await,
+        Err(e) => Err(e),
+    };
+    restart_after_install(&app, running);
+    installed?;
+
+    audit::record(
+        &app,
+        "engine.rollback",
+        serde_json::json!({ "from": current, "to": previous }),
+    );
+    // Rolling back again returns to the release we just left
+    save_record(
+        &app,
+        &EngineRecord {
+            previous_version: current,
+        },
+    )?;
+
+    get_engine_version(app).await
+}
+
+// Whatever got installed, a proxy that was running should be running again
+fn restart_after_install(app: &AppHandle, running: Option<sidecar::Launch>) {
+    if let Err(e) = sidecar::resume(app, running) {
+        log::error!("engine did not start again after the install: {e}");
+    }
+}
+
+// From the tarball kept by an earlier update when there is one
+async fn install_release(app: &AppHandle, version: &str) -> Result<(), String> {
+    let tarball = match cached_tarball(app, version) {
+        Some(path) => path,
+        None => download_verified(app, &fetch_version(version).await?).await?,
+    };
+    npm_install(app, &tarball.to_string_lossy()).await?;
+    verify_installed(app, version).await
+}
+
+pub async fn installed_version(app: &AppHandle) -> Option<String> {
+    let output = app
+        .shell()
+        .command(sidecar::PROXY_BIN)
+        .args(["--version"])
+        .output()
+        .await
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+async fn verify_installed(app: &AppHandle, expected: &str) -> Result<(), String> {
+    match installed_version(app).await {
+        Some(v) if v == expected => Ok(()),
+        other => Err(format!(
+            "expected engine {expected} after install, found {other:?}"
+        )),
+    }
+}
+
+async fn fetch_version(version: &str) -> Result<RegistryVersion, String> {
+    let body = reqwest::get(format!("{REGISTRY_URL}/{PACKAGE_NAME}/{version}"))
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+async fn download_verified(app: &AppHandle, target: &RegistryVersion) -> Result<PathBuf, String> {
+    verify_signature(target).await?;
+
+    let bytes = reqwest::get(&target.dist.tarball)
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    verify_integrity(&bytes, &target.dist.integrity)?;
+
+    let path = tarball_path(app, &target.version)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn tarball_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join("engine"))
+}
+
+fn tarball_path(app: &AppHandle, version: &str) -> Result<PathBuf, String> {
+    Ok(tarball_dir(app)?.join(format!("{PACKAGE_NAME}-{version}.tgz")))
+}
+
+// Only ever written after its checksum and signature were verified
+fn cached_tarball(app: &AppHandle, version: &str) -> Option<PathBuf> {
+    tarball_path(app, version).ok().filter(|p| p.is_file())
+}
+
+fn prune_tarballs(app: &AppHandle, keep: &[Option<&str>]) {
+    let keep: Vec<PathBuf> = keep
+        .iter()
+        .flatten()
+        .filter_map(|v| tarball_path(app, v).ok())
+        .collect();
+    let Ok(entries) =
+        tarball_dir(app).and_then(|d| std::fs::read_dir(d).map_err(|e| e.to_string()))
+    else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if !keep.contains(&path) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+// npm integrity strings look like "sha512-<base64 digest>"
+fn verify_integrity(bytes: &[u8], integrity: &str) -> Result<(), String> {
+    let expected = integrity
+        .split_whitespace()
+        .find_map(|entry| entry.strip_prefix("sha512-"))
+        .ok_or_else(|| format!("unsupported integrity value: {integrity}"))?;
+
+    let actual = STANDARD.encode(Sha512::digest(bytes));
+    if actual != expected {
+        return Err("engine tarball checksum mismatch".into());
+    }
+    Ok(())
+}
+
+// Registry signatures cover "<name>@<version>:<integrity>" and are checked against npm's published keys
+async fn verify_signature(target: &RegistryVersion) -> Result<(), String> {
+    if target.dist.signatures.is_empty() {
+        return Err("engine release is not signed by the registry".into());
+    }
+
+    let body = reqwest::get(format!("{REGISTRY_URL}/-/npm/v1/keys"))
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+    let keys: RegistryKeys = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    let published = publish_time(&target.version).await?;
+
+    let message = format!(
+        "{PACKAGE_NAME}@{}:{}",
+        target.version, target.dist.integrity
+    );
+    let verified = target.dist.signatures.iter().any(|sig| {
+        keys.keys
+            .iter()
+            .filter(|k| k.keyid == sig.keyid && k.valid_for(published))
+            .any(|k| verify_ecdsa(&k.key, &sig.sig, message.as_bytes()))
+    });
+
+    if verified {
+        Ok(())
+    } else {
+        Err("engine release signature could not be verified".into())
+    }
+}
+
+async fn publish_time(version: &str) -> Result<DateTime<FixedOffset>, String> {
+    let body = reqwest::get(format!("{REGISTRY_URL}/{PACKAGE_NAME}"))
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+    let package: RegistryPackage = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    package
+        .time
+        .get(version)
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .ok_or_else(|| format!("the registry lists no publish time for engine {version}"))
+}
+
+fn verify_ecdsa(key_b64: &str, sig_b64: &str, message: &[u8]) -> bool {
+    let (Ok(key_der), Ok(sig_der)) = (STANDARD.decode(key_b64), STANDARD.decode(sig_b64)) else {
+        return false;
+    };
+    let (Ok(key), Ok(sig)) = (
+        VerifyingKey::from_public_key_der(&key_der),
+        Signature::from_der(&sig_der),
+    ) else {
+        return false;
+    };
+    key.verify(message, &sig).is_ok()
+}
+
+async fn npm_install(app: &AppHandle, spec: &str) -> Result<(), String> {
+    let output = app
+        .shell()
+        .command(NPM_BIN)
+        .args(["install", "-g", spec, "--force"])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn record_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(RECORD_FILE))
+}
+
+fn load_record(app: &AppHandle) -> EngineRecord {
+    record_path(app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_record(app: &AppHandle, record: &EngineRecord) -> Result<(), String> {
+    let path = record_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    };
+    parse(a).cmp(&parse(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(expires: Option<&str>) -> RegistryKey {
+        RegistryKey {
+            keyid: "SHA256:test".into(),
+            key: String::new(),
+            expires: expires.map(str::to_string),
+        }
+    }
+
+    fn at(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(time).unwrap()
+    }
+
+    #[test]
+    fn current_key_is_valid_for_any_release() {
+        assert!(key(None).valid_for(at("2026-10-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn expired_key_still_covers_releases_published_before_expiry() {
+        let rotated = key(Some("2025-01-29T00:00:00.000Z"));
+        assert!(rotated.valid_for(at("2024-06-12T08:30:00.000Z")));
+        assert!(!rotated.valid_for(at("2025-02-03T10:00:00.000Z")));
+    }
+
+    #[test]
+    fn key_with_unreadable_expiry_is_never_valid() {
+        assert!(!key(Some("soon")).valid_for(at("2020-01-01T00:00:00Z")));
+    }
+}
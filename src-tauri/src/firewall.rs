@@ -0,0 +1,114 @@
+// Inbound Windows Firewall rule for LAN mode. Once the engine listens beyond
+// localhost, Windows silently drops connections from other machines unless a
+// rule allows them. Adding and removing the rule need elevation, so both go
+// through a single UAC prompt. Other platforms report the rule as unsupported.
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use crate::{audit, lock};
+
+const RULE_NAME: &str = "Antigravity Claude Proxy";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirewallStatus {
+    supported: bool,
+    pub present: bool,
+    /// Port the existing rule opens
+    port: Option<u16>,
+}
+
+#[tauri::command]
+pub async fn get_firewall_rule(app: AppHandle) -> Result<FirewallStatus, String> {
+    if !cfg!(windows) {
+        return Ok(FirewallStatus {
+            supported: false,
+            present: false,
+            port: None,
+        });
+    }
+    // Get-NetFirewallRule instead of netsh, whose output is localized
+    let output = powershell(
+        &app,
+        &format!(
+            "$rule = Get-NetFirewallRule -DisplayName '{RULE_NAME}' -ErrorAction SilentlyContinue; if ($rule) {{ ($rule | Get-NetFirewallPortFilter | Select-Object -First 1).LocalPort }} else {{ 'none' }}"
+        ),
+    )
+    .await?;
+    let output = output.trim();
+    Ok(FirewallStatus {
+        supported: true,
+        present: output != "none",
+        port: output.parse().ok(),
+    })
+}
+
+/// Allow inbound TCP on `port` from private and domain networks, replacing an
+/// older rule for another port. Public networks (cafés, airports) stay closed.
+#[tauri::command]
+pub async fn add_firewall_rule(app: AppHandle, port: u16) -> Result<FirewallStatus, String> {
+    lock::ensure_unlocked(&app)?;
+    elevated_netsh(
+        &app,
+        &format!(
+            "netsh advfirewall firewall delete rule name=\"{RULE_NAME}\" & netsh advfirewall firewall add rule name=\"{RULE_NAME}\" dir=in action=allow protocol=TCP localport={port} profile=private,domain"
+        ),
+    )
+    .await?;
+    log::info!("added firewall rule for port {port}");
+    audit::record(&app, "firewall.add", serde_json::json!({ "port": port }));
+    get_firewall_rule(app).await
+}
+
+#[tauri::command]
+pub async fn remove_firewall_rule(app: AppHandle) -> Result<FirewallStatus, String> {
+    lock::ensure_unlocked(&app)?;
+    remove_rule(&app).await?;
+    get_firewall_rule(app).await
+}
+
+/// `remove_firewall_rule` without the lock check, for the uninstaller
+pub async fn remove_rule(app: &AppHandle) -> Result<(), String> {
+    elevated_netsh(
+        app,
+        &format!("netsh advfirewall firewall delete rule name=\"{RULE_NAME}\""),
+    )
+    .await?;
+    log::info!("removed firewall rule");
+    audit::record(app, "firewall.remove", serde_json::Value::Null);
+    Ok(())
+}
+
+// Runs `commands` in an elevated cmd; fails when the UAC prompt is declined
+async fn elevated_netsh(app: &AppHandle, commands: &str) -> Result<(), String> {
+    if !cfg!(windows) {
+        return Err("Firewall rules are only managed on Windows".into());
+    }
+    powershell(
+        app,
+        &format!(
+            "$p = Start-Process -FilePath cmd.exe -ArgumentList '/c {commands}' -Verb RunAs -Wait -WindowStyle Hidden -PassThru -ErrorAction Stop; exit $p.ExitCode"
+        ),
+    )
+    .await
+    .map(|_| ())
+}
+
+async fn powershell(app: &AppHandle, script: &str) -> Result<String, String> {
+    let output = app
+        .shell()
+        .command("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => format!("Command failed with {:?}", output.status.code()),
+            message => message.to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,109 @@
+// Strings the shell shows itself (tray menu, notifications, alert emails) in the
+// same language as the frontend. The language is the `language` setting, or
+// the OS locale until one is chosen. Translations are bundled JSON resources;
+// missing keys fall back to English.
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::{settings, tray};
+
+const DEFAULT_LOCALE: &str = "en";
+
+static TRANSLATIONS: LazyLock<HashMap<&'static str, HashMap<String, String>>> =
+    LazyLock::new(|| {
+        [
+            ("en", include_str!("../locales/en.json")),
+            ("tr", include_str!("../locales/tr.json")),
+        ]
+        .into_iter()
+        .map(|(locale, json)| {
+            let table = serde_json::from_str(json).expect("bundled locale is valid JSON");
+            (locale, table)
+        })
+        .collect()
+    });
+
+// Resolved once, then kept in step by set_locale
+static CURRENT: RwLock<Option<String>> = RwLock::new(None);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleInfo {
+    locale: String,
+    /// What the OS asks for, reduced to a supported locale
+    detected: String,
+    available: Vec<&'static str>,
+}
+
+#[tauri::command]
+pub fn get_locale(app: AppHandle) -> LocaleInfo {
+    let mut available: Vec<&'static str> = TRANSLATIONS.keys().copied().collect();
+    available.sort_unstable();
+    LocaleInfo {
+        locale: current(&app),
+        detected: detect(),
+        available,
+    }
+}
+
+/// Switch the shell's language, save it, and tell every window ("locale-changed")
+#[tauri::command]
+pub fn set_locale(app: AppHandle, locale: String) -> Result<(), String> {
+    let locale = supported(&locale).ok_or(format!("Unsupported locale: {locale}"))?;
+    settings::update(&app, "language", locale.into())?;
+    *CURRENT.write().unwrap() = Some(locale.to_string());
+    let _ = app.emit("locale-changed", locale);
+    tray::refresh(&app);
+    Ok(())
+}
+
+pub fn current(app: &AppHandle) -> String {
+    if let Some(locale) = CURRENT.read().unwrap().clone() {
+        return locale;
+    }
+    let locale = settings::load(app)
+        .get("language")
+        .and_then(Value::as_str)
+        .and_then(supported)
+        .map(String::from)
+        .unwrap_or_else(detect);
+    *CURRENT.write().unwrap() = Some(locale.clone());
+    locale
+}
+
+/// Translation of `key` in the current language
+pub fn t(app: &AppHandle, key: &str) -> String {
+    let locale = current(app);
+    [locale.as_str(), DEFAULT_LOCALE]
+        .iter()
+        .find_map(|l| TRANSLATIONS.get(l)?.get(key))
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Like `t`, filling `{name}` placeholders
+pub fn tf(app: &AppHandle, key: &str, args: &[(&str, String)]) -> String {
+    args.iter().fold(t(app, key), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+// "tr-TR", "tr_TR.UTF-8" -> "tr"
+fn supported(locale: &str) -> Option<&'static str> {
+    let language = locale.split(['-', '_', '.']).next()?.to_ascii_lowercase();
+    TRANSLATIONS.keys().copied().find(|l| *l == language)
+}
+
+fn detect() -> String {
+    sys_locale::get_locale()
+        .as_deref()
+        .and_then(supported)
+        .unwrap_or(DEFAULT_LOCALE)
+        .to_string()
+}
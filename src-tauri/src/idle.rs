@@ -0,0 +1,86 @@
+// Idle suspend: after a configurable time without API requests the app's own
+// background sampling drops to near zero, and wakes the moment a request shows up.
+// The engine's upstream keep-alive connections are its own and stay open.
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::settings;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+// Longest a suspended task waits before running anyway
+const IDLE_PAUSE: Duration = Duration::from_secs(5 * 60);
+
+pub struct IdleState {
+    last_activity: Mutex<Instant>,
+    wake: Condvar,
+    idle: AtomicBool,
+}
+
+impl Default for IdleState {
+    fn default() -> Self {
+        Self {
+            last_activity: Mutex::new(Instant::now()),
+            wake: Condvar::new(),
+            idle: AtomicBool::new(false),
+        }
+    }
+}
+
+/// Record API activity, waking suspended tasks if we were idle
+pub fn touch(app: &AppHandle) {
+    let state = app.state::<IdleState>();
+    let mut last_activity = state.last_activity.lock().unwrap();
+    *last_activity = Instant::now();
+    // Flipped and notified under the lock, so a pause() that just saw `idle` set
+    // is already waiting and can't miss the wakeup
+    let woke = state.idle.swap(false, Ordering::Relaxed);
+    if woke {
+        state.wake.notify_all();
+    }
+    drop(last_activity);
+    if woke {
+        log::info!("request received, leaving idle mode");
+        let _ = app.emit("idle-changed", false);
+    }
+}
+
+/// Sleep for `interval`, or while idle until activity (capped by IDLE_PAUSE)
+pub fn pause(app: &AppHandle, interval: Duration) {
+    let state = app.state::<IdleState>();
+    if !state.idle.load(Ordering::Relaxed) {
+        thread::sleep(interval);
+        return;
+    }
+    let guard = state.last_activity.lock().unwrap();
+    let _ = state
+        .wake
+        .wait_timeout_while(guard, IDLE_PAUSE, |_| state.idle.load(Ordering::Relaxed));
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(CHECK_INTERVAL);
+
+        // Off unless the user opted in
+        let Some(minutes) =
+            settings::get_u64(&settings::load(&app), "idleSuspendMinutes").filter(|m| *m > 0)
+        else {
+            continue;
+        };
+
+        let state = app.state::<IdleState>();
+        let quiet = state.last_activity.lock().unwrap().elapsed();
+        if quiet >= Duration::from_secs(minutes * 60) && !state.idle.swap(true, Ordering::Relaxed) {
+            log::info!("no requests for {minutes} min, entering idle mode");
+            let _ = app.emit("idle-changed", true);
+        }
+    });
+}
@@ -0,0 +1,200 @@
+// On-disk journal of the requests the engine is working on, for postmortems of
+// mid-stream crashes. inflight.json is rewritten whenever a request starts or
+// finishes. Whatever is still in it when the engine exits, or when the app starts
+// again after going down with requests open, was cut off and moves to
+// interrupted.json. The engine's log carries no request ids or client addresses,
+// so entries get a running number plus the model, account and start time the
+// engine logged, and responses are matched to requests oldest first.
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::{alerts, i18n, lock, traffic};
+
+const INFLIGHT_FILE: &str = "inflight.json";
+const INTERRUPTED_FILE: &str = "interrupted.json";
+const MAX_INTERRUPTED: usize = 200;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    id: u64,
+    model: String,
+    account: Option<String>,
+    started_at: u64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Cause {
+    /// The engine process went away
+    EngineExited,
+    /// The app itself went down, taking the engine with it
+    AppClosed,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interrupted {
+    #[serde(flatten)]
+    entry: Entry,
+    ended_at: u64,
+    cause: Cause,
+    /// Engine exit code, when it reported one
+    exit_code: Option<i32>,
+}
+
+#[derive(Default)]
+struct Journal {
+    next_id: u64,
+    open: VecDeque<Entry>,
+}
+
+#[derive(Default)]
+pub struct JournalState(Mutex<Journal>);
+
+/// The engine logged a new request for `model`
+pub fn started(app: &AppHandle, model: &str) {
+    let state = app.state::<JournalState>();
+    let mut journal = state.0.lock().unwrap();
+    journal.next_id += 1;
+    let entry = Entry {
+        id: journal.next_id,
+        model: model.to_string(),
+        account: None,
+        started_at: traffic::now_ms(),
+    };
+    journal.open.push_back(entry);
+    persist(app, &journal.open);
+}
+
+/// The engine picked an account for its newest request
+pub fn routed(app: &AppHandle, account: &str) {
+    let state = app.state::<JournalState>();
+    let mut journal = state.0.lock().unwrap();
+    if let Some(entry) = journal.open.back_mut().filter(|e| e.account.is_none()) {
+        entry.account = Some(account.to_string());
+        persist(app, &journal.open);
+    }
+}
+
+/// A request was answered or aborted by its client
+pub fn finished(app: &AppHandle) {
+    let state = app.state::<JournalState>();
+    let mut journal = state.0.lock().unwrap();
+    if journal.open.pop_front().is_some() {
+        persist(app, &journal.open);
+    }
+}
+
+/// The engine exited; everything still open was cut off
+pub fn engine_exited(app: &AppHandle, exit_code: Option<i32>) {
+    let open = {
+        let state = app.state::<JournalState>();
+        let mut journal = state.0.lock().unwrap();
+        let open: Vec<Entry> = journal.open.drain(..).collect();
+        persist(app, &journal.open);
+        open
+    };
+    interrupt(app, open, Cause::EngineExited, exit_code);
+}
+
+/// Report requests left open by the previous run. Call before the engine starts.
+pub fn recover(app: &AppHandle) {
+    let Ok(path) = data_path(app, INFLIGHT_FILE) else {
+        return;
+    };
+    let open: Vec<Entry> = read(&path);
+    let _ = fs::remove_file(path);
+    interrupt(app, open, Cause::AppClosed, None);
+}
+
+fn interrupt(app: &AppHandle, open: Vec<Entry>, cause: Cause, exit_code: Option<i32>) {
+    if open.is_empty() {
+        return;
+    }
+    let ended_at = traffic::now_ms();
+    let count = open.len();
+    log::warn!("{count} request(s) were cut off ({cause:?}, exit code {exit_code:?})");
+
+    let mut interrupted = load_interrupted(app);
+    interrupted.extend(open.into_iter().map(|entry| Interrupted {
+        entry,
+        ended_at,
+        cause,
+        exit_code,
+    }));
+    let excess = interrupted.len().saturating_sub(MAX_INTERRUPTED);
+    interrupted.drain(..excess);
+    if let Err(e) = data_path(app, INTERRUPTED_FILE).and_then(|p| write(&p, &interrupted)) {
+        log::warn!("failed to save interrupted requests: {e}");
+    }
+
+    alerts::notify(
+        app,
+        &i18n::t(app, "journal.title"),
+        &i18n::tf(app, "journal.body", &[("count", count.to_string())]),
+    );
+}
+
+/// Requests that were cut off by an engine exit or an app crash, newest first
+#[tauri::command]
+pub fn get_interrupted_requests(app: AppHandle) -> Vec<Interrupted> {
+    let mut interrupted = load_interrupted(&app);
+    interrupted.reverse();
+    interrupted
+}
+
+#[tauri::command]
+pub fn clear_interrupted_requests(app: AppHandle) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    match fs::remove_file(data_path(&app, INTERRUPTED_FILE)?) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn load_interrupted(app: &AppHandle) -> Vec<Interrupted> {
+    data_path(app, INTERRUPTED_FILE)
+        .map(|p| read(&p))
+        .unwrap_or_default()
+}
+
+// Best-effort: a full disk must not hold up the log reader
+fn persist(app: &AppHandle, open: &VecDeque<Entry>) {
+    if let Err(e) = data_path(app, INFLIGHT_FILE).and_then(|p| write(&p, open)) {
+        log::debug!("failed to write request journal: {e}");
+    }
+}
+
+fn data_path(app: &AppHandle, file: &str) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(file))
+}
+
+fn read<T: DeserializeOwned + Default>(path: &Path) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn write<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    // Write beside and rename, so a crash mid-write leaves the previous journal
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
@@ -1,18 +1,72 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-use tauri::{
-    menu::{Menu, MenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WindowEvent,
-};
+use tauri::{Manager, WindowEvent};
+use tauri_plugin_log::{Target, TargetKind};
+
+mod accounts;
+mod alerts;
+mod api;
+mod audit;
+mod badge;
+mod bench;
+mod captures;
+mod catalog;
+mod compare;
+mod crash;
+mod credentials;
+mod diagnostics;
+mod disk;
+mod email;
+mod engine;
+mod firewall;
+mod i18n;
+mod idle;
+mod journal;
+mod lock;
+mod mappings;
+mod mcp;
+mod monitor;
+mod network;
+mod onboarding;
+mod power;
+mod pricing;
+mod priority;
+mod privacy;
+mod profiles;
+mod prompt;
+mod quota;
+mod redact;
+mod replay;
+mod scheduler;
+mod settings;
+mod sidecar;
+mod sync;
+mod telemetry;
+mod traffic;
+mod tray;
+mod uninstall;
+mod vault;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
-            // When trying to open second instance, focus the existing window
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
+        .plugin(
+            // Shell and engine (target "sidecar") messages end up in the same log file
+            tauri_plugin_log::Builder::new()
+                .targets([
+                    Target::new(TargetKind::Stdout),
+                    Target::new(TargetKind::LogDir { file_name: None }),
+                ])
+                // Narrowed to the "logLevel" setting once settings can be read
+                .level(log::LevelFilter::Trace)
+                .build(),
+        )
+        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            // `--profile <name>` starts that profile in this instance; any other
+            // second launch focuses the existing window
+            if profiles::from_args(&args).is_some() {
+                profiles::start_from_args(app, &args);
+            } else {
+                tray::show_main_window(app);
             }
         }))
         .plugin(
@@ -24,54 +78,142 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .manage(sidecar::SidecarState::default())
+        .manage(traffic::TrafficState::default())
+        .manage(journal::JournalState::default())
+        .manage(mcp::McpState::default())
+        .manage(alerts::AlertState::default())
+        .manage(credentials::CredentialState::default())
+        .manage(idle::IdleState::default())
+        .manage(accounts::AccountsState::default())
+        .manage(lock::LockState::default())
+        .manage(telemetry::TelemetryState::default())
+        .manage(quota::QuotaState::default())
+        .manage(profiles::ProfilesState::default())
+        .invoke_handler(tauri::generate_handler![
+            sidecar::start_proxy,
+            sidecar::stop_proxy,
+            sidecar::reconfigure_proxy,
+            mcp::set_mcp_port,
+            mcp::get_mcp_token,
+            mcp::reset_mcp_token,
+            profiles::list_profiles,
+            profiles::save_profile,
+            profiles::delete_profile,
+            profiles::start_profile,
+            profiles::stop_profile,
+            engine::get_engine_version,
+            engine::update_engine,
+            engine::rollback_update,
+            priority::set_engine_priority,
+            diagnostics::run_diagnostics,
+            diagnostics::export_diagnostics,
+            diagnostics::ping_upstream,
+            bench::run_benchmark,
+            prompt::send_test_prompt,
+            traffic::get_latency_stats,
+            traffic::get_error_counters,
+            traffic::reset_error_counters,
+            credentials::get_credential_warnings,
+            accounts::pin_account,
+            quota::get_quota_resets,
+            mappings::get_mapping_presets,
+            mappings::save_mapping_preset,
+            mappings::delete_mapping_preset,
+            mappings::apply_mapping_preset,
+            replay::replay_curl,
+            captures::list_captures,
+            captures::diff_captures,
+            compare::compare_mappings,
+            compare::list_comparisons,
+            privacy::wipe_all_data,
+            disk::get_disk_usage,
+            disk::clean_app_data,
+            uninstall::cleanup_everything,
+            lock::get_lock_status,
+            lock::unlock,
+            lock::lock_app,
+            lock::set_lock_passphrase,
+            settings::get_env_overrides,
+            settings::save_settings,
+            audit::audit_event,
+            audit::verify_audit_log,
+            email::set_smtp_password,
+            email::send_test_email,
+            scheduler::list_jobs,
+            scheduler::run_job_now,
+            scheduler::set_job_schedule,
+            firewall::get_firewall_rule,
+            firewall::add_firewall_rule,
+            firewall::remove_firewall_rule,
+            sync::set_sync_folder,
+            sync::sync_now,
+            i18n::get_locale,
+            i18n::set_locale,
+            onboarding::get_onboarding_state,
+            onboarding::complete_onboarding_step,
+            journal::get_interrupted_requests,
+            journal::clear_interrupted_requests,
+            crash::list_crash_reports,
+            crash::report_crash,
+            crash::delete_crash_reports,
+            telemetry::get_telemetry_queue,
+            telemetry::refresh_telemetry,
+            catalog::list_upstream_models,
+            pricing::get_pricing_table,
+            pricing::refresh_pricing_table,
+            pricing::estimate_usage_cost
+        ])
         .setup(|app| {
-            // Create tray menu
-            let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
-            let separator = tauri::menu::PredefinedMenuItem::separator(app)?;
-            let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &separator, &quit_item])?;
-
-            // Create system tray icon
-            let _tray = TrayIconBuilder::new()
-                .icon(app.default_window_icon().unwrap().clone())
-                .menu(&menu)
-                .show_menu_on_left_click(false)
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    "quit" => {
-                        app.exit(0);
-                    }
-                    _ => {}
-                })
-                .on_tray_icon_event(|tray, event| {
-                    if let TrayIconEvent::Click {
-                        button: MouseButton::Left,
-                        button_state: MouseButtonState::Up,
-                        ..
-                    } = event
-                    {
-                        let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                })
-                .build(app)?;
+            if let Some(remove_data) = uninstall::requested() {
+                uninstall::run_headless(app.handle(), remove_data);
+                std::process::exit(0);
+            }
+            crash::install(app.handle());
+            // Before the frontend or any background task reads settings
+            if let Err(e) = settings::migrate(app.handle()) {
+                log::error!("settings migration failed: {e}");
+            }
+            settings::apply_log_level(app.handle());
+            tray::create(app)?;
+            crash::notify_unseen(app.handle());
+            journal::recover(app.handle());
+            sidecar::autostart(app.handle());
+            idle::spawn(app.handle().clone());
+            monitor::spawn(app.handle().clone());
+            credentials::spawn(app.handle().clone());
+            accounts::spawn(app.handle().clone());
+            privacy::spawn(app.handle().clone());
+            power::spawn(app.handle().clone());
+            network::spawn(app.handle().clone());
+            telemetry::spawn(app.handle().clone());
+            catalog::spawn(app.handle().clone());
+            pricing::spawn(app.handle().clone());
+            email::spawn(app.handle().clone());
+            scheduler::spawn(app.handle().clone());
+            sync::spawn(app.handle().clone());
+            disk::spawn(app.handle().clone());
+            mcp::spawn(app.handle().clone());
+            let args: Vec<String> = std::env::args().collect();
+            if profiles::from_args(&args).is_some() {
+                profiles::start_from_args(app.handle(), &args);
+            } else {
+                profiles::start_configured(app.handle());
+            }
 
             Ok(())
         })
-        .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } => {
                 // Hide window instead of closing
                 let _ = window.hide();
                 api.prevent_close();
+                lock::lock(window.app_handle());
             }
+            WindowEvent::ThemeChanged(theme) => tray::on_theme_changed(window.app_handle(), *theme),
+            _ => {}
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,184 @@
+// Optional passphrase lock for the window. The proxy keeps running while locked;
+// only the UI and commands that change or delete data are gated. The window
+// locks again whenever it is hidden to the tray.
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audit;
+
+const LOCK_FILE: &str = "lock.json";
+// Slows down guessing from a script running in the webview
+const FAILED_ATTEMPT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockRecord {
+    /// Argon2 PHC string
+    passphrase_hash: Option<String>,
+}
+
+/// Starts locked; `is_locked` only reports it when a passphrase is set
+pub struct LockState {
+    locked: AtomicBool,
+}
+
+impl Default for LockState {
+    fn default() -> Self {
+        Self {
+            locked: AtomicBool::new(true),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatus {
+    enabled: bool,
+    locked: bool,
+}
+
+pub fn is_locked(app: &AppHandle) -> bool {
+    load_record(app).passphrase_hash.is_some()
+        && app.state::<LockState>().locked.load(Ordering::Relaxed)
+}
+
+/// Fail a command while the app is locked
+pub fn ensure_unlocked(app: &AppHandle) -> Result<(), String> {
+    if is_locked(app) {
+        Err("App is locked".into())
+    } else {
+        Ok(())
+    }
+}
+
+pub fn lock(app: &AppHandle) {
+    let was_locked = app
+        .state::<LockState>()
+        .locked
+        .swap(true, Ordering::Relaxed);
+    if !was_locked && load_record(app).passphrase_hash.is_some() {
+        let _ = app.emit("lock-changed", true);
+    }
+}
+
+#[tauri::command]
+pub fn get_lock_status(app: AppHandle) -> LockStatus {
+    LockStatus {
+        enabled: load_record(&app).passphrase_hash.is_some(),
+        locked: is_locked(&app),
+    }
+}
+
+#[tauri::command]
+pub async fn unlock(
+    app: AppHandle,
+    state: State<'_, LockState>,
+    passphrase: String,
+) -> Result<bool, String> {
+    let Some(hash) = load_record(&app).passphrase_hash else {
+        return Ok(true);
+    };
+    if verify(&hash, &passphrase)? {
+        state.locked.store(false, Ordering::Relaxed);
+        let _ = app.emit("lock-changed", false);
+        Ok(true)
+    } else {
+        log::warn!("failed unlock attempt");
+        tokio::time::sleep(FAILED_ATTEMPT_DELAY).await;
+        Ok(false)
+    }
+}
+
+#[tauri::command]
+pub fn lock_app(app: AppHandle) {
+    lock(&app);
+}
+
+/// Set, change or (with `None`) remove the passphrase. Changing it needs the
+/// current one, even while unlocked.
+#[tauri::command]
+pub fn set_lock_passphrase(
+    app: AppHandle,
+    current: Option<String>,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    let mut record = load_record(&app);
+    if let Some(hash) = &record.passphrase_hash {
+        if !verify(hash, current.as_deref().unwrap_or_default())? {
+            return Err("Current passphrase is incorrect".into());
+        }
+    }
+
+    record.passphrase_hash = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => {
+            let salt = SaltString::generate(&mut OsRng);
+            let hash = Argon2::default()
+                .hash_password(passphrase.as_bytes(), &salt)
+                .map_err(|e| e.to_string())?;
+            Some(hash.to_string())
+        }
+        None => None,
+    };
+    save_record(&app, &record)?;
+    // Whoever just set it is already in
+    app.state::<LockState>()
+        .locked
+        .store(false, Ordering::Relaxed);
+    log::info!(
+        "app lock {}",
+        if record.passphrase_hash.is_some() {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    audit::record(
+        &app,
+        "lock.passphrase",
+        serde_json::json!({ "enabled": record.passphrase_hash.is_some() }),
+    );
+    Ok(())
+}
+
+fn verify(hash: &str, passphrase: &str) -> Result<bool, String> {
+    let parsed = PasswordHash::new(hash).map_err(|e| e.to_string())?;
+    Ok(Argon2::default()
+        .verify_password(passphrase.as_bytes(), &parsed)
+        .is_ok())
+}
+
+fn record_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(LOCK_FILE))
+}
+
+fn load_record(app: &AppHandle) -> LockRecord {
+    record_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_record(app: &AppHandle, record: &LockRecord) -> Result<(), String> {
+    let path = record_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(record).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
@@ -0,0 +1,334 @@
+// Named model mapping presets ("fast", "best quality", ...) that can be switched
+// from the tray. Applying one pushes each mapping to the engine's live model
+// config, so it takes effect on the next request without a restart.
+//
+// Besides exact model ids a preset can hold tier rules ("tier:haiku" -> target)
+// that cover every Claude model of that class. The engine only knows exact ids,
+// so rules are expanded when the preset is applied; an exact entry always beats
+// the rule for its tier.
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::{api, audit, lock, sidecar, telemetry, tray};
+
+const PRESETS_FILE: &str = "mapping-presets.json";
+const TIER_PREFIX: &str = "tier:";
+const TIERS: [&str; 3] = ["haiku", "sonnet", "opus"];
+// Ids clients commonly send, on top of whatever the engine lists in /v1/models
+const KNOWN_CLAUDE_MODELS: [&str; 12] = [
+    "claude-3-haiku-20240307",
+    "claude-3-5-haiku-20241022",
+    "claude-haiku-4-5",
+    "claude-haiku-4-5-20251001",
+    "claude-3-5-sonnet-20241022",
+    "claude-3-7-sonnet-20250219",
+    "claude-sonnet-4-20250514",
+    "claude-sonnet-4-5",
+    "claude-sonnet-4-5-20250929",
+    "claude-opus-4-20250514",
+    "claude-opus-4-1-20250805",
+    "claude-opus-4-5",
+];
+
+/// Requested model id (or `tier:<haiku|sonnet|opus>`) -> model it should be served by
+pub type Mapping = BTreeMap<String, String>;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MappingPresets {
+    presets: BTreeMap<String, Mapping>,
+    active: Option<String>,
+}
+
+impl MappingPresets {
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.presets.keys()
+    }
+
+    pub fn mappings(&self) -> impl Iterator<Item = (&String, &Mapping)> {
+        self.presets.iter()
+    }
+
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+}
+
+#[tauri::command]
+pub fn get_mapping_presets(app: AppHandle) -> MappingPresets {
+    load(&app)
+}
+
+/// Create or replace a preset. Re-applied right away if it is the active one.
+#[tauri::command]
+pub async fn save_mapping_preset(
+    app: AppHandle,
+    name: String,
+    mapping: Mapping,
+) -> Result<MappingPresets, String> {
+    lock::ensure_unlocked(&app)?;
+    if name.trim().is_empty() {
+        return Err("Preset name can't be empty".into());
+    }
+    if let Some(key) = mapping.keys().find(|k| {
+        k.strip_prefix(TIER_PREFIX)
+            .is_some_and(|t| !TIERS.contains(&t))
+    }) {
+        return Err(format!(
+            "Unknown tier in {key}, expected one of: {}",
+            TIERS.join(", ")
+        ));
+    }
+    let mut presets = load(&app);
+    let detail = json!({ "preset": name, "mapping": mapping });
+    presets.presets.insert(name.clone(), mapping);
+    save(&app, &presets)?;
+    audit::record(&app, "mapping.save", detail);
+    if presets.active.as_deref() == Some(name.as_str()) {
+        apply(&app, Some(name)).await?;
+    }
+    tray::refresh(&app);
+    Ok(load(&app))
+}
+
+#[tauri::command]
+pub async fn delete_mapping_preset(app: AppHandle, name: String) -> Result<MappingPresets, String> {
+    lock::ensure_unlocked(&app)?;
+    if load(&app).active.as_deref() == Some(name.as_str()) {
+        apply(&app, None).await?;
+    }
+    let mut presets = load(&app);
+    presets.presets.remove(&name);
+    save(&app, &presets)?;
+    audit::record(&app, "mapping.delete", json!({ "preset": name }));
+    tray::refresh(&app);
+    Ok(presets)
+}
+
+/// Switch to a preset, or clear the active preset's mappings with `None`
+#[tauri::command]
+pub async fn apply_mapping_preset(app: AppHandle, name: Option<String>) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    telemetry::count(&app, "feature.applyMappingPreset");
+    apply(&app, name).await
+}
+
+/// `apply_mapping_preset` without the lock check, for presets arriving through sync
+pub async fn apply(app: &AppHandle, name: Option<String>) -> Result<(), String> {
+    let mut presets = load(app);
+    let next = match &name {
+        Some(name) => presets
+            .presets
+            .get(name)
+            .cloned()
+            .ok_or(format!("Unknown mapping preset: {name}"))?,
+        None => Mapping::new(),
+    };
+    let previous = presets
+        .active
+        .as_ref()
+        .and_then(|n| presets.presets.get(n))
+        .cloned()
+        .unwrap_or_default();
+
+    let port = sidecar::current_port(app);
+    let known = known_models(port).await;
+    let (previous, next) = (expand(&previous, &known), expand(&next, &known));
+    // Models only the old preset remapped go back to being served as themselves
+    for model in previous.keys().filter(|m| !next.contains_key(*m)) {
+        set_mapping(port, model, "").await?;
+    }
+    for (model, target) in &next {
+        set_mapping(port, model, target).await?;
+    }
+
+    log::info!(
+        "model mapping preset: {}",
+        name.as_deref().unwrap_or("none")
+    );
+    let detail = json!({ "preset": name });
+    presets.active = name;
+    save(app, &presets)?;
+    audit::record(app, "mapping.apply", detail);
+    tray::refresh(app);
+    Ok(())
+}
+
+/// Claude model class of a requested id, e.g. "sonnet" for claude-3-5-sonnet-20241022
+fn tier_of(model: &str) -> Option<&'static str> {
+    let model = model.to_ascii_lowercase();
+    if !model.starts_with("claude") {
+        return None;
+    }
+    TIERS.into_iter().find(|tier| model.contains(tier))
+}
+
+// Replace tier rules with one exact entry per known model of that tier
+fn expand(mapping: &Mapping, known: &[String]) -> Mapping {
+    let mut out: Mapping = mapping
+        .iter()
+        .filter(|(k, _)| !k.starts_with(TIER_PREFIX))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    for model in known {
+        let Some(tier) = tier_of(model) else {
+            continue;
+        };
+        if let Some(target) = mapping.get(&format!("{TIER_PREFIX}{tier}")) {
+            out.entry(model.clone()).or_insert_with(|| target.clone());
+        }
+    }
+    out
+}
+
+async fn known_models(port: u16) -> Vec<String> {
+    let mut models: Vec<String> = KNOWN_CLAUDE_MODELS.map(String::from).to_vec();
+    match api::get::<Value>(port, "/v1/models").await {
+        Ok(listed) => models.extend(listed_ids(&listed)),
+        Err(e) => log::warn!("could not list engine models for tier rules: {e}"),
+    }
+    models.sort();
+    models.dedup();
+    models
+}
+
+// Model ids in a /v1/models response ({"data": [{"id": ...}, ...]})
+fn listed_ids(listed: &Value) -> Vec<String> {
+    listed["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m["id"].as_str())
+        .map(String::from)
+        .collect()
+}
+
+async fn set_mapping(port: u16, model: &str, target: &str) -> Result<(), String> {
+    api::post::<serde_json::Value>(
+        port,
+        "/api/models/config",
+        &json!({ "modelId": model, "config": { "mapping": target } }),
+    )
+    .await
+    .map(|_| ())
+}
+
+pub fn presets_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(PRESETS_FILE))
+}
+
+pub fn load(app: &AppHandle) -> MappingPresets {
+    presets_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, presets: &MappingPresets) -> Result<(), String> {
+    let path = presets_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(presets).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed from the engine's answer to GET /v1/models
+    const MODELS_RESPONSE: &str = r#"{
+        "object": "list",
+        "data": [
+            { "id": "claude-sonnet-4-5", "object": "model", "created": 1759104000, "owned_by": "anthropic", "description": "Claude Sonnet 4.5" },
+            { "id": "claude-sonnet-4-5-thinking", "object": "model", "created": 1759104000, "owned_by": "anthropic", "description": "Claude Sonnet 4.5 (Thinking)" },
+            { "id": "claude-opus-4-5-thinking", "object": "model", "created": 1764028800, "owned_by": "anthropic", "description": "Claude Opus 4.5 (Thinking)" },
+            { "id": "gemini-3-pro-high", "object": "model", "created": 1763424000, "owned_by": "google", "description": "Gemini 3 Pro (High)" },
+            { "id": "gemini-2.5-flash", "object": "model", "created": 1750118400, "owned_by": "google", "description": "Gemini 2.5 Flash" }
+        ]
+    }"#;
+
+    fn known() -> Vec<String> {
+        let listed: Value = serde_json::from_str(MODELS_RESPONSE).unwrap();
+        let mut models: Vec<String> = KNOWN_CLAUDE_MODELS.map(String::from).to_vec();
+        models.extend(listed_ids(&listed));
+        models
+    }
+
+    fn mapping(entries: &[(&str, &str)]) -> Mapping {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn reads_ids_from_the_models_list() {
+        let listed: Value = serde_json::from_str(MODELS_RESPONSE).unwrap();
+        assert_eq!(
+            listed_ids(&listed),
+            [
+                "claude-sonnet-4-5",
+                "claude-sonnet-4-5-thinking",
+                "claude-opus-4-5-thinking",
+                "gemini-3-pro-high",
+                "gemini-2.5-flash",
+            ]
+        );
+        assert!(listed_ids(&json!({ "error": "not ready" })).is_empty());
+    }
+
+    #[test]
+    fn every_known_claude_model_has_a_tier() {
+        for model in known().iter().filter(|m| m.starts_with("claude")) {
+            assert!(tier_of(model).is_some(), "{model}");
+        }
+        assert_eq!(tier_of("claude-3-5-haiku-20241022"), Some("haiku"));
+        assert_eq!(tier_of("claude-3-7-sonnet-20250219"), Some("sonnet"));
+        assert_eq!(tier_of("Claude-Opus-4-5-Thinking"), Some("opus"));
+        assert_eq!(tier_of("gemini-3-pro-high"), None);
+        assert_eq!(tier_of("gpt-4o"), None);
+    }
+
+    #[test]
+    fn expands_tier_rules_to_every_model_of_the_tier() {
+        let expanded = expand(&mapping(&[("tier:opus", "gemini-3-pro-high")]), &known());
+        assert_eq!(
+            expanded.keys().collect::<Vec<_>>(),
+            [
+                "claude-opus-4-1-20250805",
+                "claude-opus-4-20250514",
+                "claude-opus-4-5",
+                "claude-opus-4-5-thinking",
+            ]
+        );
+        assert!(expanded.values().all(|v| v == "gemini-3-pro-high"));
+    }
+
+    #[test]
+    fn exact_entries_beat_tier_rules() {
+        let expanded = expand(
+            &mapping(&[
+                ("tier:sonnet", "gemini-2.5-flash"),
+                ("claude-sonnet-4-5", "claude-sonnet-4-5-thinking"),
+                ("gemini-3-pro-high", "claude-opus-4-5-thinking"),
+            ]),
+            &known(),
+        );
+        assert_eq!(expanded["claude-sonnet-4-5"], "claude-sonnet-4-5-thinking");
+        assert_eq!(expanded["claude-3-5-sonnet-20241022"], "gemini-2.5-flash");
+        assert_eq!(expanded["claude-sonnet-4-5-thinking"], "gemini-2.5-flash");
+        assert_eq!(expanded["gemini-3-pro-high"], "claude-opus-4-5-thinking");
+        assert!(!expanded.contains_key("tier:sonnet"));
+        assert!(!expanded.contains_key("claude-haiku-4-5"));
+    }
+}
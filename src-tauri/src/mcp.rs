@@ -0,0 +1,402 @@
+// Model Context Protocol server, so agents and editors can check on and steer the
+// proxy themselves. Speaks the Streamable HTTP transport in its simplest form:
+// every JSON-RPC message is POSTed to http://127.0.0.1:<mcpPort>/mcp and answered
+// with one JSON response, without SSE streams or sessions. Off unless "mcpPort" is
+// set, and only ever bound to loopback. Requests with a non-local Origin header are
+// refused, so a web page can't reach it through DNS rebinding, and every request
+// must carry the bearer token shown in Settings (kept in the OS keychain), so other
+// local programs can't use it either. While the app is locked, tools that change
+// anything are refused.
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+
+use crate::{accounts, api, audit, lock, quota, settings, sidecar, traffic, vault};
+
+const MCP_PATH: &str = "/mcp";
+const KEYCHAIN_USER: &str = "mcp-token";
+const SERVER_NAME: &str = "antigravity-claude-proxy";
+// Newest first; anything else the client asks for gets the newest
+const PROTOCOL_VERSIONS: [&str; 2] = ["2025-06-18", "2025-03-26"];
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct Listener {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    // Shared with the connection threads so a reset applies right away
+    token: Arc<Mutex<String>>,
+}
+
+#[derive(Default)]
+pub struct McpState(Mutex<Option<Listener>>);
+
+pub fn spawn(app: AppHandle) {
+    let Some(port) = settings::get_u64(&settings::load(&app), "mcpPort") else {
+        return;
+    };
+    if let Err(e) = u16::try_from(port)
+        .map_err(|e| e.to_string())
+        .and_then(|port| start(&app, port))
+    {
+        log::warn!("MCP server not started: {e}");
+    }
+}
+
+/// Serve MCP on a loopback port, or stop serving it with `None`
+#[tauri::command]
+pub fn set_mcp_port(app: AppHandle, port: Option<u16>) -> Result<(), String> {
+    let port = port.filter(|&p| p != 0);
+    let current = app
+        .state::<McpState>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|l| l.port);
+    if current == port {
+        return Ok(());
+    }
+    stop(&app);
+    if let Some(port) = port {
+        start(&app, port)?;
+    }
+    settings::update(&app, "mcpPort", port.into())?;
+    audit::record(&app, "mcp.port", json!({ "port": port }));
+    Ok(())
+}
+
+/// Bearer token for the MCP client's config, created on first use
+#[tauri::command]
+pub fn get_mcp_token(app: AppHandle) -> Result<String, String> {
+    lock::ensure_unlocked(&app)?;
+    load_token()
+}
+
+/// Replace the token; clients still using the old one are turned away
+#[tauri::command]
+pub fn reset_mcp_token(app: AppHandle) -> Result<String, String> {
+    lock::ensure_unlocked(&app)?;
+    let token = vault::random_token();
+    entry()?.set_password(&token).map_err(|e| e.to_string())?;
+    if let Some(listener) = app.state::<McpState>().0.lock().unwrap().as_ref() {
+        *listener.token.lock().unwrap() = token.clone();
+    }
+    audit::record(&app, "mcp.tokenReset", Value::Null);
+    Ok(token)
+}
+
+/// Remove the token from the keychain; a new one is made on next use
+pub fn delete_token() -> Result<(), String> {
+    match entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn load_token() -> Result<String, String> {
+    let entry = entry()?;
+    match entry.get_password() {
+        Ok(token) => Ok(token),
+        Err(keyring::Error::NoEntry) => {
+            let token = vault::random_token();
+            entry.set_password(&token).map_err(|e| e.to_string())?;
+            log::info!("created MCP token in the OS keychain");
+            Ok(token)
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(vault::KEYCHAIN_SERVICE, KEYCHAIN_USER).map_err(|e| e.to_string())
+}
+
+fn start(app: &AppHandle, port: u16) -> Result<(), String> {
+    let token = Arc::new(Mutex::new(load_token()?));
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| format!("Port {port} is not available: {e}"))?;
+    let stop = Arc::new(AtomicBool::new(false));
+    *app.state::<McpState>().0.lock().unwrap() = Some(Listener {
+        port,
+        stop: stop.clone(),
+        token: token.clone(),
+    });
+    log::info!("MCP server listening on http://127.0.0.1:{port}{MCP_PATH}");
+
+    let app = app.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let app = app.clone();
+            let token = token.clone();
+            thread::spawn(move || {
+                if let Err(e) = handle(&app, &token, stream) {
+                    log::debug!("MCP connection failed: {e}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+fn stop(app: &AppHandle) {
+    let Some(listener) = app.state::<McpState>().0.lock().unwrap().take() else {
+        return;
+    };
+    listener.stop.store(true, Ordering::Relaxed);
+    // accept() only notices the flag once something connects
+    let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, listener.port));
+}
+
+fn handle(app: &AppHandle, token: &Mutex<String>, mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut length = 0;
+    let mut origin = None;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => length = value.trim().parse().unwrap_or(0),
+                "origin" => origin = Some(value.trim().to_string()),
+                "authorization" => authorization = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    if origin.as_deref().is_some_and(|o| !is_local_origin(o)) {
+        return respond(&mut stream, 403, None);
+    }
+    let expected = format!("Bearer {}", token.lock().unwrap());
+    if !authorization.is_some_and(|a| same_secret(&a, &expected)) {
+        return respond(&mut stream, 401, None);
+    }
+    if path.split('?').next() != Some(MCP_PATH) {
+        return respond(&mut stream, 404, None);
+    }
+    // No server-initiated stream to GET
+    if method != "POST" {
+        return respond(&mut stream, 405, None);
+    }
+    if length > MAX_BODY_BYTES {
+        return respond(&mut stream, 413, None);
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let reply = match serde_json::from_slice::<Value>(&body) {
+        Ok(message) if message.is_object() => dispatch(app, &message),
+        Ok(_) => Some(error(
+            Value::Null,
+            INVALID_REQUEST,
+            "Expected a single JSON-RPC message",
+        )),
+        Err(e) => Some(error(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+    match reply {
+        Some(reply) => respond(&mut stream, 200, Some(&reply)),
+        None => respond(&mut stream, 202, None),
+    }
+}
+
+fn is_local_origin(origin: &str) -> bool {
+    reqwest::Url::parse(origin)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]"))
+}
+
+// Takes as long for a near miss as for a wrong first byte
+fn same_secret(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: Option<&Value>) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Payload Too Large",
+    };
+    let body = body.map(Value::to_string).unwrap_or_default();
+    let mut head = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        body.len()
+    );
+    if !body.is_empty() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    if status == 401 {
+        head.push_str("WWW-Authenticate: Bearer\r\n");
+    }
+    if status == 405 {
+        head.push_str("Allow: POST\r\n");
+    }
+    write!(stream, "{head}\r\n{body}")?;
+    stream.flush()
+}
+
+// Notifications (no id) are acknowledged without a reply
+fn dispatch(app: &AppHandle, message: &Value) -> Option<Value> {
+    let id = message.get("id").filter(|id| !id.is_null())?.clone();
+    let method = message["method"].as_str().unwrap_or_default();
+    let params = &message["params"];
+
+    let result = match method {
+        "initialize" => {
+            let requested = params["protocolVersion"].as_str().unwrap_or_default();
+            let version = PROTOCOL_VERSIONS
+                .into_iter()
+                .find(|v| *v == requested)
+                .unwrap_or(PROTOCOL_VERSIONS[0]);
+            Ok(json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": SERVER_NAME,
+                    "version": app.package_info().version.to_string(),
+                },
+            }))
+        }
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => call_tool(app, params),
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method {method}"))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error(id, code, &message),
+    })
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn tools() -> Value {
+    let no_arguments = json!({ "type": "object", "properties": {} });
+    json!([
+        {
+            "name": "get_proxy_status",
+            "description": "Whether the proxy is running, its port, requests in progress, the accounts it rotates through, the pinned account and recent errors.",
+            "inputSchema": no_arguments,
+        },
+        {
+            "name": "switch_account",
+            "description": "Send all traffic to one account, or go back to automatic rotation when email is left out. Returns the new status.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "email": {
+                        "type": "string",
+                        "description": "Account to pin; leave out for automatic rotation",
+                    },
+                },
+            },
+        },
+        {
+            "name": "get_usage",
+            "description": "Usage history recorded by the proxy, upcoming quota resets per account and model, and error counts.",
+            "inputSchema": no_arguments,
+        },
+    ])
+}
+
+fn call_tool(app: &AppHandle, params: &Value) -> Result<Value, (i64, String)> {
+    let outcome = match params["name"].as_str().unwrap_or_default() {
+        "get_proxy_status" => Ok(proxy_status(app)),
+        // The app lock covers changes from agents as much as from the window
+        "switch_account" => lock::ensure_unlocked(app).and_then(|_| {
+            let email = params["arguments"]["email"]
+                .as_str()
+                .map(str::to_string)
+                .filter(|e| !e.is_empty());
+            tauri::async_runtime::block_on(accounts::pin_account(app.clone(), email))
+                .map(|_| proxy_status(app))
+        }),
+        "get_usage" => tauri::async_runtime::block_on(usage(app)),
+        name => return Err((INVALID_PARAMS, format!("Unknown tool {name}"))),
+    };
+    // A failing tool is a result the model gets to read, not a protocol error
+    Ok(match outcome {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": value.to_string() }],
+            "structuredContent": value,
+            "isError": false,
+        }),
+        Err(e) => json!({
+            "content": [{ "type": "text", "text": e }],
+            "isError": true,
+        }),
+    })
+}
+
+fn proxy_status(app: &AppHandle) -> Value {
+    let pid = app.state::<sidecar::SidecarState>().pid();
+    let (accounts, pinned) = accounts::snapshot(app);
+    json!({
+        "running": pid.is_some(),
+        "port": sidecar::current_port(app),
+        "pid": pid,
+        "inFlight": traffic::in_flight(app),
+        "pinnedAccount": pinned,
+        "accounts": accounts
+            .iter()
+            .map(|a| json!({ "email": a.email, "enabled": a.enabled }))
+            .collect::<Vec<_>>(),
+        "recentErrors": traffic::recent_errors(app),
+    })
+}
+
+async fn usage(app: &AppHandle) -> Result<Value, String> {
+    if app.state::<sidecar::SidecarState>().pid().is_none() {
+        return Err("The proxy is not running".into());
+    }
+    let history = api::get::<Value>(sidecar::current_port(app), "/api/stats/history").await?;
+    Ok(json!({
+        "history": history,
+        "quotaResets": quota::get_quota_resets(app.state()),
+        "errors": traffic::get_error_counters(app.state()),
+    }))
+}
@@ -0,0 +1,93 @@
+// Periodic CPU/memory sampling of the proxy engine process tree
+use std::{collections::HashSet, thread, time::Duration};
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{idle, sidecar::SidecarState};
+
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+// Above this the engine is most likely leaking during a long session
+const MEMORY_WARN_BYTES: u64 = 1536 * 1024 * 1024;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTick {
+    pid: u32,
+    cpu_percent: f32,
+    memory_bytes: u64,
+    process_count: usize,
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        let mut warned = false;
+
+        loop {
+            idle::pause(&app, SAMPLE_INTERVAL);
+
+            let Some(pid) = app.state::<SidecarState>().pid() else {
+                warned = false;
+                continue;
+            };
+
+            sys.refresh_processes(ProcessesToUpdate::All, true);
+            let Some(tick) = sample(&sys, pid) else {
+                continue;
+            };
+
+            if tick.memory_bytes > MEMORY_WARN_BYTES && !warned {
+                warned = true;
+                log::warn!(
+                    "engine memory at {} MiB (pid {pid}), possible leak",
+                    tick.memory_bytes / 1024 / 1024
+                );
+                let _ = app.emit("resource-warning", tick.clone());
+            } else if tick.memory_bytes <= MEMORY_WARN_BYTES {
+                warned = false;
+            }
+
+            let _ = app.emit("resource-tick", tick);
+        }
+    });
+}
+
+// The spawned binary is a launcher script, so the real node process is a descendant
+fn sample(sys: &System, root: u32) -> Option<ResourceTick> {
+    let tree = process_tree(sys, root)?;
+
+    let (cpu_percent, memory_bytes) = tree
+        .iter()
+        .filter_map(|pid| sys.process(*pid))
+        .fold((0.0, 0), |(cpu, mem), p| {
+            (cpu + p.cpu_usage(), mem + p.memory())
+        });
+
+    Some(ResourceTick {
+        pid: root,
+        cpu_percent,
+        memory_bytes,
+        process_count: tree.len(),
+    })
+}
+
+/// `root` and every process descended from it, or `None` once `root` has exited
+pub fn process_tree(sys: &System, root: u32) -> Option<HashSet<Pid>> {
+    let root_pid = Pid::from_u32(root);
+    sys.process(root_pid)?;
+
+    let mut tree = HashSet::from([root_pid]);
+    loop {
+        let before = tree.len();
+        for (pid, process) in sys.processes() {
+            if process.parent().is_some_and(|p| tree.contains(&p)) {
+                tree.insert(*pid);
+            }
+        }
+        if tree.len() == before {
+            return Some(tree);
+        }
+    }
+}
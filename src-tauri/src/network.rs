@@ -0,0 +1,123 @@
+// Network change detection (Wi-Fi switch, VPN up/down) by fingerprinting the
+// interfaces and their addresses. A change gets the same re-validation as a resume.
+// The engine's pooled upstream connections are bound to the old route and die
+// silently, and the engine can't be told to drop them, so it is restarted once
+// nothing is in flight.
+use std::{
+    collections::BTreeSet,
+    net::{IpAddr, Ipv6Addr},
+    thread,
+    time::Duration,
+};
+
+use sysinfo::Networks;
+use tauri::{AppHandle, Emitter};
+
+use crate::{idle, power, sidecar};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || {
+        let mut networks = Networks::new_with_refreshed_list();
+        let mut current = fingerprint(&networks);
+        // A switch shows up as several intermediate states; act once it settles
+        let mut pending: Option<Fingerprint> = None;
+
+        loop {
+            idle::pause(&app, POLL_INTERVAL);
+            networks.refresh(true);
+            let seen = fingerprint(&networks);
+
+            if seen == current {
+                pending = None;
+                continue;
+            }
+            if pending.as_ref() != Some(&seen) {
+                pending = Some(seen);
+                continue;
+            }
+
+            let (removed, added) = (
+                current.difference(&seen).count(),
+                seen.difference(&current).count(),
+            );
+            log::info!("network changed ({added} added, {removed} removed), reconnecting");
+            current = seen;
+            pending = None;
+
+            let _ = app.emit("network-change", ());
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                power::revalidate(app.clone(), "network-ready").await;
+                if let Err(e) = sidecar::restart_when_idle(&app).await {
+                    log::warn!("engine restart after network change failed: {e}");
+                }
+            });
+        }
+    });
+}
+
+// Every interface by name (None), plus what it is connected to
+type Fingerprint = BTreeSet<(String, Option<IpAddr>)>;
+
+fn fingerprint(networks: &Networks) -> Fingerprint {
+    networks
+        .iter()
+        .flat_map(|(name, data)| {
+            let addrs = data
+                .ip_networks()
+                .iter()
+                .filter_map(|net| stable_part(net.addr))
+                .map(Some);
+            std::iter::once(None)
+                .chain(addrs)
+                .map(move |addr| (name.clone(), addr))
+        })
+        .collect()
+}
+
+// IPv6 hosts rotate temporary addresses every few hours and link-local ones say
+// nothing about the route, so only the /64 prefix of a routable IPv6 address counts
+fn stable_part(addr: IpAddr) -> Option<IpAddr> {
+    match addr {
+        IpAddr::V4(v4) if v4.is_loopback() || v4.is_link_local() => None,
+        IpAddr::V4(_) => Some(addr),
+        IpAddr::V6(v6) if v6.is_loopback() || v6.is_unicast_link_local() => None,
+        IpAddr::V6(v6) => {
+            let prefix = u128::from(v6) & !((1u128 << 64) - 1);
+            Some(IpAddr::V6(Ipv6Addr::from(prefix)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn keeps_routable_ipv4() {
+        assert_eq!(stable_part(ip("192.168.1.20")), Some(ip("192.168.1.20")));
+        assert_eq!(stable_part(ip("127.0.0.1")), None);
+        assert_eq!(stable_part(ip("169.254.10.1")), None);
+    }
+
+    #[test]
+    fn reduces_ipv6_to_its_prefix() {
+        // A temporary address and the stable one on the same network agree
+        assert_eq!(
+            stable_part(ip("2001:db8:1:2:a1b2:c3d4:e5f6:789")),
+            Some(ip("2001:db8:1:2::"))
+        );
+        assert_eq!(
+            stable_part(ip("2001:db8:1:2:21a:2bff:fe3c:4d5e")),
+            Some(ip("2001:db8:1:2::"))
+        );
+        assert_eq!(stable_part(ip("fe80::21a:2bff:fe3c:4d5e")), None);
+        assert_eq!(stable_part(ip("::1")), None);
+    }
+}
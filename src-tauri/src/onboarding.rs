@@ -0,0 +1,327 @@
+// First-run onboarding: port -> Google sign-in -> Claude CLI config -> test request.
+// Each step is carried out here rather than in the wizard, so a step only counts
+// as done once the engine, the account and the client actually work.
+use std::{
+    net::TcpListener,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_http::reqwest;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::{api, settings, sidecar};
+
+const RECORD_FILE: &str = "onboarding.json";
+const ENGINE_START_TIMEOUT: Duration = Duration::from_secs(30);
+const SIGN_IN_TIMEOUT: Duration = Duration::from_secs(120);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const TEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OnboardingStep {
+    #[default]
+    Port,
+    SignIn,
+    ClientConfig,
+    TestRequest,
+    Done,
+}
+
+impl OnboardingStep {
+    fn next(self) -> Self {
+        match self {
+            Self::Port => Self::SignIn,
+            Self::SignIn => Self::ClientConfig,
+            Self::ClientConfig => Self::TestRequest,
+            Self::TestRequest | Self::Done => Self::Done,
+        }
+    }
+}
+
+/// Progress so far; `step` is the first step that still has to be completed
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingState {
+    step: OnboardingStep,
+    port: Option<u16>,
+    account: Option<String>,
+    preset: Option<String>,
+    test_reply: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StepInput {
+    port: Option<u16>,
+    preset: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AuthUrl {
+    url: Option<String>,
+}
+
+#[tauri::command]
+pub fn get_onboarding_state(app: AppHandle) -> OnboardingState {
+    load_record(&app)
+}
+
+/// Carry out one step and advance. Earlier steps can be run again (e.g. to pick a
+/// different preset); later ones are refused until the steps before them are done.
+#[tauri::command]
+pub async fn complete_onboarding_step(
+    app: AppHandle,
+    step: OnboardingStep,
+    input: Option<Value>,
+) -> Result<OnboardingState, String> {
+    let mut state = load_record(&app);
+    if step > state.step {
+        return Err(format!("Complete the {:?} step first", state.step));
+    }
+    let input: StepInput = match input {
+        Some(value) => serde_json::from_value(value).map_err(|e| e.to_string())?,
+        None => StepInput::default(),
+    };
+    let port = input
+        .port
+        .or(state.port)
+        .unwrap_or_else(|| sidecar::current_port(&app));
+
+    match step {
+        OnboardingStep::Port => {
+            start_engine(&app, port).await?;
+            state.port = Some(port);
+        }
+        OnboardingStep::SignIn => state.account = Some(sign_in(&app, port).await?),
+        OnboardingStep::ClientConfig => {
+            let preset = input
+                .preset
+                .or(state.preset.take())
+                .unwrap_or_else(|| "claude".into());
+            write_client_config(&app, &preset, port)?;
+            state.preset = Some(preset);
+        }
+        OnboardingStep::TestRequest => {
+            let preset = state.preset.as_deref().unwrap_or("claude");
+            state.test_reply = Some(test_request(port, preset).await?);
+        }
+        OnboardingStep::Done => return Err("Onboarding is already complete".into()),
+    }
+
+    state.step = state.step.max(step.next());
+    if state.step == OnboardingStep::Done {
+        settings::update(&app, "setupCompleted", true.into())?;
+    }
+    save_record(&app, &state)?;
+    log::info!("onboarding: {step:?} done, next {:?}", state.step);
+    Ok(state)
+}
+
+// Save the port and get our engine answering on it
+async fn start_engine(app: &AppHandle, port: u16) -> Result<(), String> {
+    let running_here =
+        sidecar::current_port(app) == port && api::get::<Value>(port, "/health").await.is_ok();
+    if !running_here {
+        if let Err(e) = TcpListener::bind(("127.0.0.1", port)) {
+            return Err(format!("Port {port} is held by another process: {e}"));
+        }
+    }
+    settings::update(app, "port", port.into())?;
+    if running_here {
+        return Ok(());
+    }
+
+    let fallback = settings::load(app)
+        .get("fallbackEnabled")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    sidecar::start_proxy(app.clone(), port, fallback)?;
+
+    let started = Instant::now();
+    while started.elapsed() < ENGINE_START_TIMEOUT {
+        if api::get::<Value>(port, "/health").await.is_ok() {
+            return Ok(());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Err(format!(
+        "The proxy did not answer on port {port} within {}s",
+        ENGINE_START_TIMEOUT.as_secs()
+    ))
+}
+
+// Open Google sign-in in the browser and wait for the engine to pick up the account
+async fn sign_in(app: &AppHandle, port: u16) -> Result<String, String> {
+    let first_account = || async move {
+        api::get::<api::AccountLimits>(port, "/account-limits")
+            .await
+            .ok()
+            .and_then(|l| l.accounts.into_iter().next())
+            .map(|a| a.email)
+    };
+    if let Some(email) = first_account().await {
+        return Ok(email);
+    }
+
+    let url = api::get::<AuthUrl>(port, "/api/auth/url")
+        .await?
+        .url
+        .ok_or("The proxy did not return a sign-in URL")?;
+    app.opener()
+        .open_url(url, None::<&str>)
+        .map_err(|e| e.to_string())?;
+
+    let started = Instant::now();
+    while started.elapsed() < SIGN_IN_TIMEOUT {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if let Some(email) = first_account().await {
+            return Ok(email);
+        }
+    }
+    Err("Sign-in was not completed in the browser".into())
+}
+
+// Same presets as claudeCliService.ts; [1m] asks Claude Code for the long context window
+fn preset_env(preset: &str, port: u16) -> Result<Vec<(&'static str, String)>, String> {
+    let (main, opus, sonnet) = match preset {
+        "claude" => (
+            "claude-opus-4-5-thinking",
+            "claude-opus-4-5-thinking",
+            "claude-sonnet-4-5-thinking",
+        ),
+        "gemini" => (
+            "gemini-3-pro-high[1m]",
+            "gemini-3-pro-high[1m]",
+            "gemini-3-flash[1m]",
+        ),
+        other => return Err(format!("Unknown preset \"{other}\"")),
+    };
+    Ok(vec![
+        ("ANTHROPIC_AUTH_TOKEN", "test".into()),
+        ("ANTHROPIC_BASE_URL", api::base_url(port)),
+        ("ANTHROPIC_MODEL", main.into()),
+        ("ANTHROPIC_DEFAULT_OPUS_MODEL", opus.into()),
+        ("ANTHROPIC_DEFAULT_SONNET_MODEL", sonnet.into()),
+        (
+            "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+            "gemini-2.5-flash-lite[1m]".into(),
+        ),
+        ("CLAUDE_CODE_SUBAGENT_MODEL", sonnet.into()),
+        ("ENABLE_EXPERIMENTAL_MCP_CLI", "true".into()),
+    ])
+}
+
+fn client_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .home_dir()
+        .map_err(|e| e.to_string())?
+        .join(".claude")
+        .join("settings.json"))
+}
+
+// Merge the preset into ~/.claude/settings.json, keeping everything else the user has there
+fn write_client_config(app: &AppHandle, preset: &str, port: u16) -> Result<(), String> {
+    let path = client_settings_path(app)?;
+    let mut config: serde_json::Map<String, Value> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    let env = config
+        .entry("env")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or("\"env\" in ~/.claude/settings.json is not an object")?;
+    for (key, value) in preset_env(preset, port)? {
+        env.insert(key.into(), value.into());
+    }
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+// One small request through the configured sonnet model, as Claude Code would send it
+async fn test_request(port: u16, preset: &str) -> Result<String, String> {
+    let model = preset_env(preset, port)?
+        .into_iter()
+        .find(|(key, _)| *key == "ANTHROPIC_DEFAULT_SONNET_MODEL")
+        .map(|(_, model)| model.trim_end_matches("[1m]").to_string())
+        .ok_or("Preset has no test model")?;
+
+    let client = reqwest::Client::builder()
+        .timeout(TEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .post(format!("{}/v1/messages", api::base_url(port)))
+        .header("Content-Type", "application/json")
+        .header("anthropic-version", "2023-06-01")
+        .header("Authorization", "Bearer test")
+        .body(
+            json!({
+                "model": model,
+                "max_tokens": 32,
+                "messages": [{ "role": "user", "content": "Reply with the single word OK." }],
+            })
+            .to_string(),
+        )
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    let body: Value = response.json().await.map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        let message = body
+            .pointer("/error/message")
+            .and_then(Value::as_str)
+            .unwrap_or("no error message");
+        return Err(format!(
+            "Test request to {model} failed ({status}): {message}"
+        ));
+    }
+
+    Ok(body["content"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|block| block["text"].as_str())
+        .collect::<Vec<_>>()
+        .join("")
+        .trim()
+        .to_string())
+}
+
+fn record_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(RECORD_FILE))
+}
+
+fn load_record(app: &AppHandle) -> OnboardingState {
+    record_path(app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_record(app: &AppHandle, state: &OnboardingState) -> Result<(), String> {
+    let path = record_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
@@ -0,0 +1,103 @@
+// Sleep/resume awareness. There is no portable suspend event, so a resume is
+// detected as a jump in wall-clock time between two short ticks; the checks that
+// follow are cheap enough that a manual clock change triggering them is harmless.
+// Nothing happens before the machine goes to sleep: pausing the engine's health
+// checks and parking open streams would need an OS suspend hook and engine support.
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{alerts, api, credentials, diagnostics, sidecar};
+
+const TICK: Duration = Duration::from_secs(5);
+// Anything beyond this between ticks means the machine was asleep
+const SLEEP_GAP: Duration = Duration::from_secs(30);
+// Failures right after wake are expected and shouldn't raise a notification
+const RESUME_GRACE: Duration = Duration::from_secs(2 * 60);
+const NETWORK_ATTEMPTS: usize = 6;
+const NETWORK_RETRY: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeInfo {
+    slept_secs: u64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revalidated {
+    upstream_ok: bool,
+    tokens_refreshed: bool,
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || {
+        let mut last = SystemTime::now();
+        loop {
+            thread::sleep(TICK);
+            let now = SystemTime::now();
+            let gap = now.duration_since(last).unwrap_or_default();
+            last = now;
+
+            if gap > TICK + SLEEP_GAP {
+                log::info!("system resumed after ~{}s asleep", gap.as_secs());
+                let _ = app.emit(
+                    "power-resume",
+                    ResumeInfo {
+                        slept_secs: gap.as_secs(),
+                    },
+                );
+                tauri::async_runtime::spawn(revalidate(app.clone(), "power-ready"));
+            }
+        }
+    });
+}
+
+/// Wait for the network, drop stale tokens and re-check credentials, then emit `ready_event`
+pub async fn revalidate(app: AppHandle, ready_event: &'static str) {
+    alerts::mute(&app, RESUME_GRACE);
+
+    let mut upstream_ok = false;
+    for attempt in 1..=NETWORK_ATTEMPTS {
+        let ((status, detail), _) =
+            tauri::async_runtime::spawn_blocking(diagnostics::resolve_upstreams)
+                .await
+                .unwrap_or(((diagnostics::CheckStatus::Fail, String::new()), Vec::new()));
+        if status == diagnostics::CheckStatus::Pass {
+            upstream_ok = true;
+            break;
+        }
+        log::debug!("network not back yet (attempt {attempt}): {detail}");
+        tokio::time::sleep(NETWORK_RETRY).await;
+    }
+
+    // Cached access tokens have often expired while asleep or offline
+    let mut tokens_refreshed = false;
+    if upstream_ok && app.state::<sidecar::SidecarState>().pid().is_some() {
+        match api::post::<serde_json::Value>(
+            sidecar::current_port(&app),
+            "/refresh-token",
+            &json!({}),
+        )
+        .await
+        {
+            Ok(_) => tokens_refreshed = true,
+            Err(e) => log::warn!("token refresh after reconnect failed: {e}"),
+        }
+    }
+    credentials::check(&app).await;
+
+    log::info!("revalidated for {ready_event} (upstream ok: {upstream_ok}, tokens refreshed: {tokens_refreshed})");
+    let _ = app.emit(
+        ready_event,
+        Revalidated {
+            upstream_ok,
+            tokens_refreshed,
+        },
+    );
+}
@@ -0,0 +1,257 @@
+// Token pricing for cost estimates. The base table is whichever has the higher
+// version of the one bundled with the app and the last verified remote table
+// (cached in app data so it works offline); a hand-edited pricing.override.json
+// in the config dir wins over it per model. The remote table is only accepted with
+// a valid P-256 signature.
+use std::{collections::BTreeMap, fs, path::PathBuf, thread, time::Duration};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::{api, settings, traffic};
+
+const BUNDLED: &str = include_str!("../pricing.json");
+const CACHE_FILE: &str = "pricing.json";
+const OVERRIDE_FILE: &str = "pricing.override.json";
+const DEFAULT_URL: &str =
+    "https://raw.githubusercontent.com/StRonKEA/Antigravity-Claude-Proxy-GUI/main/src-tauri/pricing.json";
+// Base64 SPKI DER of the key release tables are signed with. Remote updates stay
+// off until one is set here or through the `pricingPublicKey` setting.
+const PUBLIC_KEY: Option<&str> = None;
+const REFRESH_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// USD per million tokens
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ModelPrice {
+    input: f64,
+    output: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingTable {
+    version: String,
+    currency: String,
+    /// Keyed by model id prefix, so "claude-opus-4-5" also prices "claude-opus-4-5-thinking"
+    models: BTreeMap<String, ModelPrice>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricingInfo {
+    #[serde(flatten)]
+    table: PricingTable,
+    /// "bundled" or "remote", plus "+override" when a local override applies
+    source: String,
+    fetched_at: Option<u64>,
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostEstimate {
+    currency: String,
+    total: f64,
+    by_model: BTreeMap<String, f64>,
+    /// Models the table has no price for; their tokens are left out of the total
+    unpriced: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedTable {
+    fetched_at: u64,
+    table: PricingTable,
+}
+
+impl PricingTable {
+    // Longest matching prefix wins, so "gemini-2.5-flash-lite" doesn't get flash prices
+    fn price(&self, model: &str) -> Option<ModelPrice> {
+        let model = model.trim_end_matches("[1m]");
+        self.models
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| *price)
+    }
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        if let Err(e) = tauri::async_runtime::block_on(refresh(&app)) {
+            log::info!("pricing table not updated: {e}");
+        }
+        thread::sleep(REFRESH_INTERVAL);
+    });
+}
+
+fn info(app: &AppHandle) -> PricingInfo {
+    let bundled: PricingTable =
+        serde_json::from_str(BUNDLED).expect("bundled pricing table is valid");
+    // An app update can ship prices newer than the last download
+    let (mut table, mut source, fetched_at) = match load_cache(app) {
+        Some(cached) if cached.table.version > bundled.version => {
+            (cached.table, "remote".to_string(), Some(cached.fetched_at))
+        }
+        _ => (bundled, "bundled".to_string(), None),
+    };
+
+    if let Some(overrides) = load_override(app) {
+        table.models.extend(overrides);
+        source.push_str("+override");
+    }
+    PricingInfo {
+        table,
+        source,
+        fetched_at,
+    }
+}
+
+#[tauri::command]
+pub fn get_pricing_table(app: AppHandle) -> PricingInfo {
+    info(&app)
+}
+
+/// Fetch the remote table now instead of waiting for the daily refresh
+#[tauri::command]
+pub async fn refresh_pricing_table(app: AppHandle) -> Result<PricingInfo, String> {
+    refresh(&app).await?;
+    Ok(info(&app))
+}
+
+/// Price the engine's usage history (/api/stats/history). Token totals are only
+/// kept per time slot, so each slot's tokens are split across models by request count.
+#[tauri::command]
+pub fn estimate_usage_cost(app: AppHandle, history: Value) -> CostEstimate {
+    let table = info(&app).table;
+    let mut estimate = CostEstimate {
+        currency: table.currency.clone(),
+        ..Default::default()
+    };
+
+    for point in history.as_object().into_iter().flat_map(|h| h.values()) {
+        let tokens = &point["_tokens"];
+        let input = tokens["input"].as_f64().unwrap_or(0.0);
+        let output = tokens["output"].as_f64().unwrap_or(0.0);
+        let requests: Vec<(&String, f64)> = ["claude", "gemini"]
+            .iter()
+            .filter_map(|family| point[family].as_object())
+            .flatten()
+            .filter(|(model, _)| !model.starts_with('_'))
+            .filter_map(|(model, count)| Some((model, count.as_f64()?)))
+            .collect();
+        let total_requests: f64 = requests.iter().map(|(_, n)| n).sum();
+        if total_requests == 0.0 {
+            continue;
+        }
+
+        for (model, count) in requests {
+            let share = count / total_requests;
+            match table.price(model) {
+                Some(price) => {
+                    let cost = (input * price.input + output * price.output) * share / 1e6;
+                    *estimate.by_model.entry(model.clone()).or_insert(0.0) += cost;
+                    estimate.total += cost;
+                }
+                None if !estimate.unpriced.contains(model) => estimate.unpriced.push(model.clone()),
+                None => {}
+            }
+        }
+    }
+    estimate
+}
+
+async fn refresh(app: &AppHandle) -> Result<(), String> {
+    let settings = settings::load(app);
+    let key = settings
+        .get("pricingPublicKey")
+        .and_then(Value::as_str)
+        .or(PUBLIC_KEY)
+        .ok_or("no pricing signing key configured")?;
+    let url = settings
+        .get("pricingUrl")
+        .and_then(Value::as_str)
+        .unwrap_or(DEFAULT_URL);
+
+    let body = fetch_text(url).await?;
+    let signature = fetch_text(&format!("{url}.sig")).await?;
+    verify(key, signature.trim(), body.as_bytes())?;
+
+    let table: PricingTable = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    if load_cache(app).is_some_and(|c| c.table.version > table.version) {
+        return Err(format!(
+            "remote table {} is older than the cached one",
+            table.version
+        ));
+    }
+    log::info!("pricing table {} fetched from {url}", table.version);
+    save_cache(
+        app,
+        &CachedTable {
+            fetched_at: traffic::now_ms(),
+            table,
+        },
+    )
+}
+
+async fn fetch_text(url: &str) -> Result<String, String> {
+    api::client()
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// Signature is base64 DER ECDSA P-256 over the exact file bytes, like npm's registry signatures
+fn verify(key_b64: &str, sig_b64: &str, message: &[u8]) -> Result<(), String> {
+    let key_der = STANDARD.decode(key_b64).map_err(|e| e.to_string())?;
+    let sig_der = STANDARD.decode(sig_b64).map_err(|e| e.to_string())?;
+    let key = VerifyingKey::from_public_key_der(&key_der).map_err(|e| e.to_string())?;
+    let sig = Signature::from_der(&sig_der).map_err(|e| e.to_string())?;
+    key.verify(message, &sig)
+        .map_err(|_| "pricing table signature could not be verified".to_string())
+}
+
+fn cache_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(CACHE_FILE))
+}
+
+fn load_cache(app: &AppHandle) -> Option<CachedTable> {
+    cache_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+}
+
+fn save_cache(app: &AppHandle, cached: &CachedTable) -> Result<(), String> {
+    let path = cache_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(cached).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+// Same shape as the "models" object of the table
+fn load_override(app: &AppHandle) -> Option<BTreeMap<String, ModelPrice>> {
+    let path = settings::config_dir(app).ok()?.join(OVERRIDE_FILE);
+    let content = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(models) => Some(models),
+        Err(e) => {
+            log::warn!("ignoring {}: {e}", path.display());
+            None
+        }
+    }
+}
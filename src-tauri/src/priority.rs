@@ -0,0 +1,130 @@
+// Scheduling priority of the engine process tree ("enginePriority" setting), so
+// heavy streaming doesn't compete with foreground work. Applied a few seconds
+// after each start, once the launcher script has spawned node, and right away
+// when the setting changes. On macOS and Linux raising priority (and undoing a
+// lowered one) needs root, so those changes fail for a normal user until the
+// next start.
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sysinfo::{ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use crate::{audit, lock, monitor, settings, sidecar::SidecarState};
+
+const APPLY_DELAY: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    BelowNormal,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    fn windows_class(self) -> &'static str {
+        match self {
+            Self::BelowNormal => "BelowNormal",
+            Self::Normal => "Normal",
+            Self::High => "High",
+        }
+    }
+
+    fn nice(self) -> i32 {
+        match self {
+            Self::BelowNormal => 10,
+            Self::Normal => 0,
+            Self::High => -5,
+        }
+    }
+}
+
+fn configured(app: &AppHandle) -> Priority {
+    settings::load(app)
+        .get("enginePriority")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Called after the engine started; nothing to do at normal priority
+pub fn apply_after_start(app: &AppHandle, pid: u32) {
+    let priority = configured(app);
+    if priority == Priority::Normal {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(APPLY_DELAY).await;
+        if let Err(e) = apply(&app, pid, priority).await {
+            log::warn!("failed to set engine priority: {e}");
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn set_engine_priority(app: AppHandle, priority: Priority) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    settings::update(
+        &app,
+        "enginePriority",
+        serde_json::to_value(priority).unwrap_or(Value::Null),
+    )?;
+    audit::record(
+        &app,
+        "engine.priority",
+        serde_json::json!({ "priority": priority }),
+    );
+    match app.state::<SidecarState>().pid() {
+        Some(pid) => apply(&app, pid, priority).await,
+        None => Ok(()),
+    }
+}
+
+async fn apply(app: &AppHandle, root: u32, priority: Priority) -> Result<(), String> {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    let Some(tree) = monitor::process_tree(&sys, root) else {
+        return Ok(());
+    };
+    let pids: Vec<String> = tree.iter().map(|p| p.as_u32().to_string()).collect();
+
+    let command = if cfg!(windows) {
+        app.shell().command("powershell").args([
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            format!(
+                "Get-Process -Id {} -ErrorAction SilentlyContinue | ForEach-Object {{ $_.PriorityClass = '{}' }}",
+                pids.join(","),
+                priority.windows_class()
+            ),
+        ])
+    } else {
+        app.shell()
+            .command("renice")
+            .args([
+                "-n".to_string(),
+                priority.nice().to_string(),
+                "-p".to_string(),
+            ])
+            .args(pids)
+    };
+    let output = command.output().await.map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => format!("Command failed with {:?}", output.status.code()),
+            message => message.to_string(),
+        });
+    }
+    log::info!(
+        "engine priority set to {} for {} process(es)",
+        priority.windows_class(),
+        tree.len()
+    );
+    Ok(())
+}
@@ -0,0 +1,180 @@
+// Retention for locally stored data and a wipe-everything command
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::{captures, email, lock, mcp, profiles, settings, vault};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const DEFAULT_CAPTURE_RETENTION_DAYS: u64 = 30;
+const DEFAULT_LOG_RETENTION_DAYS: u64 = 14;
+// Written by the engine below its home folder, holds the OAuth refresh tokens
+const ENGINE_ACCOUNTS_FILE: &str = ".config/antigravity-proxy/accounts.json";
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WipeReport {
+    pub removed_files: usize,
+    pub errors: Vec<String>,
+}
+
+/// False when the user chose to never store request/response bodies
+pub fn store_bodies(app: &AppHandle) -> bool {
+    settings::load(app)
+        .get("storeBodies")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        sweep(&app);
+        thread::sleep(SWEEP_INTERVAL);
+    });
+}
+
+/// Drop captures and rotated logs older than their configured retention; returns
+/// how many files went
+pub fn sweep(app: &AppHandle) -> usize {
+    let settings = settings::load(app);
+    let days = |key, default| {
+        Duration::from_secs(settings::get_u64(&settings, key).unwrap_or(default) * 24 * 60 * 60)
+    };
+
+    let mut removed = 0;
+    if let Ok(dir) = captures::captures_dir(app) {
+        removed += remove_older_than(
+            &dir,
+            days("captureRetentionDays", DEFAULT_CAPTURE_RETENTION_DAYS),
+        );
+    }
+    if let Ok(dir) = app.path().app_log_dir() {
+        removed += remove_older_than(&dir, days("logRetentionDays", DEFAULT_LOG_RETENTION_DAYS));
+    }
+    if removed > 0 {
+        log::info!("retention: removed {removed} expired file(s)");
+    }
+    removed
+}
+
+pub fn remove_older_than(dir: &Path, max_age: Duration) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > max_age)
+        })
+        .filter(|entry| fs::remove_file(entry.path()).is_ok())
+        .count()
+}
+
+/// Overwrite and delete everything the app stores: captures, logs, caches, app
+/// state, settings and the capture key in the keychain. The engine's accounts file is only removed on request.
+#[tauri::command]
+pub async fn wipe_all_data(app: AppHandle, include_accounts: bool) -> Result<WipeReport, String> {
+    lock::ensure_unlocked(&app)?;
+    wipe(&app, include_accounts).await
+}
+
+/// `wipe_all_data` without the app lock check, for the uninstaller
+pub async fn wipe(app: &AppHandle, include_accounts: bool) -> Result<WipeReport, String> {
+    let path = app.path();
+    let mut targets: Vec<PathBuf> = [
+        path.app_data_dir(),
+        path.app_cache_dir(),
+        path.app_log_dir(),
+    ]
+    .into_iter()
+    .filter_map(Result::ok)
+    .collect();
+    targets.push(settings::config_dir(app)?);
+    // Profile homes live in app data, so their accounts are kept unless asked
+    let mut keep = Vec::new();
+    if include_accounts {
+        targets.push(
+            path.home_dir()
+                .map_err(|e| e.to_string())?
+                .join(ENGINE_ACCOUNTS_FILE),
+        );
+    } else {
+        keep.extend(
+            profiles::homes(app)
+                .into_iter()
+                .map(|home| home.join(ENGINE_ACCOUNTS_FILE)),
+        );
+    }
+
+    // Profile engines hold their logs and accounts files open
+    profiles::stop_all(app).await;
+    let mut report = WipeReport::default();
+    for target in targets {
+        shred(&target, &keep, &mut report);
+    }
+    if let Err(e) = vault::delete_key() {
+        report.errors.push(format!("keychain: {e}"));
+    }
+    if let Err(e) = email::delete_smtp_password() {
+        report.errors.push(format!("smtpPassword: {e}"));
+    }
+    if let Err(e) = mcp::delete_token() {
+        report.errors.push(format!("mcpToken: {e}"));
+    }
+    log::warn!(
+        "wiped local data: {} file(s) removed, {} error(s)",
+        report.removed_files,
+        report.errors.len()
+    );
+    Ok(report)
+}
+
+// Overwrite file contents with zeros before unlinking. Best effort: SSDs and
+// copy-on-write filesystems may still keep the old blocks around. Files in
+// `keep` stay, along with the folders holding them.
+fn shred(path: &Path, keep: &[PathBuf], report: &mut WipeReport) {
+    if path.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                shred(&entry.path(), keep, report);
+            }
+        }
+        let _ = fs::remove_dir(path);
+        return;
+    }
+    if !path.exists() || keep.iter().any(|k| k == path) {
+        return;
+    }
+
+    let overwrite = || -> std::io::Result<()> {
+        let len = fs::metadata(path)?.len();
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        let zeros = vec![0u8; 64 * 1024];
+        let mut left = len;
+        while left > 0 {
+            let n = left.min(zeros.len() as u64) as usize;
+            file.write_all(&zeros[..n])?;
+            left -= n as u64;
+        }
+        file.sync_all()?;
+        // The log file currently being written can't be removed on Windows
+        file.set_len(0)
+    };
+    if let Err(e) = overwrite().and_then(|_| fs::remove_file(path)) {
+        report.errors.push(format!("{}: {e}", path.display()));
+    } else {
+        report.removed_files += 1;
+    }
+}
@@ -0,0 +1,331 @@
+// Extra engine instances ("profiles"), each on its own port with its own account
+// pool, next to the main engine. The engine keeps its accounts under the user's
+// home folder, so every profile runs with HOME (USERPROFILE on Windows) pointed
+// at a folder of its own under app data. Started from Settings, or by launching
+// the app with `--profile <name>`, which reaches the running instance through
+// the single-instance plugin. At startup the "profile" setting (usually set as
+// ACP_PROFILE) names one to start as well.
+//
+// Traffic metrics, alerts and the tray only follow the main engine; profile
+// output goes to the log tagged with the profile name.
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::{
+    process::{CommandChild, CommandEvent},
+    ShellExt,
+};
+
+use crate::{api, audit, lock, settings, sidecar};
+
+const PROFILES_FILE: &str = "profiles.json";
+const PROFILES_DIR: &str = "profiles";
+pub const PROFILE_FLAG: &str = "--profile";
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    port: u16,
+    #[serde(default)]
+    fallback_enabled: bool,
+}
+
+#[derive(Default)]
+pub struct ProfilesState(Mutex<BTreeMap<String, Running>>);
+
+struct Running {
+    child: CommandChild,
+    /// What it was started on; the saved profile may have moved since
+    port: u16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileStatus {
+    name: String,
+    port: u16,
+    fallback_enabled: bool,
+    running: bool,
+    pid: Option<u32>,
+    /// Stands in for the home folder; the profile's accounts live below it
+    home: String,
+}
+
+#[tauri::command]
+pub fn list_profiles(app: AppHandle) -> Result<Vec<ProfileStatus>, String> {
+    let state = app.state::<ProfilesState>();
+    let children = state.0.lock().unwrap();
+    load(&app)
+        .into_iter()
+        .map(|(name, profile)| {
+            let pid = children.get(&name).map(|running| running.child.pid());
+            Ok(ProfileStatus {
+                home: home_dir(&app, &name)?.display().to_string(),
+                running: pid.is_some(),
+                pid,
+                port: profile.port,
+                fallback_enabled: profile.fallback_enabled,
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Create or change a profile. A running profile picks up a new port on its next start.
+#[tauri::command]
+pub fn save_profile(
+    app: AppHandle,
+    name: String,
+    port: u16,
+    fallback_enabled: bool,
+) -> Result<Vec<ProfileStatus>, String> {
+    lock::ensure_unlocked(&app)?;
+    let name = name.trim().to_string();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("Profile names may only use letters, digits, - and _".into());
+    }
+    if port == sidecar::current_port(&app) {
+        return Err(format!("Port {port} is already used by the main proxy"));
+    }
+    let mut profiles = load(&app);
+    if profiles
+        .iter()
+        .any(|(other, p)| *other != name && p.port == port)
+    {
+        return Err(format!("Port {port} is already used by another profile"));
+    }
+    profiles.insert(
+        name.clone(),
+        Profile {
+            port,
+            fallback_enabled,
+        },
+    );
+    save(&app, &profiles)?;
+    audit::record(
+        &app,
+        "profile.save",
+        serde_json::json!({ "name": name, "port": port }),
+    );
+    list_profiles(app)
+}
+
+/// Stop and forget a profile. Its accounts stay on disk until app data is wiped.
+#[tauri::command]
+pub async fn delete_profile(app: AppHandle, name: String) -> Result<Vec<ProfileStatus>, String> {
+    lock::ensure_unlocked(&app)?;
+    stop(&app, &name).await;
+    let mut profiles = load(&app);
+    if profiles.remove(&name).is_some() {
+        save(&app, &profiles)?;
+        audit::record(&app, "profile.delete", serde_json::json!({ "name": name }));
+    }
+    list_profiles(app)
+}
+
+#[tauri::command]
+pub async fn start_profile(app: AppHandle, name: String) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    start(app, name).await
+}
+
+// Also used at launch (--profile, ACP_PROFILE), before anyone could unlock
+async fn start(app: AppHandle, name: String) -> Result<(), String> {
+    let profile = load(&app)
+        .remove(&name)
+        .ok_or(format!("No profile named {name}"))?;
+    stop(&app, &name).await;
+
+    let home = home_dir(&app, &name)?;
+    fs::create_dir_all(&home).map_err(|e| e.to_string())?;
+    let mut args = vec!["start"];
+    if profile.fallback_enabled {
+        args.push("--fallback");
+    }
+    let mut command = app
+        .shell()
+        .command(sidecar::PROXY_BIN)
+        .args(args)
+        .env("PORT", profile.port.to_string())
+        .env("HOME", &home)
+        .env("USERPROFILE", &home);
+    if let Some(host) = settings::load(&app)
+        .get("bindAddress")
+        .and_then(Value::as_str)
+    {
+        command = command.env("HOST", api::listen_host(host));
+    }
+    let (mut rx, child) = command.spawn().map_err(|e| e.to_string())?;
+
+    let pid = child.pid();
+    log::info!(target: sidecar::LOG_TARGET, "profile {name} started on port {} (pid {pid})", profile.port);
+    app.state::<ProfilesState>().0.lock().unwrap().insert(
+        name.clone(),
+        Running {
+            child,
+            port: profile.port,
+        },
+    );
+    audit::record(
+        &app,
+        "profile.start",
+        serde_json::json!({ "name": name, "port": profile.port }),
+    );
+
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => forward_line(&name, &line, log::Level::Info),
+                CommandEvent::Stderr(line) => forward_line(&name, &line, log::Level::Warn),
+                CommandEvent::Error(err) => {
+                    log::error!(target: sidecar::LOG_TARGET, "[{name}] {err}")
+                }
+                CommandEvent::Terminated(payload) => {
+                    log::info!(target: sidecar::LOG_TARGET, "profile {name} exited (code {:?})", payload.code);
+                    let state = app.state::<ProfilesState>();
+                    let mut children = state.0.lock().unwrap();
+                    if children.get(&name).is_some_and(|r| r.child.pid() == pid) {
+                        children.remove(&name);
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_profile(app: AppHandle, name: String) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    stop(&app, &name).await;
+    Ok(())
+}
+
+async fn stop(app: &AppHandle, name: &str) {
+    let running = app.state::<ProfilesState>().0.lock().unwrap().remove(name);
+    if let Some(running) = running {
+        halt(app, name, running).await;
+        audit::record(app, "profile.stop", serde_json::json!({ "name": name }));
+    }
+}
+
+/// Stop every running profile engine, e.g. before their files are deleted
+pub async fn stop_all(app: &AppHandle) {
+    let children = std::mem::take(&mut *app.state::<ProfilesState>().0.lock().unwrap());
+    for (name, running) in children {
+        halt(app, &name, running).await;
+    }
+}
+
+// Killing the launcher can leave node behind, so the port is freed as for the main engine
+async fn halt(app: &AppHandle, name: &str, running: Running) {
+    let pid = running.child.pid();
+    if let Err(e) = running.child.kill() {
+        log::warn!(target: sidecar::LOG_TARGET, "failed to stop profile {name} (pid {pid}): {e}");
+    }
+    sidecar::free_port(app, running.port).await;
+}
+
+/// Home folders of all profiles, including ones left behind by deleted profiles
+pub fn homes(app: &AppHandle) -> Vec<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .and_then(|dir| fs::read_dir(dir.join(PROFILES_DIR)).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Profile named by `--profile <name>` (or `--profile=<name>`) in a command line
+pub fn from_args(args: &[String]) -> Option<String> {
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == PROFILE_FLAG {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix(PROFILE_FLAG)?
+                .strip_prefix('=')
+                .map(str::to_string)
+        }
+    })
+}
+
+/// Start the profile a command line asks for, if any
+pub fn start_from_args(app: &AppHandle, args: &[String]) {
+    if let Some(name) = from_args(args) {
+        start_logged(app, name);
+    }
+}
+
+/// Start the profile named by the "profile" setting (ACP_PROFILE), if any
+pub fn start_configured(app: &AppHandle) {
+    let name = settings::load(app)
+        .get("profile")
+        .and_then(Value::as_str)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string);
+    if let Some(name) = name {
+        start_logged(app, name);
+    }
+}
+
+fn start_logged(app: &AppHandle, name: String) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start(app, name.clone()).await {
+            log::error!("failed to start profile {name}: {e}");
+        }
+    });
+}
+
+fn forward_line(name: &str, raw: &[u8], default_level: log::Level) {
+    let line = sidecar::strip_ansi(&String::from_utf8_lossy(raw));
+    let line = line.trim_end();
+    if !line.is_empty() {
+        log::log!(target: sidecar::LOG_TARGET, sidecar::level_for(line).unwrap_or(default_level), "[{name}] {line}");
+    }
+}
+
+fn home_dir(app: &AppHandle, name: &str) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(PROFILES_DIR)
+        .join(name))
+}
+
+fn profiles_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(PROFILES_FILE))
+}
+
+fn load(app: &AppHandle) -> BTreeMap<String, Profile> {
+    profiles_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, profiles: &BTreeMap<String, Profile>) -> Result<(), String> {
+    let path = profiles_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(profiles).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
@@ -0,0 +1,113 @@
+// Test prompt console: one real request through the running engine, the same
+// /v1/messages path clients use, timed per stage so "is the proxy working and
+// where is it slow?" can be answered from inside the app.
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+use tauri_plugin_http::reqwest;
+
+use crate::{api, compare, sidecar, telemetry, traffic};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+const MAX_TOKENS: u32 = 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageTiming {
+    /// Until the engine answered with headers: account selection, mapping and
+    /// the upstream accepting the request
+    headers_ms: u64,
+    /// Until the first streamed text arrived; None for non-streaming requests
+    first_token_ms: Option<u64>,
+    total_ms: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestPromptResult {
+    model: String,
+    status: u16,
+    text: String,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    /// Error message from the engine or upstream when the request failed
+    error: Option<String>,
+    timing: StageTiming,
+    /// Model and account the engine logged for the request, best effort
+    served_model: Option<String>,
+    served_account: Option<String>,
+}
+
+#[tauri::command]
+pub async fn send_test_prompt(
+    app: AppHandle,
+    model: String,
+    text: String,
+    stream: bool,
+) -> Result<TestPromptResult, String> {
+    telemetry::count(&app, "feature.testPrompt");
+    let url = format!("{}/v1/messages", api::base_url(sidecar::current_port(&app)));
+    let body = json!({
+        "model": model,
+        "max_tokens": MAX_TOKENS,
+        "stream": stream,
+        "messages": [{ "role": "user", "content": text }],
+    });
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let started = Instant::now();
+    let mut response = client
+        .post(url)
+        .header("anthropic-version", "2023-06-01")
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let headers_ms = started.elapsed().as_millis() as u64;
+    let status = response.status().as_u16();
+
+    let mut raw = Vec::new();
+    let mut first_token_ms = None;
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        if stream && first_token_ms.is_none() && contains(&chunk, b"content_block_delta") {
+            first_token_ms = Some(started.elapsed().as_millis() as u64);
+        }
+        raw.extend_from_slice(&chunk);
+    }
+    let total_ms = started.elapsed().as_millis() as u64;
+    let raw = String::from_utf8_lossy(&raw);
+
+    let (input_tokens, output_tokens, text) = compare::read_reply(&raw);
+    let error = (status >= 400).then(|| {
+        serde_json::from_str::<Value>(&raw)
+            .ok()
+            .and_then(|v| v.pointer("/error/message")?.as_str().map(String::from))
+            .unwrap_or_else(|| raw.trim().to_string())
+    });
+    let (served_model, served_account) = traffic::last_route(&app);
+
+    Ok(TestPromptResult {
+        model,
+        status,
+        text,
+        input_tokens,
+        output_tokens,
+        error,
+        timing: StageTiming {
+            headers_ms,
+            first_token_ms,
+            total_ms,
+        },
+        served_model,
+        served_account,
+    })
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
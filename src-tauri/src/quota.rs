@@ -0,0 +1,186 @@
+// When each account's quota or rate limit runs out, per model. The engine already
+// tracks this from upstream headers and the 429s it hits and reports it on
+// /account-limits; the app keeps the latest view from every accounts refresh and
+// tells the frontend when it changes, so the UI can count down to "usable again".
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::traffic;
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetKind {
+    /// The model's quota window is used up
+    Quota,
+    /// Cooling down after a 429
+    RateLimit,
+}
+
+#[derive(Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaReset {
+    email: String,
+    model: String,
+    kind: ResetKind,
+    /// Unix ms at which the account can serve the model again
+    reset_at: u64,
+}
+
+#[derive(Default)]
+pub struct QuotaState(Mutex<Vec<QuotaReset>>);
+
+/// Take the reset times from an /account-limits reply; emits "quota-resets" on change
+pub fn update(app: &AppHandle, limits: &Value) {
+    let resets = parse(limits, traffic::now_ms());
+    let state = app.state::<QuotaState>();
+    let mut current = state.0.lock().unwrap();
+    if *current != resets {
+        *current = resets.clone();
+        let _ = app.emit("quota-resets", resets);
+    }
+}
+
+/// Upcoming resets, soonest first
+#[tauri::command]
+pub fn get_quota_resets(state: State<'_, QuotaState>) -> Vec<QuotaReset> {
+    let now = traffic::now_ms();
+    let current = state.0.lock().unwrap();
+    current
+        .iter()
+        .filter(|r| r.reset_at > now)
+        .cloned()
+        .collect()
+}
+
+fn parse(limits: &Value, now: u64) -> Vec<QuotaReset> {
+    let mut resets = Vec::new();
+    for account in limits["accounts"].as_array().into_iter().flatten() {
+        let Some(email) = account["email"].as_str() else {
+            continue;
+        };
+        let mut push = |model: &str, kind, reset_at: Option<u64>| {
+            if let Some(reset_at) = reset_at.filter(|&t| t > now) {
+                resets.push(QuotaReset {
+                    email: email.to_string(),
+                    model: model.to_string(),
+                    kind,
+                    reset_at,
+                });
+            }
+        };
+
+        // Quotas report an RFC 3339 reset time whether or not they are used up
+        for (model, limit) in account["limits"].as_object().into_iter().flatten() {
+            if limit["remainingFraction"]
+                .as_f64()
+                .is_some_and(|f| f <= 0.0)
+            {
+                let reset_at = limit["resetTime"]
+                    .as_str()
+                    .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+                    .map(|t| t.timestamp_millis() as u64);
+                push(model, ResetKind::Quota, reset_at);
+            }
+        }
+        for (model, limit) in account["modelRateLimits"].as_object().into_iter().flatten() {
+            if limit["isRateLimited"].as_bool() == Some(true) {
+                push(model, ResetKind::RateLimit, limit["resetTime"].as_u64());
+            }
+        }
+    }
+    resets.sort_by_key(|r| r.reset_at);
+    resets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2025-06-01T12:00:00Z
+    const NOW: u64 = 1_748_779_200_000;
+
+    // Trimmed from a GET /account-limits?format=json reply
+    const ACCOUNT_LIMITS: &str = r#"{
+        "timestamp": "2025-06-01T12:00:00.000Z",
+        "totalAccounts": 3,
+        "models": ["claude-sonnet-4-5-thinking", "gemini-3-pro-high", "gemini-2.5-flash"],
+        "accounts": [
+            {
+                "email": "alice@example.com",
+                "status": "ok",
+                "subscription": { "tier": "pro", "projectId": "proj-a" },
+                "limits": {
+                    "claude-sonnet-4-5-thinking": { "remaining": "0%", "remainingFraction": 0, "resetTime": "2025-06-01T14:30:00Z" },
+                    "gemini-3-pro-high": { "remaining": "62%", "remainingFraction": 0.62, "resetTime": "2025-06-01T17:00:00Z" },
+                    "gemini-2.5-flash": { "remaining": "N/A", "remainingFraction": null, "resetTime": null }
+                },
+                "modelRateLimits": {
+                    "gemini-3-pro-high": { "isRateLimited": true, "resetTime": 1748779290000, "actualResetMs": 90000 },
+                    "gemini-2.5-flash": { "isRateLimited": false, "resetTime": 1748779000000 }
+                }
+            },
+            {
+                "email": "bob@example.com",
+                "status": "rate_limited",
+                "limits": {
+                    "claude-sonnet-4-5-thinking": { "remaining": "0%", "remainingFraction": 0, "resetTime": "2025-06-01T11:00:00Z" }
+                },
+                "modelRateLimits": {
+                    "claude-sonnet-4-5-thinking": { "isRateLimited": true, "resetTime": 1748779260000 }
+                }
+            },
+            { "status": "error", "error": "invalid_grant", "limits": {} }
+        ]
+    }"#;
+
+    fn summary(resets: &[QuotaReset]) -> Vec<(&str, &str, &str, u64)> {
+        resets
+            .iter()
+            .map(|r| {
+                let kind = match r.kind {
+                    ResetKind::Quota => "quota",
+                    ResetKind::RateLimit => "rate_limit",
+                };
+                (r.email.as_str(), r.model.as_str(), kind, r.reset_at)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_account_limits_reply() {
+        let limits: Value = serde_json::from_str(ACCOUNT_LIMITS).unwrap();
+        assert_eq!(
+            summary(&parse(&limits, NOW)),
+            [
+                (
+                    "bob@example.com",
+                    "claude-sonnet-4-5-thinking",
+                    "rate_limit",
+                    1_748_779_260_000
+                ),
+                (
+                    "alice@example.com",
+                    "gemini-3-pro-high",
+                    "rate_limit",
+                    1_748_779_290_000
+                ),
+                (
+                    "alice@example.com",
+                    "claude-sonnet-4-5-thinking",
+                    "quota",
+                    1_748_788_200_000
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_or_unexpected_replies_have_no_resets() {
+        assert!(parse(&Value::Null, NOW).is_empty());
+        assert!(parse(&serde_json::json!({ "accounts": [] }), NOW).is_empty());
+        assert!(parse(&serde_json::json!({ "error": "not ready" }), NOW).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+// Scrubbing of secrets and personal data before anything leaves the machine
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde_json::Value;
+
+pub const REDACTED: &str = "[redacted]";
+
+static SECRET_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        // Google OAuth access and refresh tokens
+        r"ya29\.[0-9A-Za-z_\-]+",
+        r"1//[0-9A-Za-z_\-]{20,}",
+        // API keys and bearer tokens
+        r"sk-[0-9A-Za-z_\-]{16,}",
+        r"(?i)bearer\s+[0-9A-Za-z._\-]+",
+    ]
+    .iter()
+    .map(|p| Regex::new(p).expect("valid redaction pattern"))
+    .collect()
+});
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"([A-Za-z0-9._%+\-]{1,2})[A-Za-z0-9._%+\-]*@([A-Za-z0-9.\-]+\.[A-Za-z]{2,})")
+        .expect("valid email pattern")
+});
+
+/// Remove tokens and mask emails the same way the UI does (ab***@domain)
+pub fn scrub_text(text: &str) -> String {
+    let mut out = text.to_string();
+    for pattern in SECRET_PATTERNS.iter() {
+        out = pattern.replace_all(&out, REDACTED).into_owned();
+    }
+    EMAIL.replace_all(&out, "$1***@$2").into_owned()
+}
+
+/// Blank out values under secret-looking keys and scrub every remaining string
+pub fn scrub_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *v = Value::String(REDACTED.into());
+                } else {
+                    scrub_json(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(scrub_json),
+        Value::String(s) => *s = scrub_text(s),
+        _ => {}
+    }
+}
+
+// Whole words only, so refreshToken and x-api-key match but maxTokens does not.
+// Plurals count as the word itself (apiKeys, credentials).
+pub fn is_secret_key(key: &str) -> bool {
+    let words: Vec<String> = key_words(key)
+        .into_iter()
+        .map(|w| match w.strip_suffix('s') {
+            Some(stem) if !stem.is_empty() => stem.to_string(),
+            _ => w,
+        })
+        .collect();
+    let has = |word: &str| words.iter().any(|w| w == word);
+    // Counts and limits are settings worth seeing in a bug report
+    if ["max", "min", "count", "limit", "num", "total"]
+        .iter()
+        .any(|w| has(w))
+    {
+        return false;
+    }
+    [
+        "token",
+        "secret",
+        "password",
+        "passphrase",
+        "authorization",
+        "auth",
+        "credential",
+        "private",
+        "cookie",
+        "apikey",
+    ]
+    .iter()
+    .any(|w| has(w))
+        || (has("api") && has("key"))
+}
+
+// "refreshToken", "api_key" and "x-api-key" into lowercase words
+fn key_words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut after_lower = false;
+    for c in key.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            after_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && after_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        after_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_secret_keys() {
+        for key in [
+            "token",
+            "refreshToken",
+            "refreshTokens",
+            "access_token",
+            "x-api-key",
+            "apiKey",
+            "apiKeys",
+            "api_keys",
+            "privateKey",
+            "credentials",
+            "auth",
+            "Authorization",
+            "smtpPassword",
+            "lockPassphrase",
+            "Cookie",
+            "clientSecret",
+        ] {
+            assert!(is_secret_key(key), "{key} should be redacted");
+        }
+    }
+
+    #[test]
+    fn leaves_counts_and_ordinary_keys() {
+        for key in [
+            "maxTokens",
+            "max_tokens",
+            "tokenCount",
+            "tokenLimit",
+            "totalTokens",
+            "keyboard",
+            "status",
+            "port",
+            "email",
+            "model",
+        ] {
+            assert!(!is_secret_key(key), "{key} should be kept");
+        }
+    }
+
+    #[test]
+    fn scrubs_tokens_from_text() {
+        assert_eq!(
+            scrub_text("refresh failed for ya29.a0AfH6SMBx-y_z: 401"),
+            "refresh failed for [redacted]: 401"
+        );
+        assert_eq!(
+            scrub_text("token 1//0gAbCdEfGhIjKlMnOpQrStUv saved"),
+            "token [redacted] saved"
+        );
+        assert_eq!(
+            scrub_text("key sk-ant-REDACTED rejected"),
+            "key [redacted] rejected"
+        );
+        assert_eq!(
+            scrub_text("Authorization: Bearer abc.def-123"),
+            "Authorization: [redacted]"
+        );
+    }
+
+    #[test]
+    fn masks_emails_like_the_ui() {
+        assert_eq!(
+            scrub_text("switched to alice.smith@example.com (quota)"),
+            "switched to al***@example.com (quota)"
+        );
+        assert_eq!(scrub_text("no secrets here"), "no secrets here");
+    }
+}
@@ -0,0 +1,399 @@
+// Replay a pasted cURL command against the local proxy, for reproducing bug
+// reports. Only the path and query of the original URL are kept; the request is
+// always sent to the running engine.
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_http::reqwest;
+
+use crate::{
+    api,
+    captures::{self, CapturedRequest, CapturedResponse},
+    sidecar, telemetry,
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+// Headers that describe the original connection rather than the request
+pub const DROPPED_HEADERS: [&str; 4] = ["host", "content-length", "connection", "accept-encoding"];
+// Single-letter options that take no value and can be clustered, as in -sSL
+const SHORT_SWITCHES: &str = "sSvLkifgNI#";
+// Single-letter options whose value may be attached (-XPOST) or the next word
+const SHORT_WITH_VALUE: &str = "XHdAeubomwxEcFT";
+
+#[derive(Default)]
+pub struct CurlRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayResult {
+    method: String,
+    url: String,
+    status: u16,
+    duration_ms: u64,
+    headers: BTreeMap<String, String>,
+    body: String,
+    /// Saved for diffing; None if saving failed
+    capture_id: Option<String>,
+}
+
+/// Parse `curl` (or the clipboard when not given) and send it through the proxy
+#[tauri::command]
+pub async fn replay_curl(app: AppHandle, curl: Option<String>) -> Result<ReplayResult, String> {
+    telemetry::count(&app, "feature.replayCurl");
+    let text = match curl {
+        Some(text) => text,
+        None => app.clipboard().read_text().map_err(|e| e.to_string())?,
+    };
+    let request = parse_curl(&text)?;
+    let request = CapturedRequest {
+        method: request.method,
+        path: request.path,
+        headers: request
+            .headers
+            .into_iter()
+            .filter(|(name, _)| !DROPPED_HEADERS.contains(&name.to_ascii_lowercase().as_str()))
+            .collect(),
+        body: request.body,
+    };
+
+    log::info!("replaying {} {}", request.method, request.path);
+    let (method, path) = (request.method.clone(), request.path.clone());
+    let (response, capture_id) = send(&app, request).await?;
+
+    Ok(ReplayResult {
+        method,
+        url: format!("{}{path}", api::base_url(sidecar::current_port(&app))),
+        status: response.status,
+        duration_ms: response.duration_ms,
+        headers: response.headers,
+        body: response.body,
+        capture_id,
+    })
+}
+
+/// Send a request to the running engine and save the exchange as a capture.
+/// The capture id is None when saving failed.
+pub async fn send(
+    app: &AppHandle,
+    request: CapturedRequest,
+) -> Result<(CapturedResponse, Option<String>), String> {
+    let url = format!(
+        "{}{}",
+        api::base_url(sidecar::current_port(app)),
+        request.path
+    );
+    let method = reqwest::Method::from_bytes(request.method.as_bytes())
+        .map_err(|_| format!("Unsupported method: {}", request.method))?;
+
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut builder = client.request(method, &url);
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = &request.body {
+        builder = builder.body(body.clone());
+    }
+
+    let started = Instant::now();
+    let response = builder.send().await.map_err(|e| e.to_string())?;
+    let status = response.status().as_u16();
+    let headers: BTreeMap<String, String> = response
+        .headers()
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or_default().to_string()))
+        .collect();
+    // Streaming responses are read to the end, so the body holds every SSE event
+    let body = response.text().await.map_err(|e| e.to_string())?;
+    let response = CapturedResponse {
+        status,
+        duration_ms: started.elapsed().as_millis() as u64,
+        headers,
+        body,
+    };
+
+    let capture_id = captures::save(app, request, response.clone())
+        .map_err(|e| log::warn!("failed to save capture: {e}"))
+        .ok();
+    Ok((response, capture_id))
+}
+
+pub fn parse_curl(text: &str) -> Result<CurlRequest, String> {
+    let words = split_short_options(split_words(text)?);
+    let mut args = words.iter().map(String::as_str);
+    if args.next() != Some("curl") {
+        return Err("Clipboard does not contain a curl command".into());
+    }
+
+    let mut request = CurlRequest::default();
+    let mut url = None;
+    let mut explicit_method = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .map(str::to_string)
+                .ok_or(format!("Missing value for {name}"))
+        };
+        match arg {
+            "-X" | "--request" => explicit_method = Some(value(arg)?.to_ascii_uppercase()),
+            "-H" | "--header" => {
+                let header = value(arg)?;
+                if let Some((name, val)) = header.split_once(':') {
+                    request
+                        .headers
+                        .push((name.trim().to_string(), val.trim().to_string()));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
+                let data = value(arg)?;
+                if data.starts_with('@') {
+                    return Err(
+                        "Request bodies read from files (-d @file) can't be replayed".into(),
+                    );
+                }
+                // Repeated -d flags are joined like curl does
+                request.body = Some(match request.body.take() {
+                    Some(body) => format!("{body}&{data}"),
+                    None => data,
+                });
+            }
+            "--json" => {
+                request.body = Some(value(arg)?);
+                request
+                    .headers
+                    .push(("Content-Type".into(), "application/json".into()));
+                request
+                    .headers
+                    .push(("Accept".into(), "application/json".into()));
+            }
+            "-A" | "--user-agent" => request.headers.push(("User-Agent".into(), value(arg)?)),
+            "-e" | "--referer" => request.headers.push(("Referer".into(), value(arg)?)),
+            "-u" | "--user" => {
+                let credentials = STANDARD.encode(value(arg)?);
+                request
+                    .headers
+                    .push(("Authorization".into(), format!("Basic {credentials}")));
+            }
+            "-b" | "--cookie" => {
+                let cookie = value(arg)?;
+                if !cookie.contains('=') {
+                    return Err("Cookies read from files (-b file) can't be replayed".into());
+                }
+                request.headers.push(("Cookie".into(), cookie));
+            }
+            "-I" | "--head" => explicit_method = Some("HEAD".into()),
+            "--url" => url = Some(value(arg)?),
+            // Options about the original connection or curl's own output
+            "-o" | "--output" | "-m" | "--max-time" | "--connect-timeout" | "-w"
+            | "--write-out" | "-x" | "--proxy" | "-E" | "--cert" | "--key" | "--cacert" | "-c"
+            | "--cookie-jar" | "--resolve" | "--connect-to" | "--retry" | "--retry-delay"
+            | "--retry-max-time" | "--limit-rate" => {
+                value(arg)?;
+            }
+            "-s" | "--silent" | "-S" | "--show-error" | "-v" | "--verbose" | "-L"
+            | "--location" | "-k" | "--insecure" | "-i" | "--include" | "-f" | "--fail" | "-g"
+            | "--globoff" | "-N" | "--no-buffer" | "-#" | "--progress-bar" | "--compressed"
+            | "--http1.1" | "--http2" => {}
+            "-F" | "--form" | "-T" | "--upload-file" | "-G" | "--get" | "--data-urlencode" => {
+                return Err(format!("{arg} requests can't be replayed"));
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(format!("Unsupported curl option {flag}"));
+            }
+            positional => url = Some(positional.to_string()),
+        }
+    }
+
+    let url = url.ok_or("No URL in curl command")?;
+    let parsed = reqwest::Url::parse(&url)
+        .or_else(|_| reqwest::Url::parse(&format!("http://{url}")))
+        .map_err(|e| format!("Invalid URL {url}: {e}"))?;
+    request.path = match parsed.query() {
+        Some(query) => format!("{}?{query}", parsed.path()),
+        None => parsed.path().to_string(),
+    };
+    request.method = explicit_method.unwrap_or_else(|| {
+        if request.body.is_some() {
+            "POST".into()
+        } else {
+            "GET".into()
+        }
+    });
+    Ok(request)
+}
+
+// Unclusters short options: "-sSL" becomes "-s" "-S" "-L" and "-XPOST" becomes
+// "-X" "POST", so every option can be matched on its own
+fn split_short_options(words: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(words.len());
+    for word in words {
+        if word.len() <= 2 || !word.starts_with('-') || word.starts_with("--") {
+            out.push(word);
+            continue;
+        }
+        let letters = &word[1..];
+        for (i, c) in letters.char_indices() {
+            out.push(format!("-{c}"));
+            if SHORT_WITH_VALUE.contains(c) {
+                let rest = &letters[i + c.len_utf8()..];
+                if !rest.is_empty() {
+                    out.push(rest.to_string());
+                }
+                break;
+            }
+            if !SHORT_SWITCHES.contains(c) {
+                break;
+            }
+        }
+    }
+    out
+}
+
+// POSIX shell quoting plus the line continuations people paste from docs:
+// `\` (bash) and `^` (cmd.exe) at the end of a line
+fn split_words(text: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated single quote".into()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("Unterminated double quote".into()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated double quote".into()),
+                    }
+                }
+            }
+            '\\' | '^' if matches!(chars.peek(), Some('\n' | '\r')) => {
+                while matches!(chars.peek(), Some('\n' | '\r')) {
+                    chars.next();
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    current.push(c);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_method_headers_and_body() {
+        let request = parse_curl(
+            "curl -X POST 'http://localhost:8080/v1/messages?beta=true' \\\n  -H 'content-type: application/json' \\\n  -d '{\"model\":\"x\"}'",
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/messages?beta=true");
+        assert_eq!(
+            request.headers,
+            vec![("content-type".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(request.body.as_deref(), Some("{\"model\":\"x\"}"));
+    }
+
+    #[test]
+    fn body_implies_post() {
+        let request = parse_curl("curl localhost:8080/v1/messages -d 'a=1' -d 'b=2'").unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.body.as_deref(), Some("a=1&b=2"));
+    }
+
+    #[test]
+    fn values_of_skipped_options_are_not_the_url() {
+        let request = parse_curl(
+            "curl -x http://corp-proxy:3128 -u user:pass -b 'session=1' -e https://ref http://localhost/v1/models",
+        )
+        .unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/v1/models");
+        assert!(request.headers.contains(&(
+            "Authorization".to_string(),
+            "Basic dXNlcjpwYXNz".to_string()
+        )));
+        assert!(request
+            .headers
+            .contains(&("Cookie".to_string(), "session=1".to_string())));
+    }
+
+    #[test]
+    fn attached_and_clustered_short_options() {
+        let request =
+            parse_curl("curl -sSL -XPUT -HAccept:text/plain http://localhost/v1/x").unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(
+            request.headers,
+            vec![("Accept".to_string(), "text/plain".to_string())]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_options() {
+        assert!(parse_curl("curl -F file=@a.txt http://localhost/v1/x").is_err());
+        assert!(parse_curl("curl --made-up http://localhost/v1/x").is_err());
+        assert!(parse_curl("curl -d @body.json http://localhost/v1/x").is_err());
+    }
+
+    #[test]
+    fn requires_curl_and_a_url() {
+        assert!(parse_curl("wget http://localhost").is_err());
+        assert!(parse_curl("curl -s").is_err());
+    }
+}
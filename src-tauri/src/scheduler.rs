@@ -0,0 +1,457 @@
+// Recurring background jobs on cron schedules: a daily usage summary export, a
+// weekly cleanup and a nightly account health check. Schedules and the outcome
+// of each job's last run persist in jobs.json. Runs missed while the app was
+// closed or the machine asleep are skipped, not caught up.
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Mutex, time::Duration};
+
+use chrono::{DateTime, Datelike, Local, TimeDelta, Timelike};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    alerts,
+    api::{self, AccountLimits},
+    email, i18n, lock, pricing, privacy, sidecar, traffic,
+};
+
+const JOBS_FILE: &str = "jobs.json";
+const REPORTS_DIR: &str = "reports";
+const REPORT_RETENTION: Duration = Duration::from_secs(90 * 24 * 60 * 60);
+const TICK: Duration = Duration::from_secs(30);
+// How far ahead next runs are looked up; covers any weekly schedule
+const NEXT_RUN_HORIZON_MINUTES: i64 = 8 * 24 * 60;
+
+// Read-modify-write of jobs.json from the scheduler and from commands
+static RECORD: Mutex<()> = Mutex::new(());
+
+struct Job {
+    id: &'static str,
+    description: &'static str,
+    default_schedule: &'static str,
+}
+
+static JOBS: [Job; 3] = [
+    Job {
+        id: "usage-summary",
+        description: "Export the last 24 hours of usage with an API-equivalent cost estimate",
+        default_schedule: "55 23 * * *",
+    },
+    Job {
+        id: "cleanup",
+        description: "Apply retention to captures and logs and drop old usage reports",
+        default_schedule: "0 4 * * 0",
+    },
+    Job {
+        id: "account-health",
+        description: "Check every account and alert about ones that need attention",
+        default_schedule: "0 3 * * *",
+    },
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobRecord {
+    schedule: String,
+    enabled: bool,
+    last_run: Option<u64>,
+    last_ok: Option<bool>,
+    last_result: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    id: &'static str,
+    description: &'static str,
+    /// Five-field cron expression in local time
+    schedule: String,
+    enabled: bool,
+    last_run: Option<u64>,
+    last_ok: Option<bool>,
+    last_result: Option<String>,
+    next_run: Option<u64>,
+}
+
+#[tauri::command]
+pub fn list_jobs(app: AppHandle) -> Vec<JobInfo> {
+    let records = load(&app);
+    let now = Local::now();
+    JOBS.iter()
+        .map(|job| {
+            let record = record_for(&records, job);
+            JobInfo {
+                id: job.id,
+                description: job.description,
+                next_run: record
+                    .enabled
+                    .then(|| next_run(&record.schedule, now))
+                    .flatten(),
+                schedule: record.schedule,
+                enabled: record.enabled,
+                last_run: record.last_run,
+                last_ok: record.last_ok,
+                last_result: record.last_result,
+            }
+        })
+        .collect()
+}
+
+/// Run a job right away, independent of its schedule
+#[tauri::command]
+pub async fn run_job_now(app: AppHandle, id: String) -> Result<String, String> {
+    lock::ensure_unlocked(&app)?;
+    let job = JOBS
+        .iter()
+        .find(|j| j.id == id)
+        .ok_or(format!("Unknown job: {id}"))?;
+    run(&app, job).await
+}
+
+/// Change when a job runs, or turn it off
+#[tauri::command]
+pub fn set_job_schedule(
+    app: AppHandle,
+    id: String,
+    schedule: String,
+    enabled: bool,
+) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    let job = JOBS
+        .iter()
+        .find(|j| j.id == id)
+        .ok_or(format!("Unknown job: {id}"))?;
+    let schedule = schedule.split_whitespace().collect::<Vec<_>>().join(" ");
+    cron_matches(&schedule, &Local::now())?;
+
+    let _guard = RECORD.lock().unwrap();
+    let mut records = load(&app);
+    let mut record = record_for(&records, job);
+    record.schedule = schedule;
+    record.enabled = enabled;
+    records.insert(job.id.to_string(), record);
+    save(&app, &records)
+}
+
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            let now = Local::now();
+            let now_ms = now.timestamp_millis() as u64;
+            let minute_start = now_ms - now_ms % 60_000;
+            let records = load(&app);
+            for job in &JOBS {
+                let record = record_for(&records, job);
+                let due = record.enabled
+                    && record.last_run.is_none_or(|at| at < minute_start)
+                    && cron_matches(&record.schedule, &now).unwrap_or(false);
+                if due {
+                    let _ = run(&app, job).await;
+                }
+            }
+        }
+    });
+}
+
+async fn run(app: &AppHandle, job: &Job) -> Result<String, String> {
+    let started = traffic::now_ms();
+    log::info!("running job {}", job.id);
+    let result = match job.id {
+        "usage-summary" => usage_summary(app).await,
+        "cleanup" => cleanup(app),
+        "account-health" => account_health(app).await,
+        _ => unreachable!("job ids come from JOBS"),
+    };
+    match &result {
+        Ok(message) => log::info!("job {}: {message}", job.id),
+        Err(e) => log::error!("job {} failed: {e}", job.id),
+    }
+
+    let _guard = RECORD.lock().unwrap();
+    let mut records = load(app);
+    let mut record = record_for(&records, job);
+    record.last_run = Some(started);
+    record.last_ok = Some(result.is_ok());
+    record.last_result = Some(result.clone().unwrap_or_else(|e| e));
+    records.insert(job.id.to_string(), record);
+    if let Err(e) = save(app, &records) {
+        log::error!("failed to save job state: {e}");
+    }
+    result
+}
+
+async fn usage_summary(app: &AppHandle) -> Result<String, String> {
+    let port = sidecar::current_port(app);
+    let history: Value = api::get(port, "/api/stats/history").await?;
+    let now = Local::now();
+    let since = now - TimeDelta::days(1);
+    let recent: serde_json::Map<String, Value> = history
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(slot, _)| {
+            DateTime::parse_from_rfc3339(slot).is_ok_and(|t| t >= since && t <= now)
+        })
+        .map(|(slot, point)| (slot.clone(), point.clone()))
+        .collect();
+    let slots = recent.len();
+    let recent = Value::Object(recent);
+    let cost = pricing::estimate_usage_cost(app.clone(), recent.clone());
+
+    let report = json!({
+        "exportedAt": now.to_rfc3339(),
+        "from": since.to_rfc3339(),
+        "to": now.to_rfc3339(),
+        "usageHistory": recent,
+        "estimatedCost": cost,
+    });
+    let dir = reports_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("usage-{}.json", now.format("%Y-%m-%d")));
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "{slots} time slot(s) written to {}",
+        path.display()
+    ))
+}
+
+fn cleanup(app: &AppHandle) -> Result<String, String> {
+    let mut removed = privacy::sweep(app);
+    removed += privacy::remove_older_than(&reports_dir(app)?, REPORT_RETENTION);
+    Ok(format!("removed {removed} expired file(s)"))
+}
+
+async fn account_health(app: &AppHandle) -> Result<String, String> {
+    let limits: AccountLimits = api::get(sidecar::current_port(app), "/account-limits").await?;
+    let unhealthy: Vec<String> = limits
+        .accounts
+        .iter()
+        .filter(|a| {
+            a.enabled && (a.error.is_some() || !matches!(a.status.as_str(), "ok" | "active"))
+        })
+        .map(|a| {
+            let reason = a.error.as_deref().unwrap_or(&a.status);
+            format!("{} ({reason})", a.email)
+        })
+        .collect();
+    if unhealthy.is_empty() {
+        return Ok(format!("{} account(s) healthy", limits.accounts.len()));
+    }
+
+    let body = i18n::tf(
+        app,
+        "health.body",
+        &[
+            ("count", unhealthy.len().to_string()),
+            ("total", limits.accounts.len().to_string()),
+            ("accounts", unhealthy.join(", ")),
+        ],
+    );
+    let title = i18n::t(app, "health.title");
+    alerts::notify(app, &title, &body);
+    email::alert(app, &title, &body);
+    Ok(body)
+}
+
+fn record_for(records: &BTreeMap<String, JobRecord>, job: &Job) -> JobRecord {
+    records.get(job.id).cloned().unwrap_or_else(|| JobRecord {
+        schedule: job.default_schedule.to_string(),
+        enabled: true,
+        last_run: None,
+        last_ok: None,
+        last_result: None,
+    })
+}
+
+fn next_run(schedule: &str, after: DateTime<Local>) -> Option<u64> {
+    let start = after.with_second(0)?.with_nanosecond(0)?;
+    (1..=NEXT_RUN_HORIZON_MINUTES)
+        .map(|m| start + TimeDelta::minutes(m))
+        .find(|t| cron_matches(schedule, t).unwrap_or(false))
+        .map(|t| t.timestamp_millis() as u64)
+}
+
+/// Standard five-field cron: minute hour day-of-month month day-of-week (0 or 7 =
+/// Sunday). Fields take `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps
+/// (`*/15`, `0-30/10`). As in classic cron, when day-of-month and day-of-week
+/// are both restricted a day matching either one runs the job.
+fn cron_matches(schedule: &str, t: &DateTime<Local>) -> Result<bool, String> {
+    let fields: Vec<&str> = schedule.split_whitespace().collect();
+    let [minute, hour, day, month, weekday] = fields[..] else {
+        return Err(format!(
+            "Expected 5 fields (minute hour day month weekday) in {schedule:?}"
+        ));
+    };
+    // Fields starting with "*", "*/2" included, count as unrestricted
+    let both_restricted = !day.starts_with('*') && !weekday.starts_with('*');
+    let (minute, hour, day, month, weekday) = (
+        parse_field(minute, 0, 59)?,
+        parse_field(hour, 0, 23)?,
+        parse_field(day, 1, 31)?,
+        parse_field(month, 1, 12)?,
+        parse_field(weekday, 0, 7)?,
+    );
+    let sunday_based = t.weekday().num_days_from_sunday();
+    let day_matches = field_matches(&day, t.day());
+    let weekday_matches =
+        field_matches(&weekday, sunday_based) || (sunday_based == 0 && field_matches(&weekday, 7));
+    Ok(field_matches(&minute, t.minute())
+        && field_matches(&hour, t.hour())
+        && field_matches(&month, t.month())
+        && if both_restricted {
+            day_matches || weekday_matches
+        } else {
+            day_matches && weekday_matches
+        })
+}
+
+// (first, last, step) per comma-separated part
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<(u32, u32, u32)>, String> {
+    let number = |s: &str| {
+        s.parse::<u32>()
+            .map_err(|_| format!("Invalid number {s:?} in cron field {field:?}"))
+    };
+    field
+        .split(',')
+        .map(|part| {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, number(step)?),
+                None => (part, 1),
+            };
+            let (first, last) = match range.split_once('-') {
+                _ if range == "*" => (min, max),
+                Some((first, last)) => (number(first)?, number(last)?),
+                // "5/15" means every 15 starting at 5
+                None if step > 1 => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            };
+            if step == 0 || first < min || last > max || first > last {
+                return Err(format!(
+                    "{part:?} is out of range {min}-{max} in cron field {field:?}"
+                ));
+            }
+            Ok((first, last, step))
+        })
+        .collect()
+}
+
+fn field_matches(parts: &[(u32, u32, u32)], value: u32) -> bool {
+    parts
+        .iter()
+        .any(|&(first, last, step)| (first..=last).contains(&value) && (value - first) % step == 0)
+}
+
+pub fn reports_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(REPORTS_DIR))
+}
+
+fn jobs_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(JOBS_FILE))
+}
+
+fn load(app: &AppHandle) -> BTreeMap<String, JobRecord> {
+    jobs_path(app)
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &AppHandle, records: &BTreeMap<String, JobRecord>) -> Result<(), String> {
+    let path = jobs_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(records).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    // 2026-10-14 is a Wednesday
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_lists_ranges_and_steps() {
+        assert_eq!(parse_field("*", 0, 59).unwrap(), vec![(0, 59, 1)]);
+        assert_eq!(parse_field("5", 0, 59).unwrap(), vec![(5, 5, 1)]);
+        assert_eq!(
+            parse_field("1-5,10", 0, 59).unwrap(),
+            vec![(1, 5, 1), (10, 10, 1)]
+        );
+        assert_eq!(parse_field("*/15", 0, 59).unwrap(), vec![(0, 59, 15)]);
+        assert_eq!(parse_field("5/15", 0, 59).unwrap(), vec![(5, 59, 15)]);
+        assert_eq!(parse_field("0-30/10", 0, 59).unwrap(), vec![(0, 30, 10)]);
+    }
+
+    #[test]
+    fn rejects_bad_fields() {
+        assert!(parse_field("60", 0, 59).is_err());
+        assert!(parse_field("0", 1, 31).is_err());
+        assert!(parse_field("5-1", 0, 59).is_err());
+        assert!(parse_field("*/0", 0, 59).is_err());
+        assert!(parse_field("x", 0, 59).is_err());
+        assert!(cron_matches("0 9 * *", &at(14, 9, 0)).is_err());
+    }
+
+    #[test]
+    fn matches_time_fields() {
+        assert!(cron_matches("30 9 * * *", &at(14, 9, 30)).unwrap());
+        assert!(!cron_matches("30 9 * * *", &at(14, 9, 31)).unwrap());
+        assert!(cron_matches("*/15 * * * *", &at(14, 3, 45)).unwrap());
+        assert!(!cron_matches("*/15 * * * *", &at(14, 3, 50)).unwrap());
+        assert!(cron_matches("0 9 * 10 *", &at(14, 9, 0)).unwrap());
+        assert!(!cron_matches("0 9 * 11 *", &at(14, 9, 0)).unwrap());
+    }
+
+    #[test]
+    fn sunday_is_zero_or_seven() {
+        // 2026-10-18 is a Sunday
+        assert!(cron_matches("0 3 * * 0", &at(18, 3, 0)).unwrap());
+        assert!(cron_matches("0 3 * * 7", &at(18, 3, 0)).unwrap());
+        assert!(!cron_matches("0 3 * * 7", &at(17, 3, 0)).unwrap());
+    }
+
+    #[test]
+    fn one_restricted_day_field_must_match() {
+        assert!(cron_matches("0 9 14 * *", &at(14, 9, 0)).unwrap());
+        assert!(!cron_matches("0 9 15 * *", &at(14, 9, 0)).unwrap());
+        assert!(cron_matches("0 9 * * 3", &at(14, 9, 0)).unwrap());
+        assert!(!cron_matches("0 9 * * 4", &at(14, 9, 0)).unwrap());
+        // "*/2" runs on odd days; starting with "*" it still needs the weekday too
+        assert!(!cron_matches("0 9 */2 * 3", &at(14, 9, 0)).unwrap());
+        assert!(cron_matches("0 9 */2 * 3", &at(7, 9, 0)).unwrap());
+    }
+
+    #[test]
+    fn both_restricted_day_fields_match_either() {
+        // The 1st of the month or any Wednesday
+        assert!(cron_matches("0 9 1 * 3", &at(14, 9, 0)).unwrap());
+        assert!(cron_matches("0 9 1 * 3", &at(1, 9, 0)).unwrap());
+        assert!(!cron_matches("0 9 1 * 3", &at(15, 9, 0)).unwrap());
+    }
+
+    #[test]
+    fn next_run_finds_the_following_match() {
+        let next = next_run("0 3 * * *", at(14, 9, 30)).unwrap();
+        assert_eq!(next, at(15, 3, 0).timestamp_millis() as u64);
+    }
+}
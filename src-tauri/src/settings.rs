@@ -0,0 +1,253 @@
+// Desktop app settings, shared with the frontend (see appStorageService.ts)
+use std::{fs, path::PathBuf};
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::lock;
+
+const CONFIG_DIR: &str = ".config/antigravity-proxy/desktop-app";
+// ACP_LOG_LEVEL overrides "logLevel", ACP_PORT overrides "port", and so on
+const ENV_PREFIX: &str = "ACP_";
+
+pub fn config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .home_dir()
+        .map_err(|e| e.to_string())?
+        .join(CONFIG_DIR))
+}
+
+pub fn settings_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(config_dir(app)?.join("settings.json"))
+}
+
+pub const DEFAULT_PORT: u16 = 8080;
+
+/// Settings as saved by the frontend, with `ACP_*` environment variables layered on top
+pub fn load(app: &AppHandle) -> serde_json::Map<String, Value> {
+    let mut settings: serde_json::Map<String, Value> = settings_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    settings.extend(env_overrides());
+    settings
+}
+
+/// Settings keys set through the environment. Values are read as JSON when they
+/// parse (numbers, booleans) and as plain strings otherwise.
+pub fn env_overrides() -> serde_json::Map<String, Value> {
+    std::env::vars()
+        .filter_map(|(name, raw)| {
+            let key = name.strip_prefix(ENV_PREFIX)?;
+            let value = serde_json::from_str::<Value>(&raw)
+                .ok()
+                .filter(|v| !v.is_object() && !v.is_array())
+                .unwrap_or(Value::String(raw));
+            Some((camel_case(key), value))
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_env_overrides() -> serde_json::Map<String, Value> {
+    env_overrides()
+}
+
+// POLLING_INTERVAL -> pollingInterval
+fn camel_case(screaming: &str) -> String {
+    let mut out = String::with_capacity(screaming.len());
+    for (i, word) in screaming.split('_').filter(|w| !w.is_empty()).enumerate() {
+        let word = word.to_ascii_lowercase();
+        if i == 0 {
+            out.push_str(&word);
+        } else {
+            let mut chars = word.chars();
+            out.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+/// Apply "logLevel" (error, warn, info, debug or trace) to the app's own log. Read
+/// once at startup; info when unset.
+pub fn apply_log_level(app: &AppHandle) {
+    let level = load(app)
+        .get("logLevel")
+        .and_then(Value::as_str)
+        .and_then(|l| l.parse::<log::LevelFilter>().ok())
+        .unwrap_or(log::LevelFilter::Info);
+    log::set_max_level(level);
+}
+
+/// Set one key in settings.json, the same way a save from the frontend would
+pub fn update(app: &AppHandle, key: &str, value: Value) -> Result<(), String> {
+    let path = settings_path(app)?;
+    let mut settings: serde_json::Map<String, Value> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    settings.insert(key.into(), value);
+    write(app, &settings)
+}
+
+/// Replace the settings file with what the frontend saves
+#[tauri::command]
+pub fn save_settings(
+    app: AppHandle,
+    settings: serde_json::Map<String, Value>,
+) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    write(&app, &settings)
+}
+
+fn write(app: &AppHandle, settings: &serde_json::Map<String, Value>) -> Result<(), String> {
+    fs::create_dir_all(config_dir(app)?).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(settings_path(app)?, json).map_err(|e| e.to_string())
+}
+
+pub fn get_u64(settings: &serde_json::Map<String, Value>, key: &str) -> Option<u64> {
+    settings.get(key).and_then(Value::as_u64)
+}
+
+/// Bump together with a new entry in MIGRATIONS
+const SCHEMA_VERSION: u64 = 1;
+
+// MIGRATIONS[n] upgrades a version-n settings object to n + 1. Files written
+// before versioning existed count as version 0.
+const MIGRATIONS: [fn(&mut serde_json::Map<String, Value>); SCHEMA_VERSION as usize] = [
+    // 0 -> 1: numbers typed into older builds were sometimes saved as strings
+    |settings| {
+        const NUMERIC: [&str; 8] = [
+            "port",
+            "pollingInterval",
+            "logBufferSize",
+            "maxRetries",
+            "retryBaseDelay",
+            "retryMaxDelay",
+            "defaultCooldown",
+            "maxWaitBeforeError",
+        ];
+        for key in NUMERIC {
+            if let Some(value) = settings.get_mut(key) {
+                if let Some(n) = value.as_str().and_then(|s| s.trim().parse::<u64>().ok()) {
+                    *value = n.into();
+                }
+            }
+        }
+    },
+];
+
+/// Upgrade settings.json to the current schema, keeping a backup of the file as
+/// it was. A file that doesn't parse is moved aside instead of being replaced by
+/// defaults on the frontend's next save.
+pub fn migrate(app: &AppHandle) -> Result<(), String> {
+    let path = settings_path(app)?;
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+
+    let mut settings: serde_json::Map<String, Value> = match serde_json::from_str(&content) {
+        Ok(settings) => settings,
+        Err(e) => {
+            let aside = path.with_extension(format!("corrupt-{}.json", crate::traffic::now_ms()));
+            fs::rename(&path, &aside).map_err(|e| e.to_string())?;
+            log::error!(
+                "settings.json is unreadable ({e}), moved to {}",
+                aside.display()
+            );
+            return Ok(());
+        }
+    };
+
+    let from = get_u64(&settings, "schemaVersion").unwrap_or(0);
+    if from >= SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let backup = path.with_extension(format!("v{from}.bak.json"));
+    fs::copy(&path, &backup).map_err(|e| e.to_string())?;
+    upgrade(&mut settings, from);
+
+    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    log::info!(
+        "migrated settings from schema {from} to {SCHEMA_VERSION} (backup at {})",
+        backup.display()
+    );
+    Ok(())
+}
+
+// Run the migrations from `from` on and stamp the current version
+fn upgrade(settings: &mut serde_json::Map<String, Value>, from: u64) {
+    for migration in &MIGRATIONS[from as usize..] {
+        migration(settings);
+    }
+    settings.insert("schemaVersion".into(), SCHEMA_VERSION.into());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> serde_json::Map<String, Value> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn upgrades_a_version_0_file() {
+        // As older builds saved it: no schemaVersion, some numbers as strings
+        let mut settings = parse(
+            r#"{
+                "port": "8081",
+                "pollingInterval": " 5000 ",
+                "maxRetries": 3,
+                "retryBaseDelay": "soon",
+                "logLevel": "debug",
+                "autoStartProxy": true
+            }"#,
+        );
+        let from = get_u64(&settings, "schemaVersion").unwrap_or(0);
+        assert_eq!(from, 0);
+        upgrade(&mut settings, from);
+
+        let saved = serde_json::to_string_pretty(&settings).unwrap();
+        let reloaded = parse(&saved);
+        assert_eq!(
+            reloaded,
+            parse(
+                r#"{
+                    "port": 8081,
+                    "pollingInterval": 5000,
+                    "maxRetries": 3,
+                    "retryBaseDelay": "soon",
+                    "logLevel": "debug",
+                    "autoStartProxy": true,
+                    "schemaVersion": 1
+                }"#
+            )
+        );
+        assert_eq!(get_u64(&reloaded, "port"), Some(8081));
+    }
+
+    #[test]
+    fn current_files_are_left_alone() {
+        let mut settings = parse(r#"{ "port": "8081", "schemaVersion": 1 }"#);
+        upgrade(&mut settings, SCHEMA_VERSION);
+        assert_eq!(settings, parse(r#"{ "port": "8081", "schemaVersion": 1 }"#));
+    }
+
+    #[test]
+    fn env_var_names_become_settings_keys() {
+        assert_eq!(camel_case("PORT"), "port");
+        assert_eq!(camel_case("LOG_LEVEL"), "logLevel");
+        assert_eq!(camel_case("POLLING_INTERVAL"), "pollingInterval");
+        assert_eq!(camel_case("MAX_WAIT_BEFORE_ERROR"), "maxWaitBeforeError");
+        assert_eq!(camel_case("AUTO__START_PROXY"), "autoStartProxy");
+        assert_eq!(camel_case("_BIND_ADDRESS_"), "bindAddress");
+        assert_eq!(camel_case(""), "");
+    }
+}
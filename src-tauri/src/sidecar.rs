@@ -0,0 +1,402 @@
+// Proxy engine process (the `antigravity-claude-proxy` npm package)
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_shell::{
+    process::{CommandChild, CommandEvent},
+    ShellExt,
+};
+
+use crate::{
+    alerts, api, audit, badge, journal, lock, priority, settings, telemetry, traffic, tray,
+};
+
+/// Log target for everything the engine prints
+pub const LOG_TARGET: &str = "sidecar";
+
+#[cfg(windows)]
+pub const PROXY_BIN: &str = "antigravity-claude-proxy.cmd";
+#[cfg(not(windows))]
+pub const PROXY_BIN: &str = "antigravity-claude-proxy";
+
+// How long a replacement engine gets to answer /health, and how long requests on
+// the old one get to finish
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Launch {
+    pub port: u16,
+    pub fallback_enabled: bool,
+}
+
+#[derive(Default)]
+pub struct SidecarState {
+    child: Mutex<Option<CommandChild>>,
+    launch: Mutex<Option<Launch>>,
+    // bindAddress the running engine was started with
+    bind: Mutex<Option<String>>,
+}
+
+impl SidecarState {
+    pub fn pid(&self) -> Option<u32> {
+        self.child.lock().unwrap().as_ref().map(|c| c.pid())
+    }
+
+    /// Arguments of the last start, kept so the engine can be restarted from Rust
+    pub fn launch(&self) -> Option<Launch> {
+        *self.launch.lock().unwrap()
+    }
+}
+
+/// Port the engine runs on, or will run on next start
+pub fn current_port(app: &AppHandle) -> u16 {
+    app.state::<SidecarState>()
+        .launch()
+        .map(|l| l.port)
+        .or_else(|| {
+            settings::get_u64(&settings::load(app), "port").and_then(|p| u16::try_from(p).ok())
+        })
+        .unwrap_or(settings::DEFAULT_PORT)
+}
+
+/// Start the proxy engine, replacing any process we already own
+#[tauri::command]
+pub fn start_proxy(app: AppHandle, port: u16, fallback_enabled: bool) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    telemetry::count(&app, "proxy.start");
+    spawn(
+        &app,
+        Launch {
+            port,
+            fallback_enabled,
+        },
+    )?;
+    audit::record(
+        &app,
+        "proxy.start",
+        serde_json::json!({ "port": port, "fallbackEnabled": fallback_enabled }),
+    );
+    Ok(())
+}
+
+/// Start the engine at launch when "autoStartProxy" is on and setup is done. Done
+/// here rather than by the window, since a locked window can't start it.
+pub fn autostart(app: &AppHandle) {
+    let settings = settings::load(app);
+    let on = |key: &str| settings.get(key).and_then(Value::as_bool).unwrap_or(false);
+    if !on("setupCompleted") || !on("autoStartProxy") {
+        return;
+    }
+    let launch = Launch {
+        port: current_port(app),
+        fallback_enabled: false,
+    };
+    if let Err(e) = spawn(app, launch) {
+        log::error!(target: LOG_TARGET, "failed to start the engine at launch: {e}");
+    }
+}
+
+/// Apply a new port, bind address or fallback flag without cutting off requests.
+/// A new port gets a fresh engine first; the old one is stopped once it is idle,
+/// so clients can move over at their own pace. On the same port the restart
+/// waits until nothing is in flight, since two engines can't share the socket.
+#[tauri::command]
+pub async fn reconfigure_proxy(
+    app: AppHandle,
+    port: u16,
+    fallback_enabled: bool,
+) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    let launch = Launch {
+        port,
+        fallback_enabled,
+    };
+    let state = app.state::<SidecarState>();
+    let bind = settings::load(&app)
+        .get("bindAddress")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let Some(old) = state.launch().filter(|_| state.pid().is_some()) else {
+        return start_proxy(app.clone(), port, fallback_enabled);
+    };
+    if old == launch && *state.bind.lock().unwrap() == bind {
+        return Ok(());
+    }
+    audit::record(
+        &app,
+        "proxy.reconfigure",
+        serde_json::json!({ "port": port, "fallbackEnabled": fallback_enabled, "bindAddress": bind }),
+    );
+
+    if old.port == port {
+        if let Some(pid) = state.pid() {
+            wait_for_idle(&app, pid).await;
+        }
+        return restart_with(&app, launch).await;
+    }
+
+    let previous = state.child.lock().unwrap().take();
+    let previous_bind = state.bind.lock().unwrap().clone();
+    // Put the old engine back in charge if the new one never takes over
+    let keep_old = |previous: Option<CommandChild>, previous_bind: Option<String>| {
+        kill_child(&state);
+        *state.child.lock().unwrap() = previous;
+        *state.launch.lock().unwrap() = Some(old);
+        api::set_bind_address(previous_bind.as_deref());
+        *state.bind.lock().unwrap() = previous_bind;
+    };
+    if let Err(e) = spawn(&app, launch) {
+        keep_old(previous, previous_bind);
+        return Err(format!("{e}; kept port {}", old.port));
+    }
+    if !wait_until_ready(port).await {
+        keep_old(previous, previous_bind);
+        return Err(format!(
+            "The proxy did not come up on port {port}; kept port {}",
+            old.port
+        ));
+    }
+    log::info!(target: LOG_TARGET, "engine moved to port {port}; draining port {}", old.port);
+
+    if let Some(previous) = previous {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let pid = previous.pid();
+            wait_for_idle(&app, pid).await;
+            if let Err(e) = previous.kill() {
+                log::warn!(target: LOG_TARGET, "failed to stop old engine (pid {pid}): {e}");
+            }
+            free_port(&app, old.port).await;
+            log::info!(target: LOG_TARGET, "old engine on port {} stopped", old.port);
+        });
+    }
+    Ok(())
+}
+
+async fn wait_until_ready(port: u16) -> bool {
+    let deadline = Instant::now() + READY_TIMEOUT;
+    while Instant::now() < deadline {
+        if api::get::<Value>(port, "/health").await.is_ok() {
+            return true;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    false
+}
+
+// Only the engine with `pid` counts; one still draining on an old port, or its
+// replacement, doesn't hold this one up
+async fn wait_for_idle(app: &AppHandle, pid: u32) {
+    let deadline = Instant::now() + DRAIN_TIMEOUT;
+    while traffic::in_flight_for(app, pid) > 0 && Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Restart a running engine once it has no requests in flight
+pub async fn restart_when_idle(app: &AppHandle) -> Result<(), String> {
+    let Some(pid) = app.state::<SidecarState>().pid() else {
+        return Ok(());
+    };
+    wait_for_idle(app, pid).await;
+    restart(app).await
+}
+
+/// Restart with the last used arguments
+pub async fn restart(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<SidecarState>();
+    let launch = state.launch().ok_or("Proxy has not been started yet")?;
+    restart_with(app, launch).await
+}
+
+async fn restart_with(app: &AppHandle, launch: Launch) -> Result<(), String> {
+    shut_down(app, launch.port).await;
+    spawn(app, launch)
+}
+
+/// Stop a running engine, port and all, e.g. while its package is replaced.
+/// Returns what it ran with, to hand to `resume` afterwards.
+pub async fn stop_for_update(app: &AppHandle) -> Option<Launch> {
+    let state = app.state::<SidecarState>();
+    let launch = state.launch().filter(|_| state.pid().is_some())?;
+    shut_down(app, launch.port).await;
+    Some(launch)
+}
+
+/// Start the engine again after `stop_for_update`, if it was running then
+pub fn resume(app: &AppHandle, launch: Option<Launch>) -> Result<(), String> {
+    launch.map_or(Ok(()), |launch| spawn(app, launch))
+}
+
+async fn shut_down(app: &AppHandle, port: u16) {
+    let state = app.state::<SidecarState>();
+    let pid = state.pid();
+    kill_child(&state);
+    // The replacement's exit events won't cover what the old engine had open
+    if let Some(pid) = pid {
+        traffic::clear_in_flight(app, pid);
+    }
+    journal::engine_exited(app, None);
+    free_port(app, port).await;
+}
+
+fn spawn(app: &AppHandle, launch: Launch) -> Result<(), String> {
+    let state = app.state::<SidecarState>();
+    kill_child(&state);
+
+    let mut args = vec!["start"];
+    if launch.fallback_enabled {
+        args.push("--fallback");
+    }
+
+    let port = launch.port;
+    let mut command = app
+        .shell()
+        .command(PROXY_BIN)
+        .args(args)
+        .env("PORT", port.to_string());
+    // Headless setups expose the engine beyond localhost (ACP_BIND_ADDRESS).
+    // "::" listens dual-stack, "::1" on IPv6 loopback only.
+    let settings = settings::load(app);
+    let bind = settings.get("bindAddress").and_then(Value::as_str);
+    if let Some(host) = bind {
+        command = command.env("HOST", api::listen_host(host));
+    }
+    api::set_bind_address(bind);
+    *state.bind.lock().unwrap() = bind.map(str::to_string);
+    let (mut rx, child) = command.spawn().map_err(|e| e.to_string())?;
+
+    let pid = child.pid();
+    log::info!(target: LOG_TARGET, "engine started on port {port} (pid {pid})");
+    *state.child.lock().unwrap() = Some(child);
+    *state.launch.lock().unwrap() = Some(launch);
+    priority::apply_after_start(app, pid);
+
+    // Forward output line by line so the engine shows up in the same log as the shell
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => forward_line(&app, pid, &line, log::Level::Info),
+                CommandEvent::Stderr(line) => forward_line(&app, pid, &line, log::Level::Warn),
+                CommandEvent::Error(err) => log::error!(target: LOG_TARGET, "{err}"),
+                CommandEvent::Terminated(payload) => {
+                    log::info!(target: LOG_TARGET, "engine exited (code {:?}, signal {:?})", payload.code, payload.signal);
+                    // Only forget the handle if it still belongs to this process;
+                    // no handle at all means it was stopped on purpose
+                    let state = app.state::<SidecarState>();
+                    let ours = {
+                        let mut child = state.child.lock().unwrap();
+                        let ours = child.as_ref().is_none_or(|c| c.pid() == pid);
+                        if ours {
+                            *child = None;
+                        }
+                        ours
+                    };
+                    // A drained engine on an old port has nothing open any more either
+                    traffic::clear_in_flight(&app, pid);
+                    if ours {
+                        journal::engine_exited(&app, payload.code);
+                        badge::refresh(&app);
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Ok(())
+}
+
+// The launcher script can leave node holding the port, same cleanup as proxyService.stopProxy
+pub(crate) async fn free_port(app: &AppHandle, port: u16) {
+    let command = if cfg!(windows) {
+        app.shell().command("powershell").args([
+            "-Command".to_string(),
+            format!("$conn = Get-NetTCPConnection -LocalPort {port} -ErrorAction SilentlyContinue | Select-Object -First 1; if ($conn) {{ taskkill /F /PID $($conn.OwningProcess) /T 2>$null }}"),
+        ])
+    } else {
+        app.shell().command("sh").args([
+            "-c".to_string(),
+            format!("lsof -ti:{port} | xargs kill -9 2>/dev/null || true"),
+        ])
+    };
+
+    if let Err(e) = command.output().await {
+        log::warn!(target: LOG_TARGET, "failed to free port {port}: {e}");
+    }
+}
+
+/// Stop the engine process we own (port cleanup is still done by the frontend)
+#[tauri::command]
+pub fn stop_proxy(app: AppHandle, state: State<'_, SidecarState>) -> Result<(), String> {
+    lock::ensure_unlocked(&app)?;
+    kill_child(&state);
+    *state.launch.lock().unwrap() = None;
+    audit::record(&app, "proxy.stop", Value::Null);
+    Ok(())
+}
+
+pub fn kill_child(state: &SidecarState) {
+    if let Some(child) = state.child.lock().unwrap().take() {
+        let pid = child.pid();
+        if let Err(e) = child.kill() {
+            log::warn!(target: LOG_TARGET, "failed to kill engine (pid {pid}): {e}");
+        }
+    }
+}
+
+fn forward_line(app: &AppHandle, pid: u32, raw: &[u8], default_level: log::Level) {
+    let line = strip_ansi(&String::from_utf8_lossy(raw));
+    let line = line.trim_end();
+    if line.is_empty() {
+        return;
+    }
+    log::log!(target: LOG_TARGET, level_for(line).unwrap_or(default_level), "{line}");
+    if let Some(class) = traffic::observe(app, pid, line) {
+        alerts::on_failure(app, class);
+        telemetry::count_error(app, class);
+        tray::refresh(app);
+    }
+    badge::refresh(app);
+}
+
+// The engine tags its own lines with [ERROR] / [WARN] / [DEBUG]; keep that level
+pub fn level_for(line: &str) -> Option<log::Level> {
+    let upper = line.to_ascii_uppercase();
+    if upper.contains("[ERROR]") {
+        Some(log::Level::Error)
+    } else if upper.contains("[WARN]") {
+        Some(log::Level::Warn)
+    } else if upper.contains("[DEBUG]") {
+        Some(log::Level::Debug)
+    } else if upper.contains("[INFO]") || upper.contains("[SUCCESS]") {
+        Some(log::Level::Info)
+    } else {
+        None
+    }
+}
+
+// Engine output is colored for terminals; drop the escape sequences
+pub fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,419 @@
+// Settings sync through a user-chosen folder that something else (Dropbox,
+// Syncthing, a network share) keeps in step between machines. settings.json and
+// the mapping presets are mirrored there. Each round does a three-way merge
+// against the copy from the last round (kept under app data), key by key, so
+// edits made on different machines to different keys both survive. A key changed
+// on both sides keeps the local value (the folder's value on the very first round,
+// when this machine joins), and the losing version of the file is set aside as a
+// conflict copy in the folder for the user to look at.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{alerts, audit, i18n, lock, mappings, settings, traffic, tray};
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(15);
+// Local files written more recently than this are left for the next round, so a
+// burst of saves from the Settings page is pushed once
+const DEBOUNCE: Duration = Duration::from_secs(5);
+const BASE_DIR: &str = "sync-base";
+// Settings that describe this machine rather than the user's preferences
+const LOCAL_KEYS: [&str; 5] = [
+    "syncFolder",
+    "bindAddress",
+    "mcpPort",
+    "schemaVersion",
+    "setupCompleted",
+];
+
+#[derive(Clone, Copy)]
+enum SyncedFile {
+    Settings,
+    MappingPresets,
+}
+
+const FILES: [SyncedFile; 2] = [SyncedFile::Settings, SyncedFile::MappingPresets];
+
+impl SyncedFile {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Settings => "settings.json",
+            Self::MappingPresets => "mapping-presets.json",
+        }
+    }
+
+    fn local_path(self, app: &AppHandle) -> Result<PathBuf, String> {
+        match self {
+            Self::Settings => settings::settings_path(app),
+            Self::MappingPresets => mappings::presets_path(app),
+        }
+    }
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    folder: Option<String>,
+    /// Files changed locally by the last round
+    pulled: Vec<String>,
+    /// Files written to the sync folder by the last round
+    pushed: Vec<String>,
+    /// Conflict copies written to the sync folder by the last round
+    conflicts: Vec<String>,
+    errors: Vec<String>,
+}
+
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(SYNC_INTERVAL);
+        if folder(&app).is_some() {
+            run(&app);
+        }
+    });
+}
+
+/// Sync right away and report what happened
+#[tauri::command]
+pub fn sync_now(app: AppHandle) -> SyncStatus {
+    run(&app)
+}
+
+/// Point sync at a folder, or turn it off with `None`. The first round runs
+/// immediately and merges whatever is already in the folder.
+#[tauri::command]
+pub fn set_sync_folder(app: AppHandle, folder: Option<String>) -> Result<SyncStatus, String> {
+    lock::ensure_unlocked(&app)?;
+    let folder = folder
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty());
+    if let Some(folder) = &folder {
+        if !Path::new(folder).is_dir() {
+            return Err(format!("{folder} is not a folder"));
+        }
+    }
+    settings::update(&app, "syncFolder", folder.clone().into())?;
+    // A new folder starts from scratch rather than from the old folder's history
+    let _ = fs::remove_dir_all(base_dir(&app)?);
+    audit::record(&app, "sync.folder", serde_json::json!({ "folder": folder }));
+
+    Ok(match folder {
+        Some(_) => run(&app),
+        None => SyncStatus::default(),
+    })
+}
+
+fn folder(app: &AppHandle) -> Option<PathBuf> {
+    settings::load(app)
+        .get("syncFolder")
+        .and_then(Value::as_str)
+        .filter(|f| !f.is_empty())
+        .map(PathBuf::from)
+}
+
+fn run(app: &AppHandle) -> SyncStatus {
+    let mut status = SyncStatus::default();
+    let Some(folder) = folder(app) else {
+        return status;
+    };
+    status.folder = Some(folder.display().to_string());
+    if !folder.is_dir() {
+        status
+            .errors
+            .push(format!("{} is not reachable", folder.display()));
+        return status;
+    }
+
+    for file in FILES {
+        if let Err(e) = sync_file(app, &folder, file, &mut status) {
+            log::warn!("sync of {} failed: {e}", file.name());
+            status.errors.push(format!("{}: {e}", file.name()));
+        }
+    }
+
+    if !status.pulled.is_empty() {
+        log::info!("sync: pulled {}", status.pulled.join(", "));
+        let _ = app.emit("settings-synced", &status.pulled);
+        tray::refresh(app);
+    }
+    if status
+        .pulled
+        .iter()
+        .any(|f| f == SyncedFile::MappingPresets.name())
+    {
+        // The engine only learns about the active preset when it is applied
+        let app = app.clone();
+        let active = mappings::load(&app).active().map(str::to_string);
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = mappings::apply(&app, active).await {
+                log::debug!("synced mapping preset not applied: {e}");
+            }
+        });
+    }
+    if !status.conflicts.is_empty() {
+        alerts::notify(
+            app,
+            &i18n::t(app, "sync.conflictTitle"),
+            &i18n::tf(
+                app,
+                "sync.conflictBody",
+                &[("files", status.conflicts.join(", "))],
+            ),
+        );
+    }
+    status
+}
+
+fn sync_file(
+    app: &AppHandle,
+    folder: &Path,
+    file: SyncedFile,
+    status: &mut SyncStatus,
+) -> Result<(), String> {
+    let local_path = file.local_path(app)?;
+    let remote_path = folder.join(file.name());
+    let base_path = base_dir(app)?.join(file.name());
+
+    if modified_within(&local_path, DEBOUNCE) {
+        return Ok(());
+    }
+    let local_full = read(&local_path)?;
+    let (local, kept) = split_local(file, local_full.clone());
+    let (remote, _) = split_local(file, read(&remote_path)?);
+    if local == remote {
+        return write(&base_path, &local);
+    }
+    let joining = !base_path.exists();
+    let base = read(&base_path)?;
+
+    let mut conflicts = 0;
+    let merged = merge(&base, &local, &remote, joining, &mut conflicts);
+
+    if conflicts > 0 {
+        let losing = if joining { &local } else { &remote };
+        let copy = folder.join(format!(
+            "{}.conflict-{}.json",
+            file.name().trim_end_matches(".json"),
+            traffic::now_ms()
+        ));
+        write(&copy, losing)?;
+        status.conflicts.push(copy.display().to_string());
+    }
+    if merged != local {
+        let mut full = merged.clone();
+        full.extend(kept);
+        if full != local_full {
+            write(&local_path, &full)?;
+            status.pulled.push(file.name().to_string());
+        }
+    }
+    if merged != remote {
+        write(&remote_path, &merged)?;
+        status.pushed.push(file.name().to_string());
+    }
+    write(&base_path, &merged)
+}
+
+// Three-way merge of JSON objects. Nested objects merge key by key too.
+fn merge(
+    base: &Map<String, Value>,
+    local: &Map<String, Value>,
+    remote: &Map<String, Value>,
+    prefer_remote: bool,
+    conflicts: &mut usize,
+) -> Map<String, Value> {
+    let mut keys: Vec<&String> = local.keys().chain(remote.keys()).collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let mut out = Map::new();
+    for key in keys {
+        let (b, l, r) = (base.get(key), local.get(key), remote.get(key));
+        let value = if l == r || r == b {
+            l
+        } else if l == b {
+            r
+        } else if let (Some(Value::Object(l)), Some(Value::Object(r))) = (l, r) {
+            let empty = Map::new();
+            let b = match b {
+                Some(Value::Object(b)) => b,
+                _ => &empty,
+            };
+            out.insert(
+                key.clone(),
+                Value::Object(merge(b, l, r, prefer_remote, conflicts)),
+            );
+            continue;
+        } else {
+            *conflicts += 1;
+            if prefer_remote {
+                r
+            } else {
+                l
+            }
+        };
+        if let Some(value) = value {
+            out.insert(key.clone(), value.clone());
+        }
+    }
+    out
+}
+
+// Machine-specific settings never leave this machine
+fn split_local(
+    file: SyncedFile,
+    mut content: Map<String, Value>,
+) -> (Map<String, Value>, Map<String, Value>) {
+    let mut kept = Map::new();
+    if let SyncedFile::Settings = file {
+        for key in LOCAL_KEYS {
+            if let Some(value) = content.remove(key) {
+                kept.insert(key.to_string(), value);
+            }
+        }
+    }
+    (content, kept)
+}
+
+fn modified_within(path: &Path, window: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|age| age < window)
+}
+
+// A missing file reads as empty; an unreadable one stops the round for that file
+fn read(path: &Path) -> Result<Map<String, Value>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("{} is not valid JSON: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn write(path: &Path, content: &Map<String, Value>) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(content).map_err(|e| e.to_string())?;
+    // Write beside and rename, so the sync client never uploads half a file
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+fn base_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(BASE_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    fn merged(base: Value, local: Value, remote: Value, prefer_remote: bool) -> (Value, usize) {
+        let mut conflicts = 0;
+        let out = merge(
+            &object(base),
+            &object(local),
+            &object(remote),
+            prefer_remote,
+            &mut conflicts,
+        );
+        (Value::Object(out), conflicts)
+    }
+
+    #[test]
+    fn takes_the_side_that_changed() {
+        let base = json!({ "a": 1, "b": 1 });
+        let (out, conflicts) = merged(
+            base.clone(),
+            json!({ "a": 2, "b": 1 }),
+            json!({ "a": 1, "b": 3 }),
+            false,
+        );
+        assert_eq!(out, json!({ "a": 2, "b": 3 }));
+        assert_eq!(conflicts, 0);
+    }
+
+    #[test]
+    fn keeps_additions_and_deletions_from_either_side() {
+        let (out, conflicts) = merged(
+            json!({ "gone_locally": 1, "gone_remotely": 1 }),
+            json!({ "gone_remotely": 1, "new_locally": 1 }),
+            json!({ "gone_locally": 1, "new_remotely": 1 }),
+            false,
+        );
+        assert_eq!(out, json!({ "new_locally": 1, "new_remotely": 1 }));
+        assert_eq!(conflicts, 0);
+    }
+
+    #[test]
+    fn same_change_on_both_sides_is_not_a_conflict() {
+        let (out, conflicts) = merged(
+            json!({ "a": 1 }),
+            json!({ "a": 2 }),
+            json!({ "a": 2 }),
+            false,
+        );
+        assert_eq!(out, json!({ "a": 2 }));
+        assert_eq!(conflicts, 0);
+    }
+
+    #[test]
+    fn conflicts_go_to_the_preferred_side() {
+        let sides = || (json!({ "a": 1 }), json!({ "a": 2 }), json!({ "a": 3 }));
+        let (base, local, remote) = sides();
+        assert_eq!(merged(base, local, remote, false), (json!({ "a": 2 }), 1));
+        let (base, local, remote) = sides();
+        assert_eq!(merged(base, local, remote, true), (json!({ "a": 3 }), 1));
+        // Deleted on one side, edited on the other
+        let (out, conflicts) = merged(json!({ "a": 1 }), json!({}), json!({ "a": 3 }), false);
+        assert_eq!(out, json!({}));
+        assert_eq!(conflicts, 1);
+    }
+
+    #[test]
+    fn merges_nested_objects_key_by_key() {
+        let (out, conflicts) = merged(
+            json!({ "m": { "x": 1, "y": 1 } }),
+            json!({ "m": { "x": 2, "y": 1 } }),
+            json!({ "m": { "x": 1, "y": 3, "z": 4 } }),
+            false,
+        );
+        assert_eq!(out, json!({ "m": { "x": 2, "y": 3, "z": 4 } }));
+        assert_eq!(conflicts, 0);
+    }
+
+    #[test]
+    fn objects_added_on_both_sides_merge_against_an_empty_base() {
+        let (out, conflicts) = merged(
+            json!({}),
+            json!({ "m": { "x": 1, "y": 2 } }),
+            json!({ "m": { "x": 1, "y": 3 } }),
+            true,
+        );
+        assert_eq!(out, json!({ "m": { "x": 1, "y": 3 } }));
+        assert_eq!(conflicts, 1);
+    }
+}
@@ -0,0 +1,236 @@
+// Opt-in anonymous usage counters. Counts of feature use and error classes are
+// batched hourly into a local queue together with the platform; no emails, paths,
+// prompts or model output are ever recorded. The queue is uploaded only when both
+// `telemetry` is on and a `telemetryUrl` is configured.
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::{api, settings, traffic};
+
+const QUEUE_FILE: &str = "telemetry-queue.json";
+const BATCH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+// Upload every sixth batch, i.e. every six hours
+const BATCHES_PER_UPLOAD: u32 = 6;
+// A week of hourly batches; older ones are dropped if uploads keep failing
+const MAX_QUEUED_BATCHES: usize = 7 * 24;
+
+#[derive(Default)]
+pub struct TelemetryState {
+    enabled: AtomicBool,
+    counters: Mutex<BTreeMap<String, u64>>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    start: u64,
+    end: u64,
+    counters: BTreeMap<String, u64>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryQueue {
+    // Random, not derived from the machine; regenerated whenever telemetry is turned off
+    install_id: String,
+    app_version: String,
+    os: String,
+    arch: String,
+    batches: Vec<Batch>,
+}
+
+/// Count one use of a feature or one failure; a no-op while telemetry is off
+pub fn count(app: &AppHandle, key: &str) {
+    let state = app.state::<TelemetryState>();
+    if state.enabled.load(Ordering::Relaxed) {
+        *state
+            .counters
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_insert(0) += 1;
+    }
+}
+
+pub fn count_error(app: &AppHandle, class: traffic::ErrorClass) {
+    if let Some(name) = serde_json::to_value(class)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+    {
+        count(app, &format!("error.{name}"));
+    }
+}
+
+pub fn spawn(app: AppHandle) {
+    refresh_enabled(&app);
+    thread::spawn(move || {
+        let mut start = traffic::now_ms();
+        let mut ticks = 0;
+        loop {
+            thread::sleep(BATCH_INTERVAL);
+            refresh_enabled(&app);
+            let end = traffic::now_ms();
+            if let Err(e) = flush(&app, start, end) {
+                log::warn!("telemetry: failed to queue batch: {e}");
+            }
+            start = end;
+
+            ticks += 1;
+            if ticks % BATCHES_PER_UPLOAD == 0 {
+                tauri::async_runtime::block_on(upload(&app));
+            }
+        }
+    });
+}
+
+fn settings_flags(app: &AppHandle) -> (bool, Option<String>) {
+    let settings = settings::load(app);
+    let enabled = settings
+        .get("telemetry")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let url = settings
+        .get("telemetryUrl")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+    (enabled, url)
+}
+
+// Turning telemetry off drops everything collected so far
+fn refresh_enabled(app: &AppHandle) {
+    let (enabled, _) = settings_flags(app);
+    let state = app.state::<TelemetryState>();
+    let was_enabled = state.enabled.swap(enabled, Ordering::Relaxed);
+    if !enabled && was_enabled {
+        state.counters.lock().unwrap().clear();
+    }
+    if !enabled {
+        if let Ok(path) = queue_path(app) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn flush(app: &AppHandle, start: u64, end: u64) -> Result<(), String> {
+    let counters = std::mem::take(&mut *app.state::<TelemetryState>().counters.lock().unwrap());
+    if counters.is_empty() {
+        return Ok(());
+    }
+
+    let mut queue = load_queue(app);
+    queue.batches.push(Batch {
+        start,
+        end,
+        counters,
+    });
+    let excess = queue.batches.len().saturating_sub(MAX_QUEUED_BATCHES);
+    queue.batches.drain(..excess);
+    save_queue(app, &queue)
+}
+
+async fn upload(app: &AppHandle) {
+    let (enabled, Some(url)) = settings_flags(app) else {
+        return;
+    };
+    let queue = load_queue(app);
+    if !enabled || queue.batches.is_empty() {
+        return;
+    }
+
+    let sent = queue.batches.len();
+    let result = api::client()
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_string(&queue).unwrap_or_default())
+        .send()
+        .await
+        .and_then(|r| r.error_for_status());
+    match result {
+        Ok(_) => {
+            // Batches queued while the upload was in flight stay for next time
+            let mut queue = load_queue(app);
+            queue.batches.drain(..sent.min(queue.batches.len()));
+            if let Err(e) = save_queue(app, &queue) {
+                log::warn!("telemetry: failed to trim queue: {e}");
+            }
+            log::info!("telemetry: uploaded {sent} batch(es)");
+        }
+        Err(e) => log::warn!("telemetry: upload failed, keeping queue: {e}"),
+    }
+}
+
+/// Everything that would be sent, for the user to inspect
+#[tauri::command]
+pub fn get_telemetry_queue(app: AppHandle) -> TelemetryQueue {
+    let mut queue = load_queue(&app);
+    // Include the batch that is still being counted
+    let pending = app
+        .state::<TelemetryState>()
+        .counters
+        .lock()
+        .unwrap()
+        .clone();
+    if !pending.is_empty() {
+        let now = traffic::now_ms();
+        queue.batches.push(Batch {
+            start: now,
+            end: now,
+            counters: pending,
+        });
+    }
+    queue
+}
+
+/// Pick up a change of the `telemetry` setting without waiting for the next batch
+#[tauri::command]
+pub fn refresh_telemetry(app: AppHandle) {
+    refresh_enabled(&app);
+}
+
+fn queue_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(QUEUE_FILE))
+}
+
+fn load_queue(app: &AppHandle) -> TelemetryQueue {
+    let mut queue: TelemetryQueue = queue_path(app)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    if queue.install_id.is_empty() {
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+        queue.install_id = id.iter().map(|b| format!("{b:02x}")).collect();
+    }
+    queue.app_version = app.package_info().version.to_string();
+    queue.os = std::env::consts::OS.into();
+    queue.arch = std::env::consts::ARCH.into();
+    queue
+}
+
+fn save_queue(app: &AppHandle, queue: &TelemetryQueue) -> Result<(), String> {
+    let path = queue_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(queue).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
@@ -0,0 +1,612 @@
+// Request metrics and error classes derived from the engine's log output.
+// The engine logs one line per finished request ("[POST] /v1/messages 200 (1234ms)")
+// plus context lines naming the model and account it picked. Attribution of model
+// and account is best-effort when requests overlap.
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{LazyLock, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use crate::{bench::percentile, idle, journal};
+
+// One hour of one-minute buckets per key
+const BUCKET_MS: u64 = 60_000;
+const MAX_BUCKETS: usize = 60;
+const MAX_SAMPLES_PER_BUCKET: usize = 1000;
+const MAX_RECENT_ERRORS: usize = 5;
+const MAX_SUMMARY_CHARS: usize = 80;
+
+static REQUEST_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[(GET|POST|PUT|DELETE|PATCH)\]\s+(/\S*)\s+(\d{3})\s+\((\d+)ms\)")
+        .expect("valid request pattern")
+});
+static MODEL_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)request for model:?\s+([A-Za-z0-9._\-]+)").expect("valid model pattern")
+});
+static ACCOUNT_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)using account:?\s+([A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+)")
+        .expect("valid account pattern")
+});
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    Auth,
+    RateLimit,
+    #[serde(rename = "upstream_5xx")]
+    Upstream5xx,
+    Network,
+    Translation,
+    ClientAbort,
+    Other,
+}
+
+impl ErrorClass {
+    fn from_status(status: u16) -> Self {
+        match status {
+            401 | 403 => Self::Auth,
+            429 => Self::RateLimit,
+            500..=599 => Self::Upstream5xx,
+            _ => Self::Other,
+        }
+    }
+
+    // Error text the engine prints before it answers with a failure status
+    fn from_message(line: &str) -> Option<Self> {
+        let lower = line.to_ascii_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+
+        if has(&[
+            "client closed",
+            "client disconnected",
+            "aborted",
+            "request aborted",
+        ]) {
+            Some(Self::ClientAbort)
+        } else if has(&[
+            "econnreset",
+            "etimedout",
+            "enotfound",
+            "econnrefused",
+            "socket hang up",
+            "fetch failed",
+            "network error",
+        ]) {
+            Some(Self::Network)
+        } else if has(&["rate limit", "resource_exhausted", "quota exceeded"]) {
+            Some(Self::RateLimit)
+        } else if has(&[
+            "unauthorized",
+            "invalid_grant",
+            "unauthenticated",
+            "permission_denied",
+        ]) {
+            Some(Self::Auth)
+        } else if has(&[
+            "failed to convert",
+            "translation",
+            "invalid_request",
+            "unexpected token",
+            "cannot read properties",
+        ]) {
+            Some(Self::Translation)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentError {
+    pub timestamp: u64,
+    pub class: ErrorClass,
+    pub summary: String,
+}
+
+pub struct RequestLine {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub duration_ms: u64,
+}
+
+pub fn parse_request_line(line: &str) -> Option<RequestLine> {
+    let caps = REQUEST_LINE.captures(line)?;
+    Some(RequestLine {
+        method: caps[1].to_string(),
+        path: caps[2].split('?').next().unwrap_or_default().to_string(),
+        status: caps[3].parse().ok()?,
+        duration_ms: caps[4].parse().ok()?,
+    })
+}
+
+// Only API traffic is interesting; the GUI's own polling would drown it out
+fn is_api_path(path: &str) -> bool {
+    path.starts_with("/v1/")
+}
+
+#[derive(Default)]
+struct Bucket {
+    start_ms: u64,
+    samples: Vec<u64>,
+}
+
+#[derive(Default)]
+struct Series {
+    buckets: VecDeque<Bucket>,
+}
+
+impl Series {
+    fn record(&mut self, now_ms: u64, latency_ms: u64) {
+        let start_ms = now_ms - now_ms % BUCKET_MS;
+        while self
+            .buckets
+            .front()
+            .is_some_and(|b| b.start_ms < window_start(now_ms))
+        {
+            self.buckets.pop_front();
+        }
+        if self.buckets.back().is_none_or(|b| b.start_ms != start_ms) {
+            self.buckets.push_back(Bucket {
+                start_ms,
+                samples: Vec::new(),
+            });
+            if self.buckets.len() > MAX_BUCKETS {
+                self.buckets.pop_front();
+            }
+        }
+        let bucket = self.buckets.back_mut().expect("bucket was just pushed");
+        if bucket.samples.len() < MAX_SAMPLES_PER_BUCKET {
+            bucket.samples.push(latency_ms);
+        }
+    }
+
+    // A key that has gone quiet still holds its last hour of traffic until
+    // something new is recorded for it, so the window is applied here as well
+    fn summary(&self, now_ms: u64, key: &str) -> LatencySummary {
+        let recent: Vec<&Bucket> = self
+            .buckets
+            .iter()
+            .filter(|b| b.start_ms >= window_start(now_ms))
+            .collect();
+        let mut all: Vec<u64> = recent
+            .iter()
+            .flat_map(|b| b.samples.iter().copied())
+            .collect();
+        all.sort_unstable();
+
+        LatencySummary {
+            key: key.to_string(),
+            count: all.len(),
+            p50_ms: percentile(&all, 50.0),
+            p95_ms: percentile(&all, 95.0),
+            series: recent
+                .iter()
+                .map(|b| {
+                    let mut samples = b.samples.clone();
+                    samples.sort_unstable();
+                    LatencyPoint {
+                        timestamp: b.start_ms,
+                        count: samples.len(),
+                        p50_ms: percentile(&samples, 50.0),
+                        p95_ms: percentile(&samples, 95.0),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+// Buckets that started before this are out of the last hour
+fn window_start(now_ms: u64) -> u64 {
+    now_ms.saturating_sub(MAX_BUCKETS as u64 * BUCKET_MS)
+}
+
+#[derive(Default)]
+struct TrafficInner {
+    endpoints: HashMap<String, Series>,
+    models: HashMap<String, Series>,
+    accounts: HashMap<String, Series>,
+    last_model: Option<String>,
+    last_account: Option<String>,
+    errors: BTreeMap<ErrorClass, u64>,
+    errors_since: u64,
+    // Cause printed by the engine, waiting for the failed request line it belongs to
+    pending_error: Option<(ErrorClass, String)>,
+    recent_errors: VecDeque<RecentError>,
+    // Requests whose model line was seen but not their response line yet, per
+    // engine pid, so a draining engine can be told apart from its replacement
+    in_flight: HashMap<u32, usize>,
+}
+
+#[derive(Default)]
+pub struct TrafficState(Mutex<TrafficInner>);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyPoint {
+    timestamp: u64,
+    count: usize,
+    p50_ms: u64,
+    p95_ms: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    key: String,
+    count: usize,
+    p50_ms: u64,
+    p95_ms: u64,
+    series: Vec<LatencyPoint>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCounters {
+    since: u64,
+    total: u64,
+    counts: BTreeMap<ErrorClass, u64>,
+}
+
+#[derive(Serialize)]
+pub struct LatencyStats {
+    endpoints: Vec<LatencySummary>,
+    models: Vec<LatencySummary>,
+    accounts: Vec<LatencySummary>,
+}
+
+// Request journal update for one line, applied once the traffic lock is
+// released since the journal writes its file on every change
+enum Journaled {
+    Started(String),
+    Routed(String),
+    Finished,
+}
+
+/// Feed one line of output from the engine with `pid`; returns the class of a
+/// failure it recorded
+pub fn observe(app: &AppHandle, pid: u32, line: &str) -> Option<ErrorClass> {
+    let mut journaled = None;
+    let failure = record_line(app, pid, line, &mut journaled);
+    match journaled {
+        Some(Journaled::Started(model)) => journal::started(app, &model),
+        Some(Journaled::Routed(account)) => journal::routed(app, &account),
+        Some(Journaled::Finished) => journal::finished(app),
+        None => {}
+    }
+    failure
+}
+
+fn record_line(
+    app: &AppHandle,
+    pid: u32,
+    line: &str,
+    journaled: &mut Option<Journaled>,
+) -> Option<ErrorClass> {
+    let state = app.state::<TrafficState>();
+    let mut inner = state.0.lock().unwrap();
+
+    if let Some(caps) = MODEL_LINE.captures(line) {
+        // Logged as soon as a request comes in, long before its response line
+        idle::touch(app);
+        inner.last_model = Some(caps[1].to_string());
+        *inner.in_flight.entry(pid).or_insert(0) += 1;
+        *journaled = Some(Journaled::Started(caps[1].to_string()));
+        return None;
+    }
+    if let Some(caps) = ACCOUNT_LINE.captures(line) {
+        inner.last_account = Some(caps[1].to_string());
+        *journaled = Some(Journaled::Routed(caps[1].to_string()));
+        return None;
+    }
+
+    let Some(request) = parse_request_line(line) else {
+        return match ErrorClass::from_message(line) {
+            // No response line follows an abort, so count it right away
+            Some(ErrorClass::ClientAbort) => {
+                finish(&mut inner, pid);
+                *journaled = Some(Journaled::Finished);
+                record_error(&mut inner, ErrorClass::ClientAbort, line);
+                Some(ErrorClass::ClientAbort)
+            }
+            Some(class) => {
+                inner.pending_error = Some((class, line.to_string()));
+                None
+            }
+            None => None,
+        };
+    };
+    // A cause only ever belongs to the response line right after it
+    let pending_error = inner.pending_error.take();
+    if !is_api_path(&request.path) {
+        return None;
+    }
+    idle::touch(app);
+
+    let failure = (request.status >= 400).then(|| {
+        let headline = format!("{} {} {}", request.method, request.path, request.status);
+        match pending_error {
+            Some((class, cause)) => (class, format!("{headline}: {cause}")),
+            None => (ErrorClass::from_status(request.status), headline),
+        }
+    });
+    if let Some((class, summary)) = &failure {
+        record_error(&mut inner, *class, summary);
+    }
+
+    let now = now_ms();
+    let latency = request.duration_ms;
+    let endpoint = format!("{} {}", request.method, request.path);
+    inner
+        .endpoints
+        .entry(endpoint)
+        .or_default()
+        .record(now, latency);
+
+    // count_tokens gets no model line, so it was never counted as started
+    if request.path.starts_with("/v1/messages") && request.path != "/v1/messages/count_tokens" {
+        finish(&mut inner, pid);
+        *journaled = Some(Journaled::Finished);
+        if let Some(model) = inner.last_model.clone() {
+            inner.models.entry(model).or_default().record(now, latency);
+        }
+        if let Some(account) = inner.last_account.clone() {
+            inner
+                .accounts
+                .entry(account)
+                .or_default()
+                .record(now, latency);
+        }
+    }
+
+    failure.map(|(class, _)| class)
+}
+
+fn finish(inner: &mut TrafficInner, pid: u32) {
+    if let Some(count) = inner.in_flight.get_mut(&pid) {
+        *count = count.saturating_sub(1);
+    }
+}
+
+fn record_error(inner: &mut TrafficInner, class: ErrorClass, summary: &str) {
+    let now = now_ms();
+    if inner.errors.is_empty() {
+        inner.errors_since = now;
+    }
+    *inner.errors.entry(class).or_insert(0) += 1;
+
+    let text = summary.trim();
+    let mut summary: String = text.chars().take(MAX_SUMMARY_CHARS).collect();
+    if summary.len() < text.len() {
+        summary.push('…');
+    }
+    inner.recent_errors.push_front(RecentError {
+        timestamp: now,
+        class,
+        summary,
+    });
+    inner.recent_errors.truncate(MAX_RECENT_ERRORS);
+}
+
+/// Model and account the engine logged for its most recent request
+pub fn last_route(app: &AppHandle) -> (Option<String>, Option<String>) {
+    let state = app.state::<TrafficState>();
+    let inner = state.0.lock().unwrap();
+    (inner.last_model.clone(), inner.last_account.clone())
+}
+
+/// Requests our engines are working on right now (best-effort, from their logs)
+pub fn in_flight(app: &AppHandle) -> usize {
+    app.state::<TrafficState>()
+        .0
+        .lock()
+        .unwrap()
+        .in_flight
+        .values()
+        .sum()
+}
+
+/// Requests the engine with `pid` is working on right now
+pub fn in_flight_for(app: &AppHandle, pid: u32) -> usize {
+    let state = app.state::<TrafficState>();
+    let inner = state.0.lock().unwrap();
+    inner.in_flight.get(&pid).copied().unwrap_or(0)
+}
+
+/// Nothing is in flight once the engine with `pid` has exited
+pub fn clear_in_flight(app: &AppHandle, pid: u32) {
+    app.state::<TrafficState>()
+        .0
+        .lock()
+        .unwrap()
+        .in_flight
+        .remove(&pid);
+}
+
+/// Last few failures, newest first
+pub fn recent_errors(app: &AppHandle) -> Vec<RecentError> {
+    let state = app.state::<TrafficState>();
+    let inner = state.0.lock().unwrap();
+    inner.recent_errors.iter().cloned().collect()
+}
+
+/// Failure counts per class since the last reset
+#[tauri::command]
+pub fn get_error_counters(state: State<'_, TrafficState>) -> ErrorCounters {
+    let inner = state.0.lock().unwrap();
+    ErrorCounters {
+        since: inner.errors_since,
+        total: inner.errors.values().sum(),
+        counts: inner.errors.clone(),
+    }
+}
+
+#[tauri::command]
+pub fn reset_error_counters(state: State<'_, TrafficState>) {
+    let mut inner = state.0.lock().unwrap();
+    inner.errors.clear();
+    inner.pending_error = None;
+}
+
+/// Rolling p50/p95 per endpoint, mapped model and account over the last hour
+#[tauri::command]
+pub fn get_latency_stats(state: State<'_, TrafficState>) -> LatencyStats {
+    let inner = state.0.lock().unwrap();
+    let now = now_ms();
+    let summarize = |map: &HashMap<String, Series>| {
+        let mut out: Vec<LatencySummary> = map.iter().map(|(k, s)| s.summary(now, k)).collect();
+        out.sort_by(|a, b| b.count.cmp(&a.count));
+        out
+    };
+
+    LatencyStats {
+        endpoints: summarize(&inner.endpoints),
+        models: summarize(&inner.models),
+        accounts: summarize(&inner.accounts),
+    }
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR_MS: u64 = MAX_BUCKETS as u64 * BUCKET_MS;
+
+    #[test]
+    fn parses_request_lines() {
+        for (line, method, path, status, duration_ms) in [
+            (
+                "[POST] /v1/messages 200 (1234ms)",
+                "POST",
+                "/v1/messages",
+                200,
+                1234,
+            ),
+            (
+                "[INFO] [POST] /v1/messages?beta=true 429 (87ms)",
+                "POST",
+                "/v1/messages",
+                429,
+                87,
+            ),
+            ("[GET] /v1/models 200 (3ms)", "GET", "/v1/models", 200, 3),
+            (
+                "2025-06-01T09:12:44.120Z [GET] /account-limits 500 (15002ms)",
+                "GET",
+                "/account-limits",
+                500,
+                15002,
+            ),
+        ] {
+            let parsed = parse_request_line(line).expect(line);
+            assert_eq!(parsed.method, method, "{line}");
+            assert_eq!(parsed.path, path, "{line}");
+            assert_eq!(parsed.status, status, "{line}");
+            assert_eq!(parsed.duration_ms, duration_ms, "{line}");
+        }
+    }
+
+    #[test]
+    fn skips_lines_that_are_not_finished_requests() {
+        for line in [
+            "[API] Request for model: claude-sonnet-4-5-thinking",
+            "[INFO] Using account: alice@example.com",
+            "POST /v1/messages 200 (12ms)",
+            "[POST] /v1/messages 200",
+            "[OPTIONS] /v1/messages 204 (1ms)",
+            "",
+        ] {
+            assert!(parse_request_line(line).is_none(), "{line}");
+        }
+    }
+
+    #[test]
+    fn classifies_engine_error_messages() {
+        for (line, class) in [
+            (
+                "[WARN] Request aborted by client",
+                Some(ErrorClass::ClientAbort),
+            ),
+            (
+                "[WARN] Client disconnected before the stream ended",
+                Some(ErrorClass::ClientAbort),
+            ),
+            (
+                "[ERROR] Upstream request failed: fetch failed (ECONNRESET)",
+                Some(ErrorClass::Network),
+            ),
+            ("[ERROR] Error: socket hang up", Some(ErrorClass::Network)),
+            (
+                "[ERROR] getaddrinfo ENOTFOUND cloudcode-pa.googleapis.com",
+                Some(ErrorClass::Network),
+            ),
+            (
+                "[WARN] RESOURCE_EXHAUSTED: Quota exceeded for gemini-3-pro-high",
+                Some(ErrorClass::RateLimit),
+            ),
+            (
+                "[WARN] Rate limit hit, retrying in 30s",
+                Some(ErrorClass::RateLimit),
+            ),
+            (
+                "[ERROR] Token refresh failed: invalid_grant",
+                Some(ErrorClass::Auth),
+            ),
+            ("[ERROR] 403 PERMISSION_DENIED", Some(ErrorClass::Auth)),
+            (
+                "[ERROR] Failed to convert request: tool_result without tool_use",
+                Some(ErrorClass::Translation),
+            ),
+            (
+                "[ERROR] TypeError: Cannot read properties of undefined (reading 'parts')",
+                Some(ErrorClass::Translation),
+            ),
+            ("[INFO] Server started on port 8080", None),
+            ("[POST] /v1/messages 200 (1234ms)", None),
+        ] {
+            assert!(ErrorClass::from_message(line) == class, "{line}");
+        }
+    }
+
+    #[test]
+    fn summary_leaves_out_buckets_older_than_an_hour() {
+        let mut series = Series::default();
+        let start = 1_700_000_000_000 - 1_700_000_000_000 % BUCKET_MS;
+        series.record(start, 100);
+        series.record(start + 30 * BUCKET_MS, 200);
+
+        let summary = series.summary(start + 30 * BUCKET_MS, "k");
+        assert_eq!(summary.count, 2);
+        let summary = series.summary(start + HOUR_MS + BUCKET_MS, "k");
+        assert_eq!(summary.count, 1);
+        assert_eq!(summary.series.len(), 1);
+        assert_eq!(summary.series[0].timestamp, start + 30 * BUCKET_MS);
+        assert_eq!(series.summary(start + 2 * HOUR_MS, "k").count, 0);
+    }
+
+    #[test]
+    fn record_drops_buckets_older_than_an_hour() {
+        let mut series = Series::default();
+        let start = 1_700_000_000_000 - 1_700_000_000_000 % BUCKET_MS;
+        series.record(start, 100);
+        series.record(start + BUCKET_MS, 150);
+        series.record(start + HOUR_MS + BUCKET_MS / 2, 200);
+
+        let starts: Vec<u64> = series.buckets.iter().map(|b| b.start_ms).collect();
+        assert_eq!(starts, [start + BUCKET_MS, start + HOUR_MS]);
+    }
+}
@@ -0,0 +1,346 @@
+// System tray icon and menu. The menu is rebuilt from app state whenever it changes.
+// The icon is a monochrome glyph that follows the OS theme; on macOS it is a
+// template image the menu bar tints itself.
+use tauri::{
+    image::Image,
+    include_image,
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    App, AppHandle, Emitter, Manager, Theme, Wry,
+};
+
+use crate::{accounts, credentials, i18n, lock, mappings, sidecar, traffic};
+
+const TRAY_ID: &str = "main";
+const TOOLTIP: &str = "Antigravity Claude Proxy";
+
+// Dark glyph for light menu bars and taskbars, light glyph for dark ones
+const ICON_FOR_LIGHT: Image<'static> = include_image!("icons/tray-light.png");
+const ICON_FOR_DARK: Image<'static> = include_image!("icons/tray-dark.png");
+
+pub fn create(app: &App) -> tauri::Result<()> {
+    let menu = build_menu(app.handle())?;
+    let theme = app
+        .get_webview_window("main")
+        .and_then(|w| w.theme().ok())
+        .unwrap_or(Theme::Dark);
+
+    // Create system tray icon
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon_for(theme))
+        .icon_as_template(cfg!(target_os = "macos"))
+        .tooltip(TOOLTIP)
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show" => show_main_window(app),
+            "open_logs" => navigate(app, "logs"),
+            "accounts" | "credentials" => navigate(app, "accounts"),
+            "restart_proxy" => {
+                if refused_while_locked(app) {
+                    return;
+                }
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = sidecar::restart(&app).await {
+                        log::warn!("restart from tray failed: {e}");
+                    }
+                });
+            }
+            "quit" => {
+                app.exit(0);
+            }
+            id if id.starts_with("account:") => {
+                if refused_while_locked(app) {
+                    return;
+                }
+                let email = Some(&id["account:".len()..]).filter(|e| *e != "auto");
+                let (app, email) = (app.clone(), email.map(str::to_string));
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = accounts::pin_account(app, email).await {
+                        log::warn!("account switch from tray failed: {e}");
+                    }
+                });
+            }
+            id if id.starts_with("mapping:") => {
+                if refused_while_locked(app) {
+                    return;
+                }
+                let name = Some(&id["mapping:".len()..]).filter(|n| !n.is_empty());
+                let (app, name) = (app.clone(), name.map(str::to_string));
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = mappings::apply_mapping_preset(app, name).await {
+                        log::warn!("mapping switch from tray failed: {e}");
+                    }
+                });
+            }
+            id => {
+                if let Some(timestamp) = id
+                    .strip_prefix("error:")
+                    .and_then(|t| t.parse::<u64>().ok())
+                {
+                    focus_log(app, timestamp);
+                }
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    Ok(())
+}
+
+// The tray stays usable while the app is locked, but only for things that change nothing
+fn refused_while_locked(app: &AppHandle) -> bool {
+    let locked = lock::is_locked(app);
+    if locked {
+        log::info!("tray action ignored while the app is locked");
+    }
+    locked
+}
+
+fn icon_for(theme: Theme) -> Image<'static> {
+    // Template images only use the alpha channel, so either variant would do
+    match theme {
+        Theme::Light => ICON_FOR_LIGHT,
+        _ => ICON_FOR_DARK,
+    }
+}
+
+/// Swap the icon variant and tell the frontend ("theme-changed") after the OS
+/// switched between light and dark
+pub fn on_theme_changed(app: &AppHandle, theme: Theme) {
+    let name = match theme {
+        Theme::Light => "light",
+        _ => "dark",
+    };
+    log::info!("OS theme changed to {name}");
+    let _ = app.emit("theme-changed", name);
+
+    // macOS tints template icons itself
+    if cfg!(target_os = "macos") {
+        return;
+    }
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Err(e) = tray.set_icon(Some(icon_for(theme))) {
+            log::warn!("failed to swap tray icon: {e}");
+        }
+    }
+}
+
+/// Rebuild the menu and tooltip after the state they show has changed
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    let expiring = credentials::warnings(app).len();
+    let tooltip = if expiring > 0 {
+        i18n::tf(
+            app,
+            "tray.tooltipCredentials",
+            &[
+                ("title", TOOLTIP.to_string()),
+                ("count", expiring.to_string()),
+            ],
+        )
+    } else {
+        TOOLTIP.to_string()
+    };
+
+    if let Err(e) = build_menu(app).and_then(|menu| tray.set_menu(Some(menu))) {
+        log::warn!("failed to rebuild tray menu: {e}");
+    }
+    let _ = tray.set_tooltip(Some(tooltip));
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+
+    // Badge-like warning at the top while any credential is about to expire
+    let expiring = credentials::warnings(app).len();
+    if expiring > 0 {
+        let text = i18n::tf(
+            app,
+            "tray.credentialsWarning",
+            &[("count", expiring.to_string())],
+        );
+        menu.append(&MenuItem::with_id(
+            app,
+            "credentials",
+            text,
+            true,
+            None::<&str>,
+        )?)?;
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    menu.append(&MenuItem::with_id(
+        app,
+        "show",
+        i18n::t(app, "tray.show"),
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "open_logs",
+        i18n::t(app, "tray.openLogs"),
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "accounts",
+        i18n::t(app, "tray.accounts"),
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "restart_proxy",
+        i18n::t(app, "tray.restartProxy"),
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&accounts_menu(app)?)?;
+    menu.append(&mappings_menu(app)?)?;
+    menu.append(&recent_errors_menu(app)?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "quit",
+        i18n::t(app, "tray.quit"),
+        true,
+        None::<&str>,
+    )?)?;
+
+    Ok(menu)
+}
+
+fn accounts_menu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let (accounts, pinned) = accounts::snapshot(app);
+    let submenu = Submenu::new(
+        app,
+        i18n::t(app, "tray.routeTrafficTo"),
+        !accounts.is_empty(),
+    )?;
+    submenu.append(&CheckMenuItem::with_id(
+        app,
+        "account:auto",
+        i18n::t(app, "tray.automaticRotation"),
+        true,
+        pinned.is_none(),
+        None::<&str>,
+    )?)?;
+    submenu.append(&PredefinedMenuItem::separator(app)?)?;
+    for account in accounts {
+        let checked = pinned.as_deref() == Some(account.email.as_str());
+        let label = if account.enabled || checked {
+            account.email.clone()
+        } else {
+            i18n::tf(
+                app,
+                "tray.accountDisabled",
+                &[("email", account.email.clone())],
+            )
+        };
+        submenu.append(&CheckMenuItem::with_id(
+            app,
+            format!("account:{}", account.email),
+            label,
+            true,
+            checked,
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+// "mapping:" alone clears the active preset, since preset names can't be empty
+fn mappings_menu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let presets = mappings::load(app);
+    let submenu = Submenu::new(
+        app,
+        i18n::t(app, "tray.modelMapping"),
+        presets.names().next().is_some(),
+    )?;
+    submenu.append(&CheckMenuItem::with_id(
+        app,
+        "mapping:",
+        i18n::t(app, "tray.noPreset"),
+        true,
+        presets.active().is_none(),
+        None::<&str>,
+    )?)?;
+    submenu.append(&PredefinedMenuItem::separator(app)?)?;
+    for name in presets.names() {
+        submenu.append(&CheckMenuItem::with_id(
+            app,
+            format!("mapping:{name}"),
+            name,
+            true,
+            presets.active() == Some(name.as_str()),
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+// Clicking an entry opens the log viewer scrolled to it
+fn recent_errors_menu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let submenu = Submenu::new(app, i18n::t(app, "tray.recentErrors"), true)?;
+    let errors = traffic::recent_errors(app);
+    if errors.is_empty() {
+        submenu.append(&MenuItem::new(
+            app,
+            i18n::t(app, "tray.noRecentErrors"),
+            false,
+            None::<&str>,
+        )?)?;
+    }
+    for error in errors {
+        let time = chrono::DateTime::from_timestamp_millis(error.timestamp as i64)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        submenu.append(&MenuItem::with_id(
+            app,
+            format!("error:{}", error.timestamp),
+            format!("{time}  {}", error.summary),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Show the window on a given page (handled by App.tsx)
+pub fn navigate(app: &AppHandle, page: &str) {
+    show_main_window(app);
+    let _ = app.emit("navigate", page);
+}
+
+/// Open the log viewer at the entry logged closest to `timestamp` (unix ms)
+pub fn focus_log(app: &AppHandle, timestamp: u64) {
+    show_main_window(app);
+    let _ = app.emit("focus-log", timestamp);
+}
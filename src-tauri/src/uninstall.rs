@@ -0,0 +1,100 @@
+// Remove everything the app leaves outside its install folder: the login item,
+// the Windows Firewall rule, keychain secrets and all local data. Runs from
+// Settings, or headless from the Windows uninstaller (see windows/hooks.nsh),
+// which starts the app with CLEANUP_FLAG and, unless the user ticked "delete
+// app data", KEEP_DATA_FLAG as well.
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::{firewall, lock, privacy, profiles, sidecar};
+
+pub const CLEANUP_FLAG: &str = "--cleanup-everything";
+pub const KEEP_DATA_FLAG: &str = "--keep-data";
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupReport {
+    /// Steps that removed something, e.g. "autostart"
+    removed: Vec<String>,
+    removed_files: usize,
+    errors: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn cleanup_everything(
+    app: AppHandle,
+    include_accounts: bool,
+) -> Result<CleanupReport, String> {
+    lock::ensure_unlocked(&app)?;
+    Ok(cleanup(&app, true, include_accounts).await)
+}
+
+/// Whether this launch came from the uninstaller, and if so whether app data stays
+pub fn requested() -> Option<bool> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .any(|a| a == CLEANUP_FLAG)
+        .then(|| !args.iter().any(|a| a == KEEP_DATA_FLAG))
+}
+
+/// Headless cleanup for the uninstaller. Saved accounts go too when app data does,
+/// since nothing is left that could use them.
+pub fn run_headless(app: &AppHandle, remove_data: bool) {
+    let report = tauri::async_runtime::block_on(cleanup(app, remove_data, remove_data));
+    for error in &report.errors {
+        log::error!("cleanup: {error}");
+    }
+}
+
+async fn cleanup(app: &AppHandle, remove_data: bool, include_accounts: bool) -> CleanupReport {
+    let mut report = CleanupReport::default();
+    let mut step = |name: &str, result: Result<bool, String>| match result {
+        Ok(true) => report.removed.push(name.to_string()),
+        Ok(false) => {}
+        Err(e) => report.errors.push(format!("{name}: {e}")),
+    };
+
+    // Engines hold their log and accounts files open
+    sidecar::kill_child(&app.state::<sidecar::SidecarState>());
+    profiles::stop_all(app).await;
+
+    let autolaunch = app.autolaunch();
+    step(
+        "autostart",
+        match autolaunch.is_enabled() {
+            Ok(true) => autolaunch
+                .disable()
+                .map(|_| true)
+                .map_err(|e| e.to_string()),
+            Ok(false) => Ok(false),
+            Err(e) => Err(e.to_string()),
+        },
+    );
+
+    let rule = match firewall::get_firewall_rule(app.clone()).await {
+        Ok(status) if status.present => firewall::remove_rule(app).await.map(|_| true),
+        Ok(_) => Ok(false),
+        Err(e) => Err(e),
+    };
+    step("firewallRule", rule);
+
+    if remove_data {
+        // Also removes the capture key, SMTP password and MCP token from the keychain
+        match privacy::wipe(app, include_accounts).await {
+            Ok(wipe) => {
+                report.removed_files = wipe.removed_files;
+                report.errors.extend(wipe.errors);
+            }
+            Err(e) => report.errors.push(format!("appData: {e}")),
+        }
+    }
+
+    log::warn!(
+        "cleanup: removed {:?} and {} file(s), {} error(s)",
+        report.removed,
+        report.removed_files,
+        report.errors.len()
+    );
+    report
+}
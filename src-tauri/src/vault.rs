@@ -0,0 +1,75 @@
+// Application-level AES-256-GCM for stored captures, since they can hold
+// proprietary code. The key lives in the OS keychain (Credential Manager,
+// Keychain, Secret Service) and is created on first use.
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+
+pub const KEYCHAIN_SERVICE: &str = "com.stronk.antigravity-claude-proxy";
+const KEYCHAIN_USER: &str = "capture-key";
+const NONCE_LEN: usize = 12;
+
+fn entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER).map_err(|e| e.to_string())
+}
+
+fn cipher() -> Result<Aes256Gcm, String> {
+    let entry = entry()?;
+    let key = match entry.get_password() {
+        Ok(encoded) => STANDARD.decode(encoded).map_err(|e| e.to_string())?,
+        Err(keyring::Error::NoEntry) => {
+            let key = Aes256Gcm::generate_key(OsRng);
+            entry
+                .set_password(&STANDARD.encode(key))
+                .map_err(|e| e.to_string())?;
+            log::info!("created capture encryption key in the OS keychain");
+            key.to_vec()
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    if key.len() != 32 {
+        return Err("Capture key in the keychain is corrupt".into());
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+/// Nonce followed by ciphertext
+pub fn encrypt(plain: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let mut out = nonce.to_vec();
+    out.extend(
+        cipher()?
+            .encrypt(&nonce, plain)
+            .map_err(|e| e.to_string())?,
+    );
+    Ok(out)
+}
+
+pub fn decrypt(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < NONCE_LEN {
+        return Err("Encrypted data is truncated".into());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher()?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Could not decrypt; the keychain key may have changed".to_string())
+}
+
+/// Random URL-safe string for shared secrets such as the MCP token
+pub fn random_token() -> String {
+    // A fresh key is 32 bytes straight from the OS generator
+    URL_SAFE_NO_PAD.encode(Aes256Gcm::generate_key(OsRng))
+}
+
+/// Remove the key; anything encrypted with it becomes unreadable
+pub fn delete_key() -> Result<(), String> {
+    match entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
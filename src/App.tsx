@@ -1,12 +1,15 @@
 import { useEffect, useState, lazy, Suspense } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import { Layout } from './components/layout';
 import { Dashboard, Accounts, Models, Logs, Settings } from './pages';
 import { useAppStore } from './stores/appStore';
 import { ToastContainer } from './components/ui/ToastContainer';
-import { stopProxy, startProxy } from './services/proxyService';
+import { stopProxy } from './services/proxyService';
 import { SetupWizard } from './components/SetupWizard';
 import { isSetupNeeded } from './services/claudeCliService';
 import { loadSettings } from './services/appStorageService';
+import { getLockStatus } from './services/lockService';
+import { LockScreen } from './components/LockScreen';
 
 // Lazy load Statistics page (contains recharts - 351KB)
 const Statistics = lazy(() => import('./pages/Statistics').then(m => ({ default: m.Statistics })));
@@ -19,8 +22,19 @@ const PageLoader = () => (
 );
 
 function App() {
-  const { currentPage, config, setConfig, setProxyStatus, setProxyStartTime, showSetupWizard, setShowSetupWizard } = useAppStore();
+  const { currentPage, setCurrentPage, setLogFocus, config, setConfig, setProxyStatus, setProxyStartTime, showSetupWizard, setShowSetupWizard } = useAppStore();
   const [isCheckingSetup, setIsCheckingSetup] = useState(true);
+  // null until the backend answers, so a locked window never flashes its content
+  const [isLocked, setIsLocked] = useState<boolean | null>(null);
+
+  // App lock: relocks whenever the window is hidden to the tray
+  useEffect(() => {
+    getLockStatus().then(status => setIsLocked(status.locked));
+    const unlisten = listen<boolean>('lock-changed', (event) => setIsLocked(event.payload));
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
 
   // Check if setup wizard should be shown and auto-start proxy if enabled
   useEffect(() => {
@@ -50,14 +64,11 @@ function App() {
           }
         });
 
-        // Auto-start proxy if setting is enabled and setup is not needed
+        // The Rust side already started the proxy at launch (it can't wait for an unlock)
         if (!needsSetup && settings.autoStartProxy) {
-          const success = await startProxy(config.proxy.port);
-          if (success) {
-            setProxyStatus({ running: true, port: config.proxy.port });
-            setProxyStartTime(Date.now());
-            await new Promise(resolve => setTimeout(resolve, 2000));
-          }
+          setProxyStatus({ running: true, port: settings.port || config.proxy.port });
+          setProxyStartTime(Date.now());
+          await new Promise(resolve => setTimeout(resolve, 2000));
         }
       } catch (e) {
         console.error('Failed to check setup status:', e);
@@ -68,6 +79,19 @@ function App() {
     checkSetup();
   }, []);
 
+  // Tray menu and notifications ask the window to open a specific page
+  useEffect(() => {
+    const unlisten = listen<string>('navigate', (event) => setCurrentPage(event.payload));
+    const unlistenFocus = listen<number>('focus-log', (event) => {
+      setLogFocus(event.payload);
+      setCurrentPage('logs');
+    });
+    return () => {
+      unlisten.then(fn => fn());
+      unlistenFocus.then(fn => fn());
+    };
+  }, []);
+
   // Cleanup proxy when app closes
   useEffect(() => {
     const handleBeforeUnload = () => {
@@ -105,7 +129,7 @@ function App() {
   };
 
   // Show nothing while checking setup status
-  if (isCheckingSetup) {
+  if (isCheckingSetup || isLocked === null) {
     return (
       <div className="h-screen bg-bg-primary flex items-center justify-center">
         <div className="w-8 h-8 border-2 border-accent-primary border-t-transparent rounded-full animate-spin" />
@@ -113,6 +137,10 @@ function App() {
     );
   }
 
+  if (isLocked) {
+    return <LockScreen onUnlock={() => setIsLocked(false)} />;
+  }
+
   const handleWizardComplete = () => {
     setShowSetupWizard(false);
     window.location.reload();
@@ -0,0 +1,58 @@
+import { useState } from 'react';
+import { Lock, Loader2 } from 'lucide-react';
+import { useTranslation } from '../i18n';
+import { unlock } from '../services/lockService';
+
+interface LockScreenProps {
+    onUnlock: () => void;
+}
+
+export function LockScreen({ onUnlock }: LockScreenProps) {
+    const { t } = useTranslation();
+    const [passphrase, setPassphrase] = useState('');
+    const [isChecking, setIsChecking] = useState(false);
+    const [error, setError] = useState(false);
+
+    const handleSubmit = async (e: React.FormEvent) => {
+        e.preventDefault();
+        setIsChecking(true);
+        const ok = await unlock(passphrase);
+        setIsChecking(false);
+        if (ok) {
+            setPassphrase('');
+            onUnlock();
+        } else {
+            setError(true);
+        }
+    };
+
+    return (
+        <div className="h-screen bg-bg-primary flex items-center justify-center">
+            <form onSubmit={handleSubmit} className="glass-card p-6 w-80 flex flex-col items-center gap-4">
+                <Lock size={32} className="text-accent-primary" />
+                <h1 className="text-lg font-semibold text-text-primary">{t('appLocked')}</h1>
+                <p className="text-xs text-text-muted text-center">{t('appLockedDesc')}</p>
+                <input
+                    type="password"
+                    autoFocus
+                    value={passphrase}
+                    onChange={(e) => {
+                        setPassphrase(e.target.value);
+                        setError(false);
+                    }}
+                    placeholder={t('passphrase')}
+                    className="input text-sm py-2 px-3 w-full"
+                />
+                {error && <p className="text-xs text-red-400">{t('wrongPassphrase')}</p>}
+                <button
+                    type="submit"
+                    disabled={isChecking || !passphrase}
+                    className="btn-primary text-sm w-full flex items-center justify-center gap-2"
+                >
+                    {isChecking && <Loader2 size={14} className="animate-spin" />}
+                    {t('unlock')}
+                </button>
+            </form>
+        </div>
+    );
+}
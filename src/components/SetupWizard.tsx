@@ -1,10 +1,11 @@
 import { useState, useEffect } from 'react';
-import { Sparkles, Zap, Check, AlertCircle, ArrowRight, ArrowLeft, Package, Terminal, Settings2, Loader2, Globe, Download, CheckCircle2 } from 'lucide-react';
-import { applyPreset, checkClaudeCli, installClaudeCli, settingsFileExists, setOnboardingComplete, type ClaudeCliCheckResult } from '../services/claudeCliService';
+import { Sparkles, Zap, Check, AlertCircle, ArrowRight, ArrowLeft, Package, Terminal, Settings2, Loader2, Globe, Download, CheckCircle2, UserCircle } from 'lucide-react';
+import { checkClaudeCli, installClaudeCli, settingsFileExists, setOnboardingComplete, type ClaudeCliCheckResult } from '../services/claudeCliService';
 import { checkInstallation, installPackage, type PackageStatus } from '../services/proxyPackageService';
+import { completeOnboardingStep, getOnboardingState } from '../services/onboardingService';
 import { useTranslation, useI18nStore } from '../i18n';
 
-type WizardStep = 'welcome' | 'proxy' | 'claude' | 'config' | 'completed';
+type WizardStep = 'welcome' | 'proxy' | 'claude' | 'account' | 'config' | 'completed';
 
 interface SetupWizardProps {
     onComplete: () => void;
@@ -35,6 +36,11 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
     const [isApplying, setIsApplying] = useState(false);
     const [error, setError] = useState<string | null>(null);
 
+    // Account step: port check, engine start and Google sign-in run in Rust
+    const [port, setPort] = useState(8080);
+    const [signedInAs, setSignedInAs] = useState<string | null>(null);
+    const [isSigningIn, setIsSigningIn] = useState(false);
+
     // Skip onboarding checkbox
     const [skipOnboarding, setSkipOnboarding] = useState(true);
 
@@ -52,6 +58,16 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
         }
     }, [currentStep]);
 
+    // Pick up progress from an earlier, interrupted run
+    useEffect(() => {
+        if (currentStep === 'account') {
+            getOnboardingState().then((state) => {
+                if (state?.port) setPort(state.port);
+                if (state?.account) setSignedInAs(state.account);
+            });
+        }
+    }, [currentStep]);
+
     // Check if config already exists when entering config step
     useEffect(() => {
         if (currentStep === 'config') {
@@ -123,6 +139,20 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
         }
     };
 
+    const handleSignIn = async () => {
+        setIsSigningIn(true);
+        setError(null);
+        try {
+            await completeOnboardingStep('port', { port });
+            const state = await completeOnboardingStep('signIn');
+            setSignedInAs(state.account);
+        } catch (e) {
+            setError((e as Error).message);
+        } finally {
+            setIsSigningIn(false);
+        }
+    };
+
     const handleApplyConfig = async () => {
         if (!selectedPreset) return;
 
@@ -130,21 +160,19 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
         setError(null);
 
         try {
-            const result = await applyPreset(selectedPreset);
-            if (result) {
-                // Save all settings including language and preset
-                const { updateSettings } = await import('../services/appStorageService');
-                await updateSettings({
-                    setupCompleted: true,
-                    language: selectedLanguage,
-                    lastSelectedPreset: selectedPreset
-                });
-                setCurrentStep('completed');
-            } else {
-                setError('Failed to write settings file.');
-            }
-        } catch {
-            setError('An error occurred while applying settings.');
+            // Writes ~/.claude/settings.json, then proves it with a real request
+            await completeOnboardingStep('clientConfig', { preset: selectedPreset });
+            await completeOnboardingStep('testRequest');
+            // Save all settings including language and preset
+            const { updateSettings } = await import('../services/appStorageService');
+            await updateSettings({
+                setupCompleted: true,
+                language: selectedLanguage,
+                lastSelectedPreset: selectedPreset
+            });
+            setCurrentStep('completed');
+        } catch (e) {
+            setError((e as Error).message);
         } finally {
             setIsApplying(false);
         }
@@ -167,7 +195,7 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
     };
 
     const goNext = async () => {
-        const steps: WizardStep[] = ['welcome', 'proxy', 'claude', 'config', 'completed'];
+        const steps: WizardStep[] = ['welcome', 'proxy', 'claude', 'account', 'config', 'completed'];
         const currentIndex = steps.indexOf(currentStep);
 
         // If leaving Claude step and skipOnboarding is checked, apply it
@@ -186,7 +214,7 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
     };
 
     const goBack = () => {
-        const steps: WizardStep[] = ['welcome', 'proxy', 'claude', 'config', 'completed'];
+        const steps: WizardStep[] = ['welcome', 'proxy', 'claude', 'account', 'config', 'completed'];
         const currentIndex = steps.indexOf(currentStep);
         if (currentIndex > 0) {
             setCurrentStep(steps[currentIndex - 1]);
@@ -194,7 +222,7 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
     };
 
     const getStepNumber = () => {
-        const steps: WizardStep[] = ['welcome', 'proxy', 'claude', 'config', 'completed'];
+        const steps: WizardStep[] = ['welcome', 'proxy', 'claude', 'account', 'config', 'completed'];
         return steps.indexOf(currentStep);
     };
 
@@ -205,7 +233,7 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
                 {/* Step Indicator */}
                 {currentStep !== 'welcome' && currentStep !== 'completed' && (
                     <div className="flex items-center justify-center gap-2 mb-6">
-                        {[1, 2, 3].map((step) => (
+                        {[1, 2, 3, 4].map((step) => (
                             <div key={step} className="flex items-center">
                                 <div className={`w-8 h-8 rounded-full flex items-center justify-center text-sm font-bold transition-all ${getStepNumber() >= step
                                     ? 'bg-accent-primary text-white'
@@ -213,7 +241,7 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
                                     }`}>
                                     {getStepNumber() > step ? <Check size={16} /> : step}
                                 </div>
-                                {step < 3 && (
+                                {step < 4 && (
                                     <div className={`w-12 h-0.5 mx-1 ${getStepNumber() > step ? 'bg-accent-primary' : 'bg-white/10'
                                         }`} />
                                 )}
@@ -463,6 +491,83 @@ export function SetupWizard({ onComplete, onSkip }: SetupWizardProps) {
                     </div>
                 )}
 
+                {/* Account Step */}
+                {currentStep === 'account' && (
+                    <div>
+                        <div className="text-center mb-6">
+                            <div className="w-16 h-16 rounded-xl bg-cyan-500/20 flex items-center justify-center mx-auto mb-4">
+                                <UserCircle size={32} className="text-cyan-400" />
+                            </div>
+                            <h2 className="text-xl font-bold text-text-primary mb-2">{t('onboardingAccountTitle')}</h2>
+                            <p className="text-sm text-text-secondary">{t('onboardingAccountDesc')}</p>
+                        </div>
+
+                        <div className="p-4 rounded-xl bg-white/5 border border-white/10 mb-6 space-y-4">
+                            <label className="flex items-center justify-between gap-3">
+                                <span className="text-sm text-text-secondary">{t('port')}</span>
+                                <input
+                                    type="number"
+                                    min={1024}
+                                    max={65535}
+                                    value={port}
+                                    disabled={isSigningIn || !!signedInAs}
+                                    onChange={(e) => setPort(Number(e.target.value))}
+                                    className="input w-28 text-sm"
+                                />
+                            </label>
+                            {signedInAs ? (
+                                <div className="flex items-center gap-3">
+                                    <CheckCircle2 size={20} className="text-green-400" />
+                                    <span className="text-green-400 font-medium">{t('onboardingSignedInAs')} {signedInAs}</span>
+                                </div>
+                            ) : (
+                                <button
+                                    onClick={handleSignIn}
+                                    disabled={isSigningIn}
+                                    className="w-full py-2.5 rounded-lg bg-cyan-500/20 text-cyan-400 hover:bg-cyan-500/30 transition-colors flex items-center justify-center gap-2"
+                                >
+                                    {isSigningIn ? (
+                                        <>
+                                            <Loader2 size={16} className="animate-spin" />
+                                            {t('completeOAuthInBrowser')}
+                                        </>
+                                    ) : (
+                                        <>
+                                            <UserCircle size={16} />
+                                            {t('onboardingSignIn')}
+                                        </>
+                                    )}
+                                </button>
+                            )}
+                        </div>
+
+                        {error && (
+                            <div className="flex items-center gap-2 p-3 rounded-lg bg-red-500/10 border border-red-500/20 mb-4">
+                                <AlertCircle size={16} className="text-red-400" />
+                                <p className="text-xs text-red-300">{error}</p>
+                            </div>
+                        )}
+
+                        <div className="flex items-center justify-between">
+                            <button onClick={goBack} className="flex items-center gap-2 text-text-muted hover:text-text-primary">
+                                <ArrowLeft size={16} />
+                                {t('back')}
+                            </button>
+                            <button
+                                onClick={goNext}
+                                disabled={!signedInAs}
+                                className={`flex items-center gap-2 px-6 py-2.5 rounded-lg font-medium ${signedInAs
+                                    ? 'bg-gradient-to-r from-accent-primary to-accent-secondary text-white hover:opacity-90'
+                                    : 'bg-white/10 text-text-muted cursor-not-allowed'
+                                    }`}
+                            >
+                                {t('next')}
+                                <ArrowRight size={16} />
+                            </button>
+                        </div>
+                    </div>
+                )}
+
                 {/* Config Step */}
                 {currentStep === 'config' && (
                     <div>
@@ -3,6 +3,8 @@
  * React hook for translations with separate locale files
  */
 
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
 import { en, tr } from './locales';
@@ -22,7 +24,11 @@ export const useI18nStore = create<I18nState>()(
     persist(
         (set) => ({
             language: 'en',
-            setLanguage: (language) => set({ language }),
+            setLanguage: (language) => {
+                set({ language });
+                // Tray menu and notifications are translated on the Rust side
+                invoke('set_locale', { locale: language }).catch(() => {});
+            },
         }),
         {
             name: 'antigravity-i18n',
@@ -30,6 +36,13 @@ export const useI18nStore = create<I18nState>()(
     )
 );
 
+// Language changed from another window or the shell
+listen<Language>('locale-changed', (event) => {
+    if (useI18nStore.getState().language !== event.payload) {
+        useI18nStore.setState({ language: event.payload });
+    }
+}).catch(() => {});
+
 /**
  * Translation hook
  * Usage: const { t, language, setLanguage } = useTranslation();
@@ -7,6 +7,7 @@ export const en = {
     dashboard: 'Dashboard',
     accounts: 'Accounts',
     models: 'Models',
+    staleMappings: 'These mappings point at models no account offers any more:',
     logs: 'Logs',
     settings: 'Settings',
     statistics: 'Statistics',
@@ -244,6 +245,8 @@ export const en = {
     last30d: 'Last 30D',
     allTime: 'All Time',
     todayRequests: 'Today',
+    estimatedApiCost: 'API Equivalent Cost',
+    unpricedModels: 'No price for',
     hourlyTrend: 'Hourly Trend',
     dailyUsage: 'Daily Usage',
     usagePercentage: 'Usage Rate',
@@ -296,6 +299,7 @@ export const en = {
 
     // Accounts Page Additional
     limited: 'LIMITED',
+    usableAgainIn: 'usable again in',
     fix: 'FIX',
     lastUsedPrefix: 'Last:',
     noAccountsFound: 'No accounts found',
@@ -351,6 +355,8 @@ export const en = {
     updating: 'Updating...',
     updateSuccess: 'Proxy updated!',
     checkingVersion: 'Checking version...',
+    networkReconnecting: 'Network changed, reconnecting...',
+    networkReconnected: 'Reconnected',
 
     // Bulk Account Operations
     reloadingAccounts: 'Reloading accounts...',
@@ -420,6 +426,10 @@ export const en = {
     chooseModelFamily: 'Choose your preferred model family',
     applying: 'Applying...',
     applyFinish: 'Apply & Finish',
+    onboardingAccountTitle: 'Google Account',
+    onboardingAccountDesc: 'Start the proxy and sign in with the account it should use',
+    onboardingSignIn: 'Sign in with Google',
+    onboardingSignedInAs: 'Signed in as',
 
     // New Settings Page (unique keys only, others reuse existing)
     generalSettings: 'General',
@@ -436,7 +446,103 @@ export const en = {
     autoStartProxy: 'Auto-Start Proxy',
     autoStartProxyDesc: 'Start proxy automatically when app opens',
     pollingIntervalDesc: 'How often to refresh statistics',
+    enginePriority: 'Process Priority',
+    enginePriorityDesc: 'CPU priority of the proxy engine. Below normal keeps foreground apps smooth during heavy streaming.',
+    priorityBelowNormal: 'Below normal',
+    priorityNormal: 'Normal',
+    priorityHigh: 'High',
+    idleSuspend: 'Idle Suspend (min)',
+    idleSuspendDesc: 'Pause background activity after this many minutes without requests (0 = off)',
+    activityBadge: 'Activity Badge',
+    activityBadgeDesc: 'Show the number of requests in progress on the dock or taskbar icon',
+    profile: 'Profile',
+    addProfile: 'Add Profile',
+    addProfileDesc: 'Run another proxy on its own port with separate accounts, e.g. to keep work and personal traffic apart. Also started with --profile <name>.',
+    deleteProfileConfirm: 'Stop and remove this profile? Its accounts stay on disk.',
+    syncFolder: 'Sync Folder',
+    syncFolderDesc: 'Share settings and mapping presets with your other machines through a synced folder (Dropbox, Syncthing, ...)',
+    syncNow: 'Sync Now',
+    syncDone: 'Settings synced',
+    syncConflicts: 'Some settings changed on two machines. The unused version was saved next to the synced files.',
+    emailAlerts: 'Email Alerts',
+    emailAlertsDesc: 'SMTP server and recipients (comma-separated) for proxy-down and failure alerts. Port, TLS mode and sender: smtpPort, smtpSecurity, smtpFrom in settings.json',
+    smtpLogin: 'SMTP Login',
+    smtpLoginDesc: 'The password is stored in the OS keychain',
+    username: 'Username',
+    password: 'Password',
+    sendTestEmail: 'Send Test',
+    testEmailSent: 'Test email sent',
+    scheduledJob: 'Scheduled Job',
+    lastRun: 'Last run',
+    nextRun: 'Next run',
+    runNow: 'Run Now',
+    privacySettings: 'Privacy',
+    appDataSize: 'App Data Size',
+    appDataSizeDesc: 'Logs, captures, reports and caches stored on this machine',
+    freeSpace: 'free',
+    cleanUp: 'Clean Up',
+    cleanedUp: 'Freed',
+    captureRetention: 'Capture Retention (days)',
+    captureRetentionDesc: 'Delete saved request captures after this many days',
+    logRetention: 'Log Retention (days)',
+    logRetentionDesc: 'Delete old log files after this many days',
+    storeBodies: 'Store Request Bodies',
+    storeBodiesDesc: 'When off, captures keep only headers and status, never prompts or responses. Requests sent with x-proxy-no-log: true are always kept that way.',
+    encryptCaptures: 'Encrypt Captures',
+    encryptCapturesDesc: 'Encrypt saved captures with a key stored in the system keychain',
+    appLock: 'App Lock',
+    appLockDesc: 'Ask for a passphrase before showing the window. The proxy keeps running while locked.',
+    currentPassphrase: 'Current',
+    newPassphrase: 'New passphrase',
+    removePassphrase: 'Remove',
+    savePassphrase: 'Save',
+    appLockEnabled: 'App lock enabled',
+    appLockDisabled: 'App lock removed',
+    appLocked: 'Locked',
+    appLockedDesc: 'Enter your passphrase to open the app. The proxy is still running.',
+    passphrase: 'Passphrase',
+    wrongPassphrase: 'Wrong passphrase',
+    unlock: 'Unlock',
+    wipeAllData: 'Wipe All Data',
+    wipeAllDataDesc: 'Securely delete captures, logs, caches and settings',
+    crashReports: 'Crash Reports',
+    crashReportsDesc: 'Save a report on this computer when the app crashes. Nothing is uploaded.',
+    crashReportsSaved: 'Saved reports',
+    interruptedRequests: 'Interrupted Requests',
+    interruptedByEngine: 'engine stopped',
+    interruptedByApp: 'app closed unexpectedly',
+    reportCrash: 'Report',
+    telemetry: 'Anonymous Usage Statistics',
+    telemetryDesc: 'Count feature use and error types (no emails, prompts or paths) to help prioritize fixes',
+    showTelemetryData: 'Show Data',
+    auditLog: 'Audit Log',
+    auditLogDesc: 'Record config changes, account switches and engine updates in a tamper-evident log',
+    verifyAuditLog: 'Verify',
+    auditLogIntact: 'Audit log intact',
+    auditLogBroken: 'Audit log broken at entry',
+    hideTelemetryData: 'Hide Data',
+    wipeAllDataConfirm: 'This permanently deletes all local app data. Continue?',
+    wipeAccountsConfirm: 'Also remove saved account credentials? (Cancel keeps them)',
+    wipeAllDataDone: 'All local data wiped',
+    wipeAllDataFailed: 'Some files could not be deleted',
+    cleanupEverything: 'Prepare for Uninstall',
+    cleanupEverythingDesc: 'Also remove the login item, firewall rule and keychain secrets, so nothing is left behind after uninstalling',
+    cleanupEverythingConfirm: 'This removes auto-start, the firewall rule, stored secrets and all local app data. Continue?',
+    cleanupEverythingDone: 'Everything removed. You can uninstall the app now.',
     portDesc: 'Local proxy server port',
+    lanAccess: 'LAN Access',
+    lanAccessDesc: 'Let other machines on your network use the proxy',
+    firewallRuleConfirm: 'Allow the proxy through Windows Firewall on private networks? Windows will ask for administrator rights.',
+    firewallRuleFailed: 'Could not change the firewall rule',
+    firewallRuleActive: 'Firewall rule active',
+    firewallRuleMissing: 'No firewall rule',
+    mcpServer: 'MCP Server',
+    mcpServerDesc: 'Let MCP-capable agents and editors check the proxy status, switch accounts and read usage. Only reachable from this computer.',
+    mcpCopyToken: 'Copy Token',
+    mcpTokenCopied: 'Token copied. Send it as "Authorization: Bearer <token>" from your MCP client.',
+    mcpResetToken: 'Reset Token',
+    mcpResetTokenConfirm: 'MCP clients using the current token will stop working until you give them the new one. Continue?',
+    mcpTokenFailed: 'Could not read the MCP token',
     logBufferDesc: 'Maximum log lines to keep in memory',
     maxRetriesDesc: 'Number of retry attempts on failure',
     retryBaseDelayDesc: 'Initial delay between retries',
@@ -469,9 +575,17 @@ export const en = {
     documentationDesc: 'View guides and API docs',
     sourceCode: 'Source Code',
     sourceCodeDesc: 'View on GitHub',
+    engineVersion: 'Proxy Engine',
+    previousVersion: 'Previous',
+    rollbackEngine: 'Roll Back',
+    rollbackEngineConfirm: 'Stop the proxy and reinstall',
+    rollbackEngineDone: 'Proxy engine rolled back to',
+    rollbackEngineFailed: 'Rollback failed, see logs for details',
 
     // Fallback notifications
     restartProxyForChanges: 'Restart proxy for changes to take effect',
+    applyingProxyChanges: 'Applying changes once requests in progress have finished...',
+    proxyChangesApplied: 'Proxy settings applied',
     fallbackTriggered: 'Model Fallback Activated',
     fallbackTriggeredDesc: 'Switched from {original} to {fallback} due to rate limits',
 
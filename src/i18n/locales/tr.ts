@@ -7,6 +7,7 @@ export const tr = {
     dashboard: 'Panel',
     accounts: 'Hesaplar',
     models: 'Modeller',
+    staleMappings: 'Bu eşlemeler artık hiçbir hesabın sunmadığı modellere yönlendiriyor:',
     logs: 'Günlükler',
     settings: 'Ayarlar',
     statistics: 'İstatistikler',
@@ -244,6 +245,8 @@ export const tr = {
     last30d: 'Son 30G',
     allTime: 'Tüm Zamanlar',
     todayRequests: 'Bugün',
+    estimatedApiCost: 'API Eşdeğer Maliyeti',
+    unpricedModels: 'Fiyatı olmayan',
     hourlyTrend: 'Saatlik Trend',
     dailyUsage: 'Günlük Kullanım',
     usagePercentage: 'Kullanım Oranı',
@@ -296,6 +299,7 @@ export const tr = {
 
     // Accounts Page Additional
     limited: 'SINIRLI',
+    usableAgainIn: 'tekrar kullanılabilir:',
     fix: 'ONAR',
     lastUsedPrefix: 'Son:',
     noAccountsFound: 'Hesap bulunamadı',
@@ -351,6 +355,8 @@ export const tr = {
     updating: 'Güncelleniyor...',
     updateSuccess: 'Proxy güncellendi!',
     checkingVersion: 'Sürüm kontrol ediliyor...',
+    networkReconnecting: 'Ağ değişti, yeniden bağlanılıyor...',
+    networkReconnected: 'Yeniden bağlandı',
 
     // Bulk Account Operations
     reloadingAccounts: 'Hesaplar yeniden yükleniyor...',
@@ -420,6 +426,10 @@ export const tr = {
     chooseModelFamily: 'Tercih ettiğiniz model ailesini seçin',
     applying: 'Uygulanıyor...',
     applyFinish: 'Uygula ve Bitir',
+    onboardingAccountTitle: 'Google Hesabı',
+    onboardingAccountDesc: 'Proxy\'yi başlatın ve kullanacağı hesapla giriş yapın',
+    onboardingSignIn: 'Google ile giriş yap',
+    onboardingSignedInAs: 'Giriş yapıldı:',
 
     // New Settings Page
     generalSettings: 'Genel',
@@ -434,7 +444,103 @@ export const tr = {
     autoStartProxy: 'Proxy\'yi Otomatik Başlat',
     autoStartProxyDesc: 'Uygulama açıldığında proxy\'yi otomatik başlat',
     pollingIntervalDesc: 'İstatistikleri ne sıklıkla yenile',
+    enginePriority: 'İşlem Önceliği',
+    enginePriorityDesc: 'Proxy motorunun CPU önceliği. Normalin altı, yoğun akış sırasında ön plandaki uygulamaların akıcı kalmasını sağlar.',
+    priorityBelowNormal: 'Normalin altı',
+    priorityNormal: 'Normal',
+    priorityHigh: 'Yüksek',
+    idleSuspend: 'Boşta Askıya Alma (dk)',
+    idleSuspendDesc: 'Bu kadar dakika istek gelmezse arka plan etkinliğini duraklat (0 = kapalı)',
+    activityBadge: 'Etkinlik Rozeti',
+    activityBadgeDesc: 'Devam eden istek sayısını dock veya görev çubuğu simgesinde göster',
+    profile: 'Profil',
+    addProfile: 'Profil Ekle',
+    addProfileDesc: 'Ayrı hesaplarla kendi portunda başka bir proxy çalıştırın; örneğin iş ve kişisel trafiği ayırmak için. --profile <ad> ile de başlatılır.',
+    deleteProfileConfirm: 'Bu profil durdurulup kaldırılsın mı? Hesapları diskte kalır.',
+    syncFolder: 'Senkronizasyon Klasörü',
+    syncFolderDesc: 'Ayarları ve model eşleme ön ayarlarını senkronize edilen bir klasör (Dropbox, Syncthing, ...) üzerinden diğer makinelerinizle paylaşın',
+    syncNow: 'Şimdi Senkronize Et',
+    syncDone: 'Ayarlar senkronize edildi',
+    syncConflicts: 'Bazı ayarlar iki makinede değiştirildi. Kullanılmayan sürüm senkronize dosyaların yanına kaydedildi.',
+    emailAlerts: 'E-posta Uyarıları',
+    emailAlertsDesc: 'Proxy kapalı ve hata uyarıları için SMTP sunucusu ve alıcılar (virgülle ayrılmış). Port, TLS modu ve gönderen: settings.json içinde smtpPort, smtpSecurity, smtpFrom',
+    smtpLogin: 'SMTP Girişi',
+    smtpLoginDesc: 'Parola işletim sisteminin anahtar zincirinde saklanır',
+    username: 'Kullanıcı adı',
+    password: 'Parola',
+    sendTestEmail: 'Test Gönder',
+    testEmailSent: 'Test e-postası gönderildi',
+    scheduledJob: 'Zamanlanmış Görev',
+    lastRun: 'Son çalışma',
+    nextRun: 'Sonraki çalışma',
+    runNow: 'Şimdi Çalıştır',
+    privacySettings: 'Gizlilik',
+    appDataSize: 'Uygulama Verisi Boyutu',
+    appDataSizeDesc: 'Bu makinede saklanan günlükler, kayıtlar, raporlar ve önbellekler',
+    freeSpace: 'boş',
+    cleanUp: 'Temizle',
+    cleanedUp: 'Boşaltılan',
+    captureRetention: 'Kayıt Saklama Süresi (gün)',
+    captureRetentionDesc: 'Kaydedilen istek kayıtlarını bu kadar gün sonra sil',
+    logRetention: 'Log Saklama Süresi (gün)',
+    logRetentionDesc: 'Eski log dosyalarını bu kadar gün sonra sil',
+    storeBodies: 'İstek İçeriklerini Sakla',
+    storeBodiesDesc: 'Kapalıyken kayıtlar yalnızca başlık ve durum kodunu tutar, istem veya yanıtları asla. x-proxy-no-log: true başlığıyla gönderilen istekler her zaman bu şekilde kaydedilir.',
+    encryptCaptures: 'Kayıtları Şifrele',
+    encryptCapturesDesc: 'Kaydedilen istekleri sistem anahtar zincirinde tutulan bir anahtarla şifrele',
+    appLock: 'Uygulama Kilidi',
+    appLockDesc: 'Pencereyi göstermeden önce parola iste. Kilitliyken proxy çalışmaya devam eder.',
+    currentPassphrase: 'Mevcut',
+    newPassphrase: 'Yeni parola',
+    removePassphrase: 'Kaldır',
+    savePassphrase: 'Kaydet',
+    appLockEnabled: 'Uygulama kilidi etkinleştirildi',
+    appLockDisabled: 'Uygulama kilidi kaldırıldı',
+    appLocked: 'Kilitli',
+    appLockedDesc: 'Uygulamayı açmak için parolanızı girin. Proxy hâlâ çalışıyor.',
+    passphrase: 'Parola',
+    wrongPassphrase: 'Yanlış parola',
+    unlock: 'Kilidi Aç',
+    wipeAllData: 'Tüm Verileri Sil',
+    wipeAllDataDesc: 'Kayıtları, logları, önbelleği ve ayarları güvenli şekilde sil',
+    crashReports: 'Çökme Raporları',
+    crashReportsDesc: 'Uygulama çöktüğünde bu bilgisayara bir rapor kaydet. Hiçbir şey yüklenmez.',
+    crashReportsSaved: 'Kayıtlı raporlar',
+    interruptedRequests: 'Yarıda Kesilen İstekler',
+    interruptedByEngine: 'motor durdu',
+    interruptedByApp: 'uygulama beklenmedik şekilde kapandı',
+    auditLog: 'Denetim Kaydı',
+    auditLogDesc: 'Yapılandırma değişikliklerini, hesap geçişlerini ve motor güncellemelerini değiştirilemez bir kayıtta tut',
+    verifyAuditLog: 'Doğrula',
+    auditLogIntact: 'Denetim kaydı sağlam',
+    auditLogBroken: 'Denetim kaydı şu kayıtta bozuk',
+    reportCrash: 'Bildir',
+    telemetry: 'Anonim Kullanım İstatistikleri',
+    telemetryDesc: 'Özellik kullanımını ve hata türlerini say (e-posta, istem veya yol yok), düzeltmeleri önceliklendirmeye yardımcı olur',
+    showTelemetryData: 'Verileri Göster',
+    hideTelemetryData: 'Verileri Gizle',
+    wipeAllDataConfirm: 'Bu işlem tüm yerel uygulama verilerini kalıcı olarak siler. Devam edilsin mi?',
+    wipeAccountsConfirm: 'Kayıtlı hesap kimlik bilgileri de silinsin mi? (İptal bunları korur)',
+    wipeAllDataDone: 'Tüm yerel veriler silindi',
+    wipeAllDataFailed: 'Bazı dosyalar silinemedi',
+    cleanupEverything: 'Kaldırmaya Hazırla',
+    cleanupEverythingDesc: 'Oturum açma öğesini, güvenlik duvarı kuralını ve anahtarlık sırlarını da kaldırır; kaldırma sonrası geride hiçbir şey kalmaz',
+    cleanupEverythingConfirm: 'Bu işlem otomatik başlatmayı, güvenlik duvarı kuralını, saklanan sırları ve tüm yerel uygulama verilerini kaldırır. Devam edilsin mi?',
+    cleanupEverythingDone: 'Her şey kaldırıldı. Uygulamayı artık kaldırabilirsiniz.',
     portDesc: 'Yerel proxy sunucu portu',
+    lanAccess: 'LAN Erişimi',
+    lanAccessDesc: 'Ağınızdaki diğer makinelerin proxy\'yi kullanmasına izin ver',
+    firewallRuleConfirm: 'Proxy\'ye özel ağlarda Windows Güvenlik Duvarı üzerinden izin verilsin mi? Windows yönetici izni isteyecek.',
+    firewallRuleFailed: 'Güvenlik duvarı kuralı değiştirilemedi',
+    firewallRuleActive: 'Güvenlik duvarı kuralı etkin',
+    firewallRuleMissing: 'Güvenlik duvarı kuralı yok',
+    mcpServer: 'MCP Sunucusu',
+    mcpServerDesc: 'MCP destekleyen ajanların ve editörlerin proxy durumunu görmesine, hesap değiştirmesine ve kullanımı okumasına izin ver. Yalnızca bu bilgisayardan erişilebilir.',
+    mcpCopyToken: 'Tokeni Kopyala',
+    mcpTokenCopied: 'Token kopyalandı. MCP istemcinizden "Authorization: Bearer <token>" olarak gönderin.',
+    mcpResetToken: 'Tokeni Sıfırla',
+    mcpResetTokenConfirm: 'Mevcut tokeni kullanan MCP istemcileri, yenisini verene kadar çalışmayacak. Devam edilsin mi?',
+    mcpTokenFailed: 'MCP tokeni okunamadı',
     logBufferDesc: 'Hafızada tutulacak maksimum günlük satırı',
     maxRetriesDesc: 'Başarısızlık durumunda tekrar deneme sayısı',
     retryBaseDelayDesc: 'Denemeler arası başlangıç gecikmesi',
@@ -467,9 +573,17 @@ export const tr = {
     documentationDesc: 'Kılavuzları ve API belgelerini görüntüle',
     sourceCode: 'Kaynak Kodu',
     sourceCodeDesc: 'GitHub\'da görüntüle',
+    engineVersion: 'Proxy Motoru',
+    previousVersion: 'Önceki',
+    rollbackEngine: 'Geri Al',
+    rollbackEngineConfirm: 'Proxy durdurulup yeniden kurulsun mu:',
+    rollbackEngineDone: 'Proxy motoru geri alındı:',
+    rollbackEngineFailed: 'Geri alma başarısız, ayrıntılar için loglara bakın',
 
     // Fallback notifications
     restartProxyForChanges: 'Değişikliklerin geçerli olması için proxy\'yi yeniden başlatın',
+    applyingProxyChanges: 'Değişiklikler, devam eden istekler bittiğinde uygulanıyor...',
+    proxyChangesApplied: 'Proxy ayarları uygulandı',
     fallbackTriggered: 'Model Yedekleme Aktif',
     fallbackTriggeredDesc: 'Hız sınırı nedeniyle {original} modelinden {fallback} modeline geçildi',
 
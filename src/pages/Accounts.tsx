@@ -4,7 +4,8 @@ import type { Account } from '../types';
 import { toast } from '../stores/toastStore';
 import { useTranslation } from '../i18n';
 import { useAppStore } from '../stores/appStore';
-import { toggleAccountEnabled, refreshAccount, deleteAccount as deleteAccountApi, reloadAccounts, getOAuthUrl, getAccountLimits, getProxyStatus, refreshAllTokens } from '../services/proxyService';
+import { toggleAccountEnabled, refreshAccount, deleteAccount as deleteAccountApi, reloadAccounts, getOAuthUrl, getAccountLimits, getProxyStatus, refreshAllTokens, getQuotaResets, type QuotaReset } from '../services/proxyService';
+import { listen } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/plugin-shell';
 import { MaskedEmail } from '../components/MaskedEmail';
 
@@ -21,6 +22,19 @@ export function Accounts() {
     const [isProxyRunning, setIsProxyRunning] = useState(false);
     const [sortField, setSortField] = useState<'email' | 'tier' | 'health' | 'lastUsed'>('email');
     const [sortOrder, setSortOrder] = useState<'asc' | 'desc'>('asc');
+    const [quotaResets, setQuotaResets] = useState<QuotaReset[]>([]);
+    const [now, setNow] = useState(Date.now());
+
+    // Reset times come from the backend; the countdown ticks locally
+    useEffect(() => {
+        getQuotaResets().then(setQuotaResets);
+        const unlisten = listen<QuotaReset[]>('quota-resets', (event) => setQuotaResets(event.payload));
+        const timer = setInterval(() => setNow(Date.now()), 1000);
+        return () => {
+            unlisten.then(fn => fn());
+            clearInterval(timer);
+        };
+    }, []);
 
     // Check proxy status when modal opens
     useEffect(() => {
@@ -471,22 +485,22 @@ export function Accounts() {
                                                         account.status === 'inactive' ? t('inactive').toUpperCase() :
                                                             t('error').toUpperCase()}
                                             </span>
-                                            {/* Rate Limit Countdown */}
-                                            {account.modelRateLimits && (() => {
-                                                const activeLimit = Object.entries(account.modelRateLimits)
-                                                    .filter(([_, limit]) => limit.isRateLimited && limit.resetTime > Date.now())
-                                                    .sort((a, b) => a[1].resetTime - b[1].resetTime)[0];
-                                                if (activeLimit) {
-                                                    const remainingMs = activeLimit[1].resetTime - Date.now();
-                                                    const mins = Math.floor(remainingMs / 60000);
-                                                    const secs = Math.floor((remainingMs % 60000) / 1000);
-                                                    return (
-                                                        <div className="text-[9px] text-yellow-400 font-mono animate-pulse">
-                                                            ⏳ {mins}m {secs}s
-                                                        </div>
-                                                    );
-                                                }
-                                                return null;
+                                            {/* Quota / Rate Limit Countdown */}
+                                            {(() => {
+                                                const resets = quotaResets.filter(r => r.email === account.email && r.resetAt > now);
+                                                if (resets.length === 0) return null;
+                                                const remainingMs = resets[0].resetAt - now;
+                                                const hours = Math.floor(remainingMs / 3600000);
+                                                const mins = Math.floor((remainingMs % 3600000) / 60000);
+                                                const secs = Math.floor((remainingMs % 60000) / 1000);
+                                                return (
+                                                    <div
+                                                        className="text-[9px] text-yellow-400 font-mono animate-pulse"
+                                                        title={resets.map(r => `${r.model}: ${new Date(r.resetAt).toLocaleTimeString()}`).join('\n')}
+                                                    >
+                                                        ⏳ {t('usableAgainIn')} {hours > 0 ? `${hours}h ${mins}m` : `${mins}m ${secs}s`}
+                                                    </div>
+                                                );
                                             })()}
                                         </div>
                                     </div>
@@ -1,4 +1,5 @@
 import { useState, useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
 import { startProxy, stopProxy, getProxyStatus, getAccountLimits, subscribeToLogStream, type LogEntry as ProxyLogEntry } from '../services/proxyService';
 import type { ProxyStatus } from '../types';
 import { Power, Play, X, Clock, Users, Database, Zap, AlertTriangle, RefreshCw, Wifi, WifiOff, Download, ArrowUpCircle, Copy, Check, ChevronDown, XCircle, AlertCircle } from 'lucide-react';
@@ -276,6 +277,44 @@ export function Dashboard() {
     // Ref to track if we're in the middle of stopping (to prevent polling race condition)
     const isStoppingRef = useRef(false);
 
+    // Polls are held while the backend re-validates after system sleep or a network switch;
+    // bumping resumeCount restarts them once it's done
+    const isResumingRef = useRef(false);
+    const isIdleRef = useRef(false);
+    const [resumeCount, setResumeCount] = useState(0);
+
+    useEffect(() => {
+        const unlistenResume = listen('power-resume', () => {
+            isResumingRef.current = true;
+        });
+        const unlistenReady = listen('power-ready', () => {
+            isResumingRef.current = false;
+            setResumeCount(count => count + 1);
+        });
+        // Same pause while the backend reconnects after a Wi-Fi/VPN switch
+        const unlistenNetwork = listen('network-change', () => {
+            isResumingRef.current = true;
+            toast.info(t('networkReconnecting'));
+        });
+        const unlistenNetworkReady = listen<{ upstreamOk: boolean }>('network-ready', (event) => {
+            isResumingRef.current = false;
+            setResumeCount(count => count + 1);
+            if (event.payload.upstreamOk) toast.success(t('networkReconnected'));
+        });
+        // Nothing to refresh while idle; a new request brings polling back immediately
+        const unlistenIdle = listen<boolean>('idle-changed', (event) => {
+            isIdleRef.current = event.payload;
+            if (!event.payload) setResumeCount(count => count + 1);
+        });
+        return () => {
+            unlistenIdle.then(fn => fn());
+            unlistenResume.then(fn => fn());
+            unlistenReady.then(fn => fn());
+            unlistenNetwork.then(fn => fn());
+            unlistenNetworkReady.then(fn => fn());
+        };
+    }, [t]);
+
     // Poll proxy status and data - only poll when proxy is supposed to be running
     useEffect(() => {
         if (!proxyPackage.isInstalled) return;
@@ -286,6 +325,14 @@ export function Dashboard() {
                 console.debug('[Dashboard] Skipping poll - stop in progress');
                 return;
             }
+            if (isResumingRef.current) {
+                console.debug('[Dashboard] Skipping poll - waking from sleep');
+                return;
+            }
+            if (isIdleRef.current) {
+                console.debug('[Dashboard] Skipping poll - idle');
+                return;
+            }
 
             const status = await getProxyStatus(config.proxy.port);
 
@@ -329,7 +376,7 @@ export function Dashboard() {
             clearTimeout(initialDelay);
             if (interval) clearInterval(interval);
         };
-    }, [proxyPackage.isInstalled, proxyStatus.running, config.proxy.port, config.app.pollingInterval]);
+    }, [proxyPackage.isInstalled, proxyStatus.running, config.proxy.port, config.app.pollingInterval, resumeCount]);
 
     // Calculate real stats from accounts data
     const realAccountCount = accounts.length;
@@ -9,12 +9,13 @@ import { formatLogMessage } from '../utils/logFormatter';
 export function Logs() {
     const { t } = useTranslation();
     const { language } = useI18nStore();
-    const { proxyStatus, config } = useAppStore();
+    const { proxyStatus, config, logFocus, setLogFocus } = useAppStore();
 
     const [humanReadable, setHumanReadable] = useState(true);
     const [autoScroll, setAutoScroll] = useState(true);
     const [searchQuery, setSearchQuery] = useState('');
     const [terminalLogs, setTerminalLogs] = useState<ProxyLogEntry[]>([]);
+    const [focusedTimestamp, setFocusedTimestamp] = useState<string | null>(null);
     const terminalRef = useRef<HTMLDivElement>(null);
     const eventSourceRef = useRef<EventSource | null>(null);
 
@@ -131,6 +132,29 @@ export function Logs() {
         return filterKey ? filters[filterKey] : true;
     });
 
+    // Jump to an entry picked from the tray's recent errors: the closest
+    // warning/error in time, or the closest line of any level
+    useEffect(() => {
+        if (logFocus === null || filteredLogs.length === 0) return;
+
+        const visible = filteredLogs.slice(-500);
+        const distance = (log: ProxyLogEntry) => Math.abs(new Date(log.timestamp).getTime() - logFocus);
+        const closest = (logs: ProxyLogEntry[]) =>
+            logs.reduce<ProxyLogEntry | null>((best, log) => (!best || distance(log) < distance(best) ? log : best), null);
+        const problems = visible.filter(log => ['warn', 'warning', 'error'].includes(log.level?.toLowerCase()));
+        const target = closest(problems) ?? closest(visible);
+        if (!target) return;
+
+        setAutoScroll(false);
+        setFocusedTimestamp(target.timestamp);
+        setLogFocus(null);
+        requestAnimationFrame(() => {
+            terminalRef.current
+                ?.querySelector(`[data-log-ts="${CSS.escape(target.timestamp)}"]`)
+                ?.scrollIntoView({ block: 'center' });
+        });
+    }, [logFocus, filteredLogs.length]);
+
     const handleClear = () => {
         setTerminalLogs([]);
         toast.info(t('logsCleared'));
@@ -307,7 +331,7 @@ export function Logs() {
                                         gray: 'text-text-muted'
                                     };
                                     return (
-                                        <div key={i} className={`py-1 px-2 -mx-2 rounded hover:bg-white/5 flex items-center gap-2 ${colorClasses[formatted.color]}`}>
+                                        <div key={i} data-log-ts={log.timestamp} className={`py-1 px-2 -mx-2 rounded hover:bg-white/5 flex items-center gap-2 ${colorClasses[formatted.color]} ${log.timestamp === focusedTimestamp ? 'bg-white/10' : ''}`}>
                                             {/* Brand logos as custom SVGs, everything else as Lucide icons */}
                                             {formatted.icon === '🟣' ? (
                                                 <img src="/claude-color.svg" alt="Claude" className="w-4 h-4" />
@@ -347,7 +371,7 @@ export function Logs() {
                                     );
                                 }
                                 return (
-                                    <div key={i} className={`py-0.5 ${getTerminalLineColor(log.level)} hover:bg-white/5`}>
+                                    <div key={i} data-log-ts={log.timestamp} className={`py-0.5 ${getTerminalLineColor(log.level)} hover:bg-white/5 ${log.timestamp === focusedTimestamp ? 'bg-white/10' : ''}`}>
                                         <span className="text-text-muted">[{formatLogTimestamp(log.timestamp)}]</span>
                                         {' '}
                                         <span className={getTerminalLineColor(log.level)}>[{log.level.toUpperCase()}]</span>
@@ -1,9 +1,11 @@
 import { useState, useMemo, useEffect, useCallback } from 'react';
-import { Search, Eye, EyeOff, Bookmark } from 'lucide-react';
+import { Search, Eye, EyeOff, Bookmark, AlertTriangle } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
 import { toast } from '../stores/toastStore';
 import { useTranslation } from '../i18n';
 import { useAppStore } from '../stores/appStore';
 import { getModelConfigs, updateModelConfig as updateModelConfigAPI, type ModelConfig } from '../services/proxyService';
+import { listUpstreamModels, type ModelCatalog, type StaleMapping } from '../services/mappingService';
 import { ModelIcon } from '../components/ModelIcon';
 import { MaskedEmail } from '../components/MaskedEmail';
 
@@ -153,6 +155,18 @@ export function Models() {
         loadModelConfigs();
     }, [loadModelConfigs]);
 
+    // Mappings whose target no account offers any more, from the Rust catalog sync
+    const [staleMappings, setStaleMappings] = useState<StaleMapping[]>([]);
+    useEffect(() => {
+        listUpstreamModels().then((catalog) => setStaleMappings(catalog?.staleMappings ?? []));
+        const unlisten = listen<ModelCatalog>('model-catalog', (event) => {
+            setStaleMappings(event.payload.staleMappings);
+        });
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, []);
+
     // Compute global models from accounts data
     const globalModels = useMemo<GlobalModel[]>(() => {
         const modelMap = new Map<string, {
@@ -384,6 +398,20 @@ export function Models() {
 
     return (
         <div className="h-full flex flex-col animate-fade-in">
+            {staleMappings.length > 0 && (
+                <div className="flex items-start gap-2 p-3 mb-3 rounded-lg bg-yellow-500/10 border border-yellow-500/20 shrink-0">
+                    <AlertTriangle size={16} className="text-yellow-400 shrink-0 mt-0.5" />
+                    <div className="text-xs text-yellow-300">
+                        <p className="font-medium mb-1">{t('staleMappings')}</p>
+                        {staleMappings.map((m) => (
+                            <p key={`${m.preset ?? ''}:${m.model}`} className="font-mono">
+                                {m.preset ? `[${m.preset}] ` : ''}{m.model} → {m.target}
+                            </p>
+                        ))}
+                    </div>
+                </div>
+            )}
+
             {/* Header */}
             <div className="flex items-center justify-between mb-3 shrink-0">
                 <div className="flex items-center gap-3">
@@ -1,5 +1,6 @@
 import { useState, useEffect, useCallback } from 'react';
-import { Globe, Zap, Server, Settings as SettingsIcon, Info, Save, RotateCcw, Bookmark, Trash2, ExternalLink, Github, Terminal, Monitor, RefreshCw } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
+import { Globe, Zap, Server, Settings as SettingsIcon, Info, Save, RotateCcw, Bookmark, Trash2, ExternalLink, Github, Terminal, Monitor, RefreshCw, Shield } from 'lucide-react';
 import { useAppStore } from '../stores/appStore';
 import { toast } from '../stores/toastStore';
 import { useTranslation, type TranslationKey } from '../i18n';
@@ -13,12 +14,29 @@ import {
     getAvailableModels,
     getServerConfig,
     updateServerConfig,
-    setClaudeOnboardingComplete
+    setClaudeOnboardingComplete,
+    getFirewallRule,
+    setFirewallRule,
+    setEnginePriority,
+    reconfigureProxy,
+    setMcpPort,
+    getMcpToken,
+    resetMcpToken,
+    listProfiles,
+    saveProfile,
+    deleteProfile,
+    setProfileRunning,
+    type EnginePriority,
+    type FirewallStatus,
+    type ProfileStatus
 } from '../services/proxyService';
 import { setAutoStart, getAutoStartStatus } from '../services/autostartService';
-import { loadSettings, saveSettings, updateSettings } from '../services/appStorageService';
+import { cleanAppData, cleanupEverything, getDiskUsage, loadSettings, saveSettings, setSyncFolder, syncNow, updateSettings, wipeAllData, type DiskUsage } from '../services/appStorageService';
+import { getLockStatus, setLockPassphrase } from '../services/lockService';
 import { getKiroStatus, patchKiro, restoreKiro, startKiroServer, stopKiroServer, type KiroStatus } from '../services/kiroService';
 import { checkForUpdates, getCurrentVersion, type UpdateInfo } from '../services/updateService';
+import { getEngineVersion, rollbackPackage, type EngineVersionInfo } from '../services/proxyPackageService';
+import { clearInterruptedRequests, deleteCrashReports, getInterruptedRequests, getTelemetryQueue, listCrashReports, listJobs, refreshTelemetry, reportCrash, runJobNow, sendTestEmail, setJobSchedule, setSmtpPassword, verifyAuditLog, type CrashSummary, type InterruptedRequest, type JobInfo, type TelemetryQueue } from '../services/diagnosticsService';
 import { Accordion } from '../components/ui/Accordion';
 import { SettingRow, ToggleSwitch } from '../components/ui/SettingRow';
 import type { Preset } from '../types';
@@ -78,6 +96,8 @@ const addSuffixIfGemini = (model?: string, enabled?: boolean) => {
     return stripped;
 };
 
+const formatMb = (bytes: number) => `${(bytes / 1024 / 1024).toFixed(bytes < 10 * 1024 * 1024 ? 1 : 0)} MB`;
+
 export function Settings() {
     const { t, language, setLanguage } = useTranslation();
     const { config, setConfig, proxyStatus } = useAppStore();
@@ -112,7 +132,45 @@ export function Settings() {
     const [persistentSessions, setPersistentSessions] = useState(true);
     const [defaultCooldown, setDefaultCooldown] = useState(10);
     const [maxWaitBeforeError, setMaxWaitBeforeError] = useState(120);
+    const [idleSuspendMinutes, setIdleSuspendMinutes] = useState(0);
+    const [enginePriority, setEnginePriorityValue] = useState<EnginePriority>('normal');
+    const [activityBadge, setActivityBadge] = useState(true);
+
+    // Email alerts (read by the Rust side)
+    const [smtpHost, setSmtpHost] = useState('');
+    const [smtpTo, setSmtpTo] = useState('');
+    const [smtpUsername, setSmtpUsername] = useState('');
+    const [smtpPassword, setSmtpPasswordInput] = useState('');
+    const [isSendingTestEmail, setIsSendingTestEmail] = useState(false);
+    const [jobs, setJobs] = useState<JobInfo[]>([]);
+    const [lanAccess, setLanAccess] = useState(false);
+    const [mcpEnabled, setMcpEnabled] = useState(false);
+    const [mcpPort, setMcpPortValue] = useState(8765);
+    const [firewall, setFirewall] = useState<FirewallStatus | null>(null);
+    const [profiles, setProfiles] = useState<ProfileStatus[]>([]);
+    const [newProfileName, setNewProfileName] = useState('');
+    const [newProfilePort, setNewProfilePort] = useState(8081);
+    const [runningJob, setRunningJob] = useState<string | null>(null);
+    const [syncFolder, setSyncFolderInput] = useState('');
+    const [savedSyncFolder, setSavedSyncFolder] = useState('');
+    // Privacy settings (read by the Rust side)
+    const [captureRetentionDays, setCaptureRetentionDays] = useState(30);
+    const [logRetentionDays, setLogRetentionDays] = useState(14);
+    const [diskUsage, setDiskUsage] = useState<DiskUsage | null>(null);
+    const [storeBodies, setStoreBodies] = useState(true);
+    const [encryptCaptures, setEncryptCaptures] = useState(false);
+    const [crashReports, setCrashReports] = useState(false);
+    const [savedCrashes, setSavedCrashes] = useState<CrashSummary[]>([]);
+    const [interrupted, setInterrupted] = useState<InterruptedRequest[]>([]);
+    const [telemetry, setTelemetry] = useState(false);
+    const [auditLog, setAuditLog] = useState(false);
+    const [telemetryQueue, setTelemetryQueue] = useState<TelemetryQueue | null>(null);
+    const [lockEnabled, setLockEnabled] = useState(false);
+    const [currentPassphrase, setCurrentPassphrase] = useState('');
+    const [newPassphrase, setNewPassphrase] = useState('');
     const [accountStrategy, setAccountStrategy] = useState<'sticky' | 'round-robin' | 'hybrid'>('hybrid');
+    const [engineVersion, setEngineVersion] = useState<EngineVersionInfo | null>(null);
+    const [isRollingBack, setIsRollingBack] = useState(false);
 
     // Kiro IDE Integration
     const [kiroStatus, setKiroStatus] = useState<KiroStatus & { installed: boolean }>({
@@ -135,10 +193,227 @@ export function Settings() {
             if (settings.defaultCooldown !== undefined) setDefaultCooldown(settings.defaultCooldown);
             if (settings.maxWaitBeforeError !== undefined) setMaxWaitBeforeError(settings.maxWaitBeforeError);
             if (settings.persistentSessions !== undefined) setPersistentSessions(settings.persistentSessions);
+            if (settings.idleSuspendMinutes !== undefined) setIdleSuspendMinutes(settings.idleSuspendMinutes);
+            if (settings.enginePriority !== undefined) setEnginePriorityValue(settings.enginePriority);
+            if (settings.activityBadge !== undefined) setActivityBadge(settings.activityBadge);
+            if (settings.smtpHost !== undefined) setSmtpHost(settings.smtpHost);
+            if (settings.smtpTo !== undefined) setSmtpTo(settings.smtpTo);
+            if (settings.smtpUsername !== undefined) setSmtpUsername(settings.smtpUsername);
+            if (settings.captureRetentionDays !== undefined) setCaptureRetentionDays(settings.captureRetentionDays);
+            if (settings.logRetentionDays !== undefined) setLogRetentionDays(settings.logRetentionDays);
+            if (settings.storeBodies !== undefined) setStoreBodies(settings.storeBodies);
+            if (settings.encryptCaptures !== undefined) setEncryptCaptures(settings.encryptCaptures);
+            if (settings.crashReports !== undefined) setCrashReports(settings.crashReports);
+            if (settings.telemetry !== undefined) setTelemetry(settings.telemetry);
+            if (settings.auditLog !== undefined) setAuditLog(settings.auditLog);
+            setSyncFolderInput(settings.syncFolder ?? '');
+            setSavedSyncFolder(settings.syncFolder ?? '');
+            setSavedCrashes(await listCrashReports());
+            setInterrupted(await getInterruptedRequests());
+            setDiskUsage(await getDiskUsage());
+            setJobs(await listJobs());
+            setMcpEnabled(settings.mcpPort !== undefined);
+            if (settings.mcpPort !== undefined) setMcpPortValue(settings.mcpPort);
+            setLanAccess(!!settings.bindAddress && !['127.0.0.1', 'localhost', '::1'].includes(settings.bindAddress));
+            setFirewall(await getFirewallRule());
+            setProfiles(await listProfiles());
+            setLockEnabled((await getLockStatus()).enabled);
         };
         loadPerformanceSettings();
+        // Another machine changed settings through the sync folder
+        const unlisten = listen('settings-synced', () => loadPerformanceSettings());
+        return () => { unlisten.then(fn => fn()); };
+    }, []);
+
+    // An empty new passphrase removes the lock
+    const handleSaveLock = async () => {
+        const error = await setLockPassphrase(lockEnabled ? currentPassphrase : null, newPassphrase || null);
+        if (error) {
+            toast.error(error);
+            return;
+        }
+        setLockEnabled(!!newPassphrase);
+        setCurrentPassphrase('');
+        setNewPassphrase('');
+        toast.success(newPassphrase ? t('appLockEnabled') : t('appLockDisabled'));
+    };
+
+    const handleTestEmail = async () => {
+        setIsSendingTestEmail(true);
+        if (smtpPassword && !(await setSmtpPassword(smtpPassword))) {
+            toast.error(t('operationFailed'));
+        } else {
+            setSmtpPasswordInput('');
+            const error = await sendTestEmail();
+            if (error) toast.error(error);
+            else toast.success(t('testEmailSent'));
+        }
+        setIsSendingTestEmail(false);
+    };
+
+    // Takes effect on the next proxy start. On Windows, offer the firewall rule
+    // that lets other machines through, and remove it again when LAN access goes off.
+    const handleLanAccess = async (enabled: boolean) => {
+        setLanAccess(enabled);
+        await updateSettings({ bindAddress: enabled ? '0.0.0.0' : '127.0.0.1' });
+        const port = config.proxy.port;
+        if (firewall?.supported) {
+            const wanted = enabled && confirm(t('firewallRuleConfirm'));
+            const needsChange = wanted
+                ? !firewall.present || firewall.port !== port
+                : !enabled && firewall.present;
+            if (needsChange) {
+                const error = await setFirewallRule(wanted ? port : null);
+                if (error) toast.error(`${t('firewallRuleFailed')}: ${error}`);
+                setFirewall(await getFirewallRule());
+            }
+        }
+        await applyProxyChanges(config.proxy.port, config.proxy.fallbackEnabled);
+    };
+
+    // Running proxies pick up listener changes without dropping requests in progress
+    const applyProxyChanges = async (port: number, fallbackEnabled: boolean) => {
+        if (!proxyStatus.running) return;
+        toast.info(t('applyingProxyChanges'));
+        const error = await reconfigureProxy(port, fallbackEnabled);
+        if (error) toast.error(error);
+        else toast.success(t('proxyChangesApplied'));
+    };
+
+    const handleMcpServer = async (enabled: boolean, port: number) => {
+        const error = await setMcpPort(enabled ? port : null);
+        if (error) {
+            toast.error(error);
+            return;
+        }
+        setMcpEnabled(enabled);
+    };
+
+    const handleCopyMcpToken = async (reset: boolean) => {
+        if (reset && !confirm(t('mcpResetTokenConfirm'))) return;
+        const token = reset ? await resetMcpToken() : await getMcpToken();
+        if (!token) {
+            toast.error(t('mcpTokenFailed'));
+            return;
+        }
+        await navigator.clipboard.writeText(token);
+        toast.success(t('mcpTokenCopied'));
+    };
+
+    const handleSyncFolder = async () => {
+        const folder = syncFolder.trim();
+        if (folder === savedSyncFolder) return;
+        const status = await setSyncFolder(folder || null);
+        if (typeof status === 'string') {
+            toast.error(status);
+            setSyncFolderInput(savedSyncFolder);
+            return;
+        }
+        setSavedSyncFolder(folder);
+        if (status.errors.length > 0) toast.error(status.errors.join('\n'));
+        else if (status.conflicts.length > 0) toast.info(t('syncConflicts'));
+        else if (folder) toast.success(t('syncDone'));
+    };
+
+    const handleSyncNow = async () => {
+        const status = await syncNow();
+        if (!status || status.errors.length > 0) toast.error(status?.errors.join('\n') || t('operationFailed'));
+        else if (status.conflicts.length > 0) toast.info(t('syncConflicts'));
+        else toast.success(t('syncDone'));
+    };
+
+    const handleAddProfile = async () => {
+        const error = await saveProfile(newProfileName, newProfilePort);
+        if (error) {
+            toast.error(error);
+            return;
+        }
+        setNewProfileName('');
+        setProfiles(await listProfiles());
+    };
+
+    const handleProfileRunning = async (name: string, running: boolean) => {
+        const error = await setProfileRunning(name, running);
+        if (error) toast.error(error);
+        setProfiles(await listProfiles());
+    };
+
+    const handleDeleteProfile = async (name: string) => {
+        if (!confirm(t('deleteProfileConfirm'))) return;
+        await deleteProfile(name);
+        setProfiles(await listProfiles());
+    };
+
+    const handleRunJob = async (id: string) => {
+        setRunningJob(id);
+        try {
+            toast.success(await runJobNow(id));
+        } catch (error) {
+            toast.error(String(error));
+        }
+        setJobs(await listJobs());
+        setRunningJob(null);
+    };
+
+    const handleJobSchedule = async (job: JobInfo, schedule: string, enabled: boolean) => {
+        const error = await setJobSchedule(job.id, schedule, enabled);
+        if (error) toast.error(error);
+        setJobs(await listJobs());
+    };
+
+    const handleCleanAppData = async () => {
+        const before = diskUsage?.totalBytes ?? 0;
+        const after = await cleanAppData();
+        if (!after) {
+            toast.error(t('operationFailed'));
+            return;
+        }
+        setDiskUsage(after);
+        toast.success(`${t('cleanedUp')}: ${formatMb(Math.max(0, before - after.totalBytes))}`);
+    };
+
+    const handleCleanupEverything = async () => {
+        if (!confirm(t('cleanupEverythingConfirm'))) return;
+        const includeAccounts = confirm(t('wipeAccountsConfirm'));
+        const report = await cleanupEverything(includeAccounts);
+        if (report && report.errors.length === 0) {
+            toast.success(t('cleanupEverythingDone'));
+        } else {
+            toast.error(report?.errors.join('\n') || t('wipeAllDataFailed'));
+        }
+        window.location.reload();
+    };
+
+    const handleWipeAllData = async () => {
+        if (!confirm(t('wipeAllDataConfirm'))) return;
+        const includeAccounts = confirm(t('wipeAccountsConfirm'));
+        const report = await wipeAllData(includeAccounts);
+        if (report && report.errors.length === 0) {
+            toast.success(t('wipeAllDataDone'));
+        } else {
+            toast.error(t('wipeAllDataFailed'));
+        }
+        // Settings are gone too, so start over from the setup wizard
+        window.location.reload();
+    };
+
+    useEffect(() => {
+        getEngineVersion().then(setEngineVersion);
     }, []);
 
+    const handleRollbackEngine = async () => {
+        if (!engineVersion?.previous || !confirm(`${t('rollbackEngineConfirm')} v${engineVersion.previous}?`)) return;
+        setIsRollingBack(true);
+        const info = await rollbackPackage();
+        setIsRollingBack(false);
+        if (info) {
+            setEngineVersion(info);
+            toast.success(`${t('rollbackEngineDone')} v${info.installed}`);
+        } else {
+            toast.error(t('rollbackEngineFailed'));
+        }
+    };
+
     // Load Kiro status on mount
     useEffect(() => {
         const loadKiroStatusAndSettings = async () => {
@@ -515,10 +790,7 @@ export function Settings() {
                                 // Save to disk
                                 const currentSettings = await loadSettings();
                                 await saveSettings({ ...currentSettings, fallbackEnabled: v });
-                                // Show notification about restart requirement
-                                if (proxyStatus.running) {
-                                    toast.info(t('restartProxyForChanges'));
-                                }
+                                await applyProxyChanges(config.proxy.port, v);
                             }}
                         />
                     </SettingRow>
@@ -545,6 +817,146 @@ export function Settings() {
                             <span className="text-sm font-mono text-text-secondary w-12">{config.app.pollingInterval}s</span>
                         </div>
                     </SettingRow>
+
+                    <SettingRow
+                        label={t('idleSuspend')}
+                        description={t('idleSuspendDesc')}
+                    >
+                        <input
+                            type="number"
+                            min="0"
+                            value={idleSuspendMinutes}
+                            onChange={async (e) => {
+                                const value = Math.max(0, parseInt(e.target.value) || 0);
+                                setIdleSuspendMinutes(value);
+                                await updateSettings({ idleSuspendMinutes: value });
+                            }}
+                            className="input text-sm py-1.5 px-3 w-24 text-center"
+                        />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('activityBadge')}
+                        description={t('activityBadgeDesc')}
+                    >
+                        <ToggleSwitch
+                            checked={activityBadge}
+                            onChange={async (v) => {
+                                setActivityBadge(v);
+                                await updateSettings({ activityBadge: v });
+                            }}
+                        />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('syncFolder')}
+                        description={t('syncFolderDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <input
+                                type="text"
+                                value={syncFolder}
+                                onChange={(e) => setSyncFolderInput(e.target.value)}
+                                onBlur={handleSyncFolder}
+                                placeholder="/path/to/Dropbox/antigravity-proxy"
+                                className="input text-sm py-1.5 px-3 w-56"
+                            />
+                            <button
+                                onClick={handleSyncNow}
+                                disabled={!savedSyncFolder}
+                                className="btn-secondary text-xs py-1.5 px-2"
+                            >
+                                {t('syncNow')}
+                            </button>
+                        </div>
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('emailAlerts')}
+                        description={t('emailAlertsDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <input
+                                type="text"
+                                value={smtpHost}
+                                onChange={(e) => setSmtpHost(e.target.value)}
+                                onBlur={() => updateSettings({ smtpHost: smtpHost.trim() })}
+                                placeholder="smtp.example.com"
+                                className="input text-sm py-1.5 px-3 w-36"
+                            />
+                            <input
+                                type="text"
+                                value={smtpTo}
+                                onChange={(e) => setSmtpTo(e.target.value)}
+                                onBlur={() => updateSettings({ smtpTo: smtpTo.trim() })}
+                                placeholder="ops@example.com"
+                                className="input text-sm py-1.5 px-3 w-36"
+                            />
+                        </div>
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('smtpLogin')}
+                        description={t('smtpLoginDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <input
+                                type="text"
+                                value={smtpUsername}
+                                onChange={(e) => setSmtpUsername(e.target.value)}
+                                onBlur={() => updateSettings({ smtpUsername: smtpUsername.trim() })}
+                                placeholder={t('username')}
+                                className="input text-sm py-1.5 px-3 w-32"
+                            />
+                            <input
+                                type="password"
+                                value={smtpPassword}
+                                onChange={(e) => setSmtpPasswordInput(e.target.value)}
+                                placeholder={t('password')}
+                                className="input text-sm py-1.5 px-3 w-28"
+                            />
+                            <button
+                                onClick={handleTestEmail}
+                                disabled={!smtpHost || !smtpTo || isSendingTestEmail}
+                                className="btn-secondary text-xs py-1.5 px-2"
+                            >
+                                {t('sendTestEmail')}
+                            </button>
+                        </div>
+                    </SettingRow>
+
+                    {jobs.map(job => (
+                        <SettingRow
+                            key={job.id}
+                            label={`${t('scheduledJob')}: ${job.id}`}
+                            description={job.lastRun
+                                ? `${job.description} · ${t('lastRun')} ${new Date(job.lastRun).toLocaleString()}: ${job.lastResult ?? ''}`
+                                : job.description}
+                        >
+                            <div className="flex items-center gap-2">
+                                <input
+                                    type="text"
+                                    defaultValue={job.schedule}
+                                    onBlur={(e) => {
+                                        if (e.target.value !== job.schedule) handleJobSchedule(job, e.target.value, job.enabled);
+                                    }}
+                                    title={job.nextRun ? `${t('nextRun')} ${new Date(job.nextRun).toLocaleString()}` : undefined}
+                                    className="input text-sm py-1.5 px-3 w-28 font-mono text-center"
+                                />
+                                <button
+                                    onClick={() => handleRunJob(job.id)}
+                                    disabled={runningJob !== null}
+                                    className="btn-secondary text-xs py-1.5 px-2"
+                                >
+                                    {runningJob === job.id ? <RefreshCw size={12} className="animate-spin" /> : t('runNow')}
+                                </button>
+                                <ToggleSwitch
+                                    checked={job.enabled}
+                                    onChange={(v) => handleJobSchedule(job, job.schedule, v)}
+                                />
+                            </div>
+                        </SettingRow>
+                    ))}
                 </Accordion>
 
                 {/* Section 2: Proxy */}
@@ -562,10 +974,107 @@ export function Settings() {
                                 const currentSettings = await loadSettings();
                                 await saveSettings({ ...currentSettings, port: value });
                             }}
+                            onBlur={() => applyProxyChanges(config.proxy.port, config.proxy.fallbackEnabled)}
                             className="input text-sm py-1.5 px-3 w-24 text-center"
                         />
                     </SettingRow>
 
+                    <SettingRow
+                        label={t('lanAccess')}
+                        description={firewall?.supported
+                            ? `${t('lanAccessDesc')} · ${firewall.present ? `${t('firewallRuleActive')} (${firewall.port})` : t('firewallRuleMissing')}`
+                            : t('lanAccessDesc')}
+                    >
+                        <ToggleSwitch checked={lanAccess} onChange={handleLanAccess} />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('mcpServer')}
+                        description={mcpEnabled
+                            ? `${t('mcpServerDesc')} · http://127.0.0.1:${mcpPort}/mcp`
+                            : t('mcpServerDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <input
+                                type="number"
+                                min="1024"
+                                max="65535"
+                                value={mcpPort}
+                                onChange={(e) => setMcpPortValue(parseInt(e.target.value) || 8765)}
+                                onBlur={() => mcpEnabled && handleMcpServer(true, mcpPort)}
+                                className="input text-sm py-1.5 px-3 w-24 text-center"
+                            />
+                            {mcpEnabled && (
+                                <>
+                                    <button
+                                        onClick={() => handleCopyMcpToken(false)}
+                                        className="btn-secondary text-xs py-1.5 px-2"
+                                    >
+                                        {t('mcpCopyToken')}
+                                    </button>
+                                    <button
+                                        onClick={() => handleCopyMcpToken(true)}
+                                        className="btn-secondary text-xs py-1.5 px-2"
+                                    >
+                                        {t('mcpResetToken')}
+                                    </button>
+                                </>
+                            )}
+                            <ToggleSwitch checked={mcpEnabled} onChange={(v) => handleMcpServer(v, mcpPort)} />
+                        </div>
+                    </SettingRow>
+
+                    {profiles.map(profile => (
+                        <SettingRow
+                            key={profile.name}
+                            label={`${t('profile')}: ${profile.name}`}
+                            description={`${t('port')} ${profile.port} · ${profile.home}`}
+                        >
+                            <div className="flex items-center gap-2">
+                                <button
+                                    onClick={() => handleDeleteProfile(profile.name)}
+                                    className="btn-secondary text-xs py-1.5 px-2 text-red-400 hover:text-red-300"
+                                >
+                                    <Trash2 size={12} />
+                                </button>
+                                <ToggleSwitch
+                                    checked={profile.running}
+                                    onChange={(v) => handleProfileRunning(profile.name, v)}
+                                />
+                            </div>
+                        </SettingRow>
+                    ))}
+
+                    <SettingRow
+                        label={t('addProfile')}
+                        description={t('addProfileDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <input
+                                type="text"
+                                value={newProfileName}
+                                onChange={(e) => setNewProfileName(e.target.value)}
+                                placeholder="work"
+                                className="input text-sm py-1.5 px-3 w-28"
+                            />
+                            <input
+                                type="number"
+                                min="1024"
+                                max="65535"
+                                value={newProfilePort}
+                                onChange={(e) => setNewProfilePort(parseInt(e.target.value) || 8081)}
+                                className="input text-sm py-1.5 px-3 w-24 text-center"
+                            />
+                            <button
+                                onClick={handleAddProfile}
+                                disabled={!newProfileName.trim()}
+                                className="btn-secondary text-xs py-1.5 px-2"
+                            >
+                                {t('addProfile')}
+                            </button>
+                        </div>
+                    </SettingRow>
+
                     <SettingRow
                         label={t('logBufferSize')}
                         description={t('logBufferDesc')}
@@ -749,6 +1258,26 @@ export function Settings() {
                         </div>
                     </SettingRow>
 
+                    <SettingRow
+                        label={t('enginePriority')}
+                        description={t('enginePriorityDesc')}
+                    >
+                        <select
+                            value={enginePriority}
+                            onChange={async (e) => {
+                                const value = e.target.value as EnginePriority;
+                                setEnginePriorityValue(value);
+                                const error = await setEnginePriority(value);
+                                if (error) toast.error(error);
+                            }}
+                            className="input text-sm py-1.5 px-3 w-36"
+                        >
+                            <option value="belowNormal">{t('priorityBelowNormal')}</option>
+                            <option value="normal">{t('priorityNormal')}</option>
+                            <option value="high">{t('priorityHigh')}</option>
+                        </select>
+                    </SettingRow>
+
                     <SettingRow
                         label={t('accountStrategy')}
                         description={t('accountStrategyDesc')}
@@ -799,6 +1328,260 @@ export function Settings() {
                     </div>
                 </Accordion>
 
+                {/* Privacy */}
+                <Accordion icon={<Shield size={18} />} title={t('privacySettings')}>
+                    <SettingRow
+                        label={t('appDataSize')}
+                        description={diskUsage
+                            ? `${diskUsage.locations.filter(l => l.bytes > 0).map(l => `${l.name} ${formatMb(l.bytes)}`).join(' · ')}${diskUsage.freeBytes !== null ? ` · ${t('freeSpace')} ${formatMb(diskUsage.freeBytes)}` : ''}`
+                            : t('appDataSizeDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <span className={`text-sm font-mono ${diskUsage?.warning ? 'text-amber-400' : ''}`} title={diskUsage?.warning ?? undefined}>
+                                {diskUsage ? formatMb(diskUsage.totalBytes) : '-'}
+                            </span>
+                            <button
+                                onClick={handleCleanAppData}
+                                className="btn-secondary text-xs py-1.5 px-2"
+                            >
+                                {t('cleanUp')}
+                            </button>
+                        </div>
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('captureRetention')}
+                        description={t('captureRetentionDesc')}
+                    >
+                        <input
+                            type="number"
+                            min="1"
+                            value={captureRetentionDays}
+                            onChange={async (e) => {
+                                const value = Math.max(1, parseInt(e.target.value) || 1);
+                                setCaptureRetentionDays(value);
+                                await updateSettings({ captureRetentionDays: value });
+                            }}
+                            className="input text-sm py-1.5 px-3 w-24 text-center"
+                        />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('logRetention')}
+                        description={t('logRetentionDesc')}
+                    >
+                        <input
+                            type="number"
+                            min="1"
+                            value={logRetentionDays}
+                            onChange={async (e) => {
+                                const value = Math.max(1, parseInt(e.target.value) || 1);
+                                setLogRetentionDays(value);
+                                await updateSettings({ logRetentionDays: value });
+                            }}
+                            className="input text-sm py-1.5 px-3 w-24 text-center"
+                        />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('storeBodies')}
+                        description={t('storeBodiesDesc')}
+                    >
+                        <ToggleSwitch
+                            checked={storeBodies}
+                            onChange={async (v) => {
+                                setStoreBodies(v);
+                                await updateSettings({ storeBodies: v });
+                            }}
+                        />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('encryptCaptures')}
+                        description={t('encryptCapturesDesc')}
+                    >
+                        <ToggleSwitch
+                            checked={encryptCaptures}
+                            onChange={async (v) => {
+                                setEncryptCaptures(v);
+                                await updateSettings({ encryptCaptures: v });
+                            }}
+                        />
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('crashReports')}
+                        description={savedCrashes.length > 0
+                            ? `${t('crashReportsSaved')}: ${savedCrashes.length} · ${savedCrashes[0].message}`
+                            : t('crashReportsDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            {savedCrashes.length > 0 && (
+                                <>
+                                    <button
+                                        onClick={() => reportCrash(savedCrashes[0].id)}
+                                        className="btn-secondary text-xs py-1.5 px-2 flex items-center gap-1"
+                                    >
+                                        <Github size={12} />
+                                        {t('reportCrash')}
+                                    </button>
+                                    <button
+                                        onClick={async () => {
+                                            if (await deleteCrashReports()) setSavedCrashes([]);
+                                        }}
+                                        className="btn-secondary text-xs py-1.5 px-2"
+                                        title={t('delete')}
+                                    >
+                                        <Trash2 size={12} />
+                                    </button>
+                                </>
+                            )}
+                            <ToggleSwitch
+                                checked={crashReports}
+                                onChange={async (v) => {
+                                    setCrashReports(v);
+                                    await updateSettings({ crashReports: v });
+                                }}
+                            />
+                        </div>
+                    </SettingRow>
+
+                    {interrupted.length > 0 && (
+                        <SettingRow
+                            label={t('interruptedRequests')}
+                            description={`${interrupted.length} · ${interrupted[0].model}${interrupted[0].account ? ` (${interrupted[0].account})` : ''} · ${new Date(interrupted[0].startedAt).toLocaleString()} · ${t(interrupted[0].cause === 'appClosed' ? 'interruptedByApp' : 'interruptedByEngine')}`}
+                        >
+                            <button
+                                onClick={async () => {
+                                    if (await clearInterruptedRequests()) setInterrupted([]);
+                                }}
+                                className="btn-secondary text-xs py-1.5 px-2"
+                                title={t('delete')}
+                            >
+                                <Trash2 size={12} />
+                            </button>
+                        </SettingRow>
+                    )}
+
+                    <SettingRow
+                        label={t('telemetry')}
+                        description={t('telemetryDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            {telemetry && (
+                                <button
+                                    onClick={async () => setTelemetryQueue(telemetryQueue ? null : await getTelemetryQueue())}
+                                    className="btn-secondary text-xs py-1.5 px-2"
+                                >
+                                    {telemetryQueue ? t('hideTelemetryData') : t('showTelemetryData')}
+                                </button>
+                            )}
+                            <ToggleSwitch
+                                checked={telemetry}
+                                onChange={async (v) => {
+                                    setTelemetry(v);
+                                    setTelemetryQueue(null);
+                                    await updateSettings({ telemetry: v });
+                                    await refreshTelemetry();
+                                }}
+                            />
+                        </div>
+                    </SettingRow>
+                    {telemetryQueue && (
+                        <pre className="text-[10px] font-mono text-text-muted bg-black/30 rounded-lg p-3 max-h-48 overflow-auto">
+                            {JSON.stringify(telemetryQueue, null, 2)}
+                        </pre>
+                    )}
+
+                    <SettingRow
+                        label={t('auditLog')}
+                        description={t('auditLogDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            <button
+                                onClick={async () => {
+                                    const result = await verifyAuditLog();
+                                    if (!result) {
+                                        toast.error(t('operationFailed'));
+                                    } else if (result.valid) {
+                                        toast.success(`${t('auditLogIntact')} (${result.entries})`);
+                                    } else {
+                                        toast.error(`${t('auditLogBroken')} #${result.brokenAt}: ${result.problem}`);
+                                    }
+                                }}
+                                className="btn-secondary text-xs py-1.5 px-2 flex items-center gap-1"
+                            >
+                                <Shield size={12} />
+                                {t('verifyAuditLog')}
+                            </button>
+                            <ToggleSwitch
+                                checked={auditLog}
+                                onChange={async (v) => {
+                                    setAuditLog(v);
+                                    await updateSettings({ auditLog: v });
+                                }}
+                            />
+                        </div>
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('appLock')}
+                        description={t('appLockDesc')}
+                    >
+                        <div className="flex items-center gap-2">
+                            {lockEnabled && (
+                                <input
+                                    type="password"
+                                    value={currentPassphrase}
+                                    onChange={(e) => setCurrentPassphrase(e.target.value)}
+                                    placeholder={t('currentPassphrase')}
+                                    className="input text-sm py-1.5 px-3 w-32"
+                                />
+                            )}
+                            <input
+                                type="password"
+                                value={newPassphrase}
+                                onChange={(e) => setNewPassphrase(e.target.value)}
+                                placeholder={t('newPassphrase')}
+                                className="input text-sm py-1.5 px-3 w-32"
+                            />
+                            <button
+                                onClick={handleSaveLock}
+                                disabled={!lockEnabled && !newPassphrase}
+                                className="btn-secondary text-xs py-1.5 px-2"
+                            >
+                                {lockEnabled && !newPassphrase ? t('removePassphrase') : t('savePassphrase')}
+                            </button>
+                        </div>
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('wipeAllData')}
+                        description={t('wipeAllDataDesc')}
+                    >
+                        <button
+                            onClick={handleWipeAllData}
+                            className="btn-secondary text-xs py-1.5 px-2 text-red-400 hover:text-red-300 flex items-center gap-1"
+                        >
+                            <Trash2 size={12} />
+                            {t('wipeAllData')}
+                        </button>
+                    </SettingRow>
+
+                    <SettingRow
+                        label={t('cleanupEverything')}
+                        description={t('cleanupEverythingDesc')}
+                    >
+                        <button
+                            onClick={handleCleanupEverything}
+                            className="btn-secondary text-xs py-1.5 px-2 text-red-400 hover:text-red-300 flex items-center gap-1"
+                        >
+                            <Trash2 size={12} />
+                            {t('cleanupEverything')}
+                        </button>
+                    </SettingRow>
+                </Accordion>
+
                 {/* Section 4: IDE Integrations */}
                 <Accordion icon={<Monitor size={18} />} title={t('ideIntegrations')}>
                     {/* Kiro IDE */}
@@ -1099,6 +1882,24 @@ export function Settings() {
                         </div>
                     </SettingRow>
 
+                    <SettingRow label={t('engineVersion')} description={engineVersion?.previous ? `${t('previousVersion')}: v${engineVersion.previous}` : "antigravity-claude-proxy"}>
+                        <div className="flex items-center gap-3">
+                            <span className="text-sm font-mono text-accent-primary">
+                                {engineVersion?.installed ? `v${engineVersion.installed}` : t('notInstalled')}
+                            </span>
+                            {engineVersion?.previous && (
+                                <button
+                                    onClick={handleRollbackEngine}
+                                    disabled={isRollingBack}
+                                    className="btn-secondary text-xs flex items-center gap-1.5 py-1 px-2"
+                                >
+                                    <RotateCcw size={12} className={isRollingBack ? 'animate-spin' : ''} />
+                                    {t('rollbackEngine')}
+                                </button>
+                            )}
+                        </div>
+                    </SettingRow>
+
                     <SettingRow label={t('sourceCode')} description="GUI Source Code">
                         <a
                             href="https://github.com/StRonKEA/Antigravity-Claude-Proxy-GUI"
@@ -8,7 +8,8 @@ import {
     Download,
     Calendar,
     Clock,
-    Users
+    Users,
+    DollarSign
 } from 'lucide-react';
 import {
     BarChart,
@@ -30,6 +31,7 @@ import { useTranslation, useI18nStore } from '../i18n';
 import { toast } from '../stores/toastStore';
 import { useAppStore } from '../stores/appStore';
 import { getUsageHistory } from '../services/proxyService';
+import { estimateUsageCost, type CostEstimate } from '../services/trafficService';
 import { getModelDisplayName } from '../utils/modelUtils';
 import { ModelIcon } from '../components/ModelIcon';
 import { MaskedEmail } from '../components/MaskedEmail';
@@ -53,6 +55,7 @@ export function Statistics() {
     const [timeRange, setTimeRange] = useState<TimeRange>('24h');
     const [isRefreshing, setIsRefreshing] = useState(false);
     const [usageHistory, setUsageHistory] = useState<UsageStats | null>(null);
+    const [cost, setCost] = useState<CostEstimate | null>(null);
 
     // Fetch data on mount and when proxy is running
     useEffect(() => {
@@ -73,6 +76,12 @@ export function Statistics() {
         return () => clearInterval(interval);
     }, [proxyStatus.running]);
 
+    useEffect(() => {
+        if (usageHistory) {
+            estimateUsageCost(usageHistory).then(setCost);
+        }
+    }, [usageHistory]);
+
     const handleRefresh = async () => {
         setIsRefreshing(true);
         const history = await getUsageHistory();
@@ -339,7 +348,7 @@ export function Statistics() {
             </div>
 
             {/* Summary Cards */}
-            <div className="grid grid-cols-4 gap-3 shrink-0">
+            <div className="grid grid-cols-5 gap-3 shrink-0">
                 <div className="glass-card p-4 text-center">
                     <Zap className="w-5 h-5 mx-auto mb-2 text-accent-primary" />
                     <p className="text-2xl font-bold text-text-primary">{stats.total.toLocaleString()}</p>
@@ -364,6 +373,16 @@ export function Statistics() {
                     <p className="text-2xl font-bold text-green-400">{stats.geminiTotal.toLocaleString()}</p>
                     <p className="text-[10px] text-text-muted uppercase tracking-wide">Gemini</p>
                 </div>
+                <div
+                    className="glass-card p-4 text-center"
+                    title={cost?.unpriced.length ? `${t('unpricedModels')}: ${cost.unpriced.join(', ')}` : undefined}
+                >
+                    <DollarSign className="w-5 h-5 mx-auto mb-2 text-yellow-400" />
+                    <p className="text-2xl font-bold text-yellow-400">
+                        {(cost?.total ?? 0).toLocaleString(undefined, { style: 'currency', currency: cost?.currency ?? 'USD' })}
+                    </p>
+                    <p className="text-[10px] text-text-muted uppercase tracking-wide">{t('estimatedApiCost')}</p>
+                </div>
             </div>
 
             {/* Main Content */}
@@ -1,9 +1,11 @@
+import { invoke } from '@tauri-apps/api/core';
 import { homeDir, join } from '@tauri-apps/api/path';
-import { readTextFile, writeTextFile, exists, mkdir } from '@tauri-apps/plugin-fs';
+import { readTextFile, exists } from '@tauri-apps/plugin-fs';
 
 const CONFIG_DIR = '.config/antigravity-proxy/desktop-app';
 
 export interface AppSettings {
+    schemaVersion?: number;  // managed by the Rust migrations in settings.rs
     setupCompleted: boolean;
     language: 'en' | 'tr';
     autoStartProxy: boolean;
@@ -11,6 +13,15 @@ export interface AppSettings {
     lastSelectedPreset?: 'claude' | 'gemini';
     // App settings
     pollingInterval?: number;  // seconds
+    idleSuspendMinutes?: number;  // 0 or unset = never suspend
+    activityBadge?: boolean;  // in-flight request count on the dock/taskbar icon, default on
+    // Email alerts (the password is kept in the OS keychain, see setSmtpPassword)
+    smtpHost?: string;
+    smtpPort?: number;  // defaults to 587 for starttls, 465 for tls, 25 for none
+    smtpSecurity?: 'starttls' | 'tls' | 'none';
+    smtpUsername?: string;
+    smtpFrom?: string;  // defaults to the username, or the first recipient
+    smtpTo?: string;  // comma-separated recipients
     logBufferSize?: number;
     // Proxy settings  
     port?: number;
@@ -23,8 +34,27 @@ export interface AppSettings {
     retryMaxDelay?: number;   // ms
     defaultCooldown?: number; // seconds
     maxWaitBeforeError?: number; // seconds
+    enginePriority?: 'belowNormal' | 'normal' | 'high';  // applied by priority.rs
     persistentSessions?: boolean;
     kiroAutoStart?: boolean;
+    // Account credentials
+    credentialLifetimeDays?: number;  // set when your org forces re-authentication
+    credentialWarningHours?: number;  // warn this long before expiry
+    // Privacy
+    captureRetentionDays?: number;
+    logRetentionDays?: number;
+    storeBodies?: boolean;  // false = never keep request/response bodies
+    encryptCaptures?: boolean;  // AES-GCM with a key kept in the OS keychain
+    crashReports?: boolean;  // opt-in: save a local report when the app panics
+    telemetry?: boolean;  // opt-in anonymous usage counters
+    telemetryUrl?: string;  // where queued counters are uploaded; unset = keep local
+    auditLog?: boolean;  // hash-chained log of config changes and account switches
+    syncFolder?: string;  // shared folder settings and mapping presets are synced through
+    diskWarnMb?: number;  // warn when app data grows past this (0 = never)
+    diskMinFreeMb?: number;  // warn when the drive holding app data has less free
+    // Headless deployments
+    bindAddress?: string;  // engine listen address, e.g. 0.0.0.0, :: (dual-stack) or ::1
+    mcpPort?: number;  // loopback port of the MCP server; unset = off
 }
 
 const DEFAULT_SETTINGS: AppSettings = {
@@ -42,7 +72,15 @@ const DEFAULT_SETTINGS: AppSettings = {
     defaultCooldown: 10,
     maxWaitBeforeError: 120,
     persistentSessions: true,
-    kiroAutoStart: false
+    kiroAutoStart: false,
+    credentialWarningHours: 24,
+    captureRetentionDays: 30,
+    logRetentionDays: 14,
+    storeBodies: true,
+    encryptCaptures: false,
+    crashReports: false,
+    telemetry: false,
+    auditLog: false
 };
 
 /**
@@ -62,44 +100,70 @@ export async function getSettingsPath(): Promise<string> {
 }
 
 /**
- * Ensure config directory exists
+ * Settings overridden by ACP_* environment variables (ACP_PORT -> port)
  */
-async function ensureConfigDir(): Promise<void> {
-    const configDir = await getAppConfigDir();
-    const dirExists = await exists(configDir);
-    if (!dirExists) {
-        await mkdir(configDir, { recursive: true });
+export async function getEnvOverrides(): Promise<Partial<AppSettings>> {
+    try {
+        return await invoke<Partial<AppSettings>>('get_env_overrides');
+    } catch {
+        return {};
     }
 }
 
+async function readSettingsFile(): Promise<Partial<AppSettings> | null> {
+    const path = await getSettingsPath();
+    if (!(await exists(path))) {
+        return null;
+    }
+    return JSON.parse(await readTextFile(path));
+}
+
 /**
- * Load app settings from disk
+ * Load app settings: defaults, then the file on disk, then environment overrides
  */
 export async function loadSettings(): Promise<AppSettings> {
+    const overrides = await getEnvOverrides();
     try {
-        const path = await getSettingsPath();
-        const fileExists = await exists(path);
+        const saved = await readSettingsFile();
 
-        if (!fileExists) {
+        if (!saved) {
             // No settings file - return defaults (setup not completed)
-            return DEFAULT_SETTINGS;
+            return { ...DEFAULT_SETTINGS, ...overrides };
         }
 
-        const content = await readTextFile(path);
-        return { ...DEFAULT_SETTINGS, ...JSON.parse(content) };
+        return { ...DEFAULT_SETTINGS, ...saved, ...overrides };
     } catch {
-        return DEFAULT_SETTINGS;
+        return { ...DEFAULT_SETTINGS, ...overrides };
     }
 }
 
 /**
- * Save app settings to disk
+ * Save app settings to disk. Keys set through the environment keep their
+ * on-disk value so an override never gets persisted. Written by the Rust side,
+ * which refuses while the app is locked.
  */
 export async function saveSettings(settings: AppSettings): Promise<boolean> {
     try {
-        await ensureConfigDir();
-        const path = await getSettingsPath();
-        await writeTextFile(path, JSON.stringify(settings, null, 2));
+        const overrides = await getEnvOverrides();
+        const saved = (await readSettingsFile().catch(() => null)) ?? {};
+        const toWrite: Record<string, unknown> = { ...settings };
+        for (const key of Object.keys(overrides) as (keyof AppSettings)[]) {
+            if (key in saved) {
+                toWrite[key] = saved[key];
+            } else {
+                delete toWrite[key];
+            }
+        }
+        await invoke('save_settings', { settings: toWrite });
+
+        const changed = Object.fromEntries(
+            Object.entries(toWrite).filter(([key, value]) =>
+                JSON.stringify(value) !== JSON.stringify(saved[key as keyof AppSettings]))
+        );
+        if (Object.keys(changed).length > 0) {
+            // Only recorded when the audit log is enabled
+            invoke('audit_event', { action: 'settings.change', detail: changed }).catch(() => {});
+        }
         return true;
     } catch {
         return false;
@@ -113,3 +177,102 @@ export async function updateSettings(updates: Partial<AppSettings>): Promise<boo
     const current = await loadSettings();
     return await saveSettings({ ...current, ...updates });
 }
+
+export interface SyncStatus {
+    folder: string | null;
+    pulled: string[];
+    pushed: string[];
+    conflicts: string[];  // conflict copies written to the sync folder
+    errors: string[];
+}
+
+/**
+ * Sync settings and mapping presets through a shared folder (Dropbox, Syncthing, ...)
+ * @param folder - Folder to sync through, or null to stop syncing
+ * @returns The status of the first sync round, or the error message
+ */
+export async function setSyncFolder(folder: string | null): Promise<SyncStatus | string> {
+    try {
+        return await invoke<SyncStatus>('set_sync_folder', { folder });
+    } catch (error) {
+        console.error('Failed to set sync folder:', error);
+        return String(error);
+    }
+}
+
+export async function syncNow(): Promise<SyncStatus | null> {
+    try {
+        return await invoke<SyncStatus>('sync_now');
+    } catch (error) {
+        console.error('Failed to sync settings:', error);
+        return null;
+    }
+}
+
+export interface DiskUsage {
+    locations: { name: 'logs' | 'captures' | 'reports' | 'cache' | 'other'; path: string; bytes: number; cleanable: boolean }[];
+    totalBytes: number;
+    freeBytes: number | null;  // free space on the drive holding app data
+    diskBytes: number | null;
+    warning: string | null;
+}
+
+export async function getDiskUsage(): Promise<DiskUsage | null> {
+    try {
+        return await invoke<DiskUsage>('get_disk_usage');
+    } catch (error) {
+        console.error('Failed to get disk usage:', error);
+        return null;
+    }
+}
+
+/**
+ * Empty logs, captures, reports and caches
+ * @param names - Locations to clean; all cleanable ones when empty
+ */
+export async function cleanAppData(names: string[] = []): Promise<DiskUsage | null> {
+    try {
+        return await invoke<DiskUsage>('clean_app_data', { names });
+    } catch (error) {
+        console.error('Failed to clean app data:', error);
+        return null;
+    }
+}
+
+export interface WipeReport {
+    removedFiles: number;
+    errors: string[];
+}
+
+/**
+ * Overwrite and delete all local app data (captures, logs, caches, settings)
+ * @param includeAccounts - Also remove the proxy's saved account credentials
+ */
+export async function wipeAllData(includeAccounts: boolean): Promise<WipeReport | null> {
+    try {
+        return await invoke<WipeReport>('wipe_all_data', { includeAccounts });
+    } catch (error) {
+        console.error('Failed to wipe data:', error);
+        return null;
+    }
+}
+
+export interface CleanupReport {
+    removed: string[];  // e.g. 'autostart', 'firewallRule'
+    removedFiles: number;
+    errors: string[];
+}
+
+/**
+ * Remove everything the app leaves behind before an uninstall: the login item,
+ * the firewall rule, keychain secrets and all local data
+ * @param includeAccounts - Also remove the proxy's saved account credentials
+ */
+export async function cleanupEverything(includeAccounts: boolean): Promise<CleanupReport | null> {
+    try {
+        return await invoke<CleanupReport>('cleanup_everything', { includeAccounts });
+    } catch (error) {
+        console.error('Failed to clean up:', error);
+        return null;
+    }
+}
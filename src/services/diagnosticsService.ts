@@ -0,0 +1,437 @@
+/**
+ * Diagnostics Service - Self-checks run on the Rust side
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type CheckStatus = 'pass' | 'warn' | 'fail' | 'skip';
+
+export interface CheckResult {
+    id: 'dns' | 'reachability' | 'tls' | 'clock' | 'port' | 'auth';
+    status: CheckStatus;
+    detail: string;
+    durationMs: number;
+}
+
+export interface DiagnosticsReport {
+    passed: boolean;
+    checks: CheckResult[];
+}
+
+export async function runDiagnostics(port: number): Promise<DiagnosticsReport | null> {
+    try {
+        return await invoke<DiagnosticsReport>('run_diagnostics', { port });
+    } catch (error) {
+        console.error('Failed to run diagnostics:', error);
+        return null;
+    }
+}
+
+/**
+ * Export a zip with scrubbed logs, settings, versions and a diagnostics report
+ * Returns the path of the written file
+ */
+export async function exportDiagnostics(port: number): Promise<string | null> {
+    try {
+        return await invoke<string>('export_diagnostics', { port });
+    } catch (error) {
+        console.error('Failed to export diagnostics:', error);
+        return null;
+    }
+}
+
+export interface AccountPing {
+    email: string;
+    latencyMs: number;
+    ok: boolean;
+    error?: string | null;
+}
+
+export interface PingReport {
+    proxyMs?: number | null;
+    networkMs?: number | null;
+    accounts: AccountPing[];
+}
+
+/**
+ * Round-trip latency to the proxy, the upstream network and each account
+ * @param account - Only ping this account (all enabled accounts if omitted)
+ */
+export async function pingUpstream(port: number, account?: string): Promise<PingReport | null> {
+    try {
+        return await invoke<PingReport>('ping_upstream', { port, account });
+    } catch (error) {
+        console.error('Failed to ping upstream:', error);
+        return null;
+    }
+}
+
+export interface BenchmarkReport {
+    model: string;
+    requests: number;
+    succeeded: number;
+    failed: number;
+    errors: Record<string, number>;
+    durationMs: number;
+    throughputRps: number;
+    p50Ms: number;
+    p95Ms: number;
+    p99Ms: number;
+}
+
+/**
+ * Fire synthetic requests through the proxy and report throughput and latency
+ * @param promptSize - Approximate prompt size in tokens
+ */
+export async function runBenchmark(
+    port: number,
+    concurrency: number,
+    n: number,
+    promptSize: number,
+    model?: string
+): Promise<BenchmarkReport | null> {
+    try {
+        return await invoke<BenchmarkReport>('run_benchmark', { port, concurrency, n, promptSize, model });
+    } catch (error) {
+        console.error('Benchmark failed:', error);
+        return null;
+    }
+}
+
+export interface ReplayResult {
+    method: string;
+    url: string;
+    status: number;
+    durationMs: number;
+    headers: Record<string, string>;
+    body: string;
+    captureId: string | null;
+}
+
+/**
+ * Replay a cURL command through the local proxy.
+ * Reads the command from the clipboard when none is given.
+ */
+export async function replayCurl(curl?: string): Promise<ReplayResult | null> {
+    try {
+        return await invoke<ReplayResult>('replay_curl', { curl: curl ?? null });
+    } catch (error) {
+        console.error('Failed to replay curl command:', error);
+        return null;
+    }
+}
+
+export interface TestPromptResult {
+    model: string;
+    status: number;
+    text: string;
+    inputTokens: number | null;
+    outputTokens: number | null;
+    error: string | null;
+    timing: {
+        headersMs: number;  // account selection, mapping and upstream accepting the request
+        firstTokenMs: number | null;  // streaming only
+        totalMs: number;
+    };
+    servedModel: string | null;
+    servedAccount: string | null;
+}
+
+/**
+ * Send one prompt through the running proxy and time each stage
+ */
+export async function sendTestPrompt(model: string, text: string, stream = true): Promise<TestPromptResult | null> {
+    try {
+        return await invoke<TestPromptResult>('send_test_prompt', { model, text, stream });
+    } catch (error) {
+        console.error('Test prompt failed:', error);
+        return null;
+    }
+}
+
+export interface CaptureSummary {
+    id: string;
+    timestamp: number;
+    method: string;
+    path: string;
+    status: number;
+}
+
+export interface Change {
+    path: string;  // JSON pointer into the body, or the header name
+    kind: 'added' | 'removed' | 'changed';
+    before: unknown | null;
+    after: unknown | null;
+}
+
+export interface CaptureDiff {
+    requestLine: Change[];
+    requestHeaders: Change[];
+    requestBody: Change[];
+    responseStatus: Change[];
+    responseHeaders: Change[];
+    responseBody: Change[];
+}
+
+export async function listCaptures(): Promise<CaptureSummary[]> {
+    try {
+        return await invoke<CaptureSummary[]>('list_captures');
+    } catch (error) {
+        console.error('Failed to list captures:', error);
+        return [];
+    }
+}
+
+/**
+ * Structured diff between two captures, e.g. a working and a failing request
+ */
+export async function diffCaptures(a: string, b: string): Promise<CaptureDiff | null> {
+    try {
+        return await invoke<CaptureDiff>('diff_captures', { a, b });
+    } catch (error) {
+        console.error('Failed to diff captures:', error);
+        return null;
+    }
+}
+
+export interface ComparisonArm {
+    model: string;
+    status: number;
+    durationMs: number;
+    inputTokens: number | null;
+    outputTokens: number | null;
+    text: string;
+    captureId: string | null;
+    error: string | null;
+}
+
+export interface Comparison {
+    id: string;
+    timestamp: number;
+    sourceCapture: string;
+    a: ComparisonArm;
+    b: ComparisonArm;
+}
+
+/**
+ * Send a captured request to two models at once and record both replies
+ */
+export async function compareMappings(captureId: string, modelA: string, modelB: string): Promise<Comparison | null> {
+    try {
+        return await invoke<Comparison>('compare_mappings', { captureId, modelA, modelB });
+    } catch (error) {
+        console.error('Failed to compare mappings:', error);
+        return null;
+    }
+}
+
+/**
+ * Recorded A/B comparisons, newest first
+ */
+export async function listComparisons(): Promise<Comparison[]> {
+    try {
+        return await invoke<Comparison[]>('list_comparisons');
+    } catch (error) {
+        console.error('Failed to list comparisons:', error);
+        return [];
+    }
+}
+
+export interface CrashSummary {
+    id: string;
+    timestamp: number;
+    message: string;
+    location: string | null;
+}
+
+/**
+ * Crash reports saved by the opt-in panic hook, newest first
+ */
+export async function listCrashReports(): Promise<CrashSummary[]> {
+    try {
+        return await invoke<CrashSummary[]>('list_crash_reports');
+    } catch (error) {
+        console.error('Failed to list crash reports:', error);
+        return [];
+    }
+}
+
+/**
+ * Open a prefilled GitHub issue and reveal the report file for attaching
+ */
+export async function reportCrash(id: string): Promise<boolean> {
+    try {
+        await invoke('report_crash', { id });
+        return true;
+    } catch (error) {
+        console.error('Failed to open crash report issue:', error);
+        return false;
+    }
+}
+
+export async function deleteCrashReports(): Promise<boolean> {
+    try {
+        await invoke('delete_crash_reports');
+        return true;
+    } catch (error) {
+        console.error('Failed to delete crash reports:', error);
+        return false;
+    }
+}
+
+export interface InterruptedRequest {
+    id: number;
+    model: string;
+    account: string | null;
+    startedAt: number;
+    endedAt: number;
+    cause: 'engineExited' | 'appClosed';
+    exitCode: number | null;
+}
+
+/**
+ * Requests cut off by an engine exit or an app crash, newest first
+ */
+export async function getInterruptedRequests(): Promise<InterruptedRequest[]> {
+    try {
+        return await invoke<InterruptedRequest[]>('get_interrupted_requests');
+    } catch (error) {
+        console.error('Failed to load interrupted requests:', error);
+        return [];
+    }
+}
+
+export async function clearInterruptedRequests(): Promise<boolean> {
+    try {
+        await invoke('clear_interrupted_requests');
+        return true;
+    } catch (error) {
+        console.error('Failed to clear interrupted requests:', error);
+        return false;
+    }
+}
+
+export interface AuditVerification {
+    entries: number;
+    valid: boolean;
+    brokenAt: number | null;  // first entry that doesn't check out
+    problem: string | null;
+    headHash: string | null;  // keep a copy elsewhere to detect removed entries
+}
+
+/**
+ * Check the audit log's hash chain
+ */
+export async function verifyAuditLog(): Promise<AuditVerification | null> {
+    try {
+        return await invoke<AuditVerification>('verify_audit_log');
+    } catch (error) {
+        console.error('Failed to verify audit log:', error);
+        return null;
+    }
+}
+
+/**
+ * Store the SMTP password for email alerts in the OS keychain (null removes it)
+ */
+export async function setSmtpPassword(password: string | null): Promise<boolean> {
+    try {
+        await invoke('set_smtp_password', { password });
+        return true;
+    } catch (error) {
+        console.error('Failed to save SMTP password:', error);
+        return false;
+    }
+}
+
+/**
+ * Send a test alert email; returns the error message on failure
+ */
+export async function sendTestEmail(): Promise<string | null> {
+    try {
+        await invoke('send_test_email');
+        return null;
+    } catch (error) {
+        console.error('Failed to send test email:', error);
+        return String(error);
+    }
+}
+
+export interface JobInfo {
+    id: string;
+    description: string;
+    schedule: string;  // five-field cron expression, local time
+    enabled: boolean;
+    lastRun: number | null;
+    lastOk: boolean | null;
+    lastResult: string | null;
+    nextRun: number | null;
+}
+
+/**
+ * Recurring background jobs (usage summary, cleanup, account health check)
+ */
+export async function listJobs(): Promise<JobInfo[]> {
+    try {
+        return await invoke<JobInfo[]>('list_jobs');
+    } catch (error) {
+        console.error('Failed to list jobs:', error);
+        return [];
+    }
+}
+
+/**
+ * Run a job now; resolves to its result message, throws on failure
+ */
+export async function runJobNow(id: string): Promise<string> {
+    try {
+        return await invoke<string>('run_job_now', { id });
+    } catch (error) {
+        console.error('Failed to run job:', error);
+        throw new Error(String(error));
+    }
+}
+
+/**
+ * Change a job's cron schedule or turn it off; returns the error message on failure
+ */
+export async function setJobSchedule(id: string, schedule: string, enabled: boolean): Promise<string | null> {
+    try {
+        await invoke('set_job_schedule', { id, schedule, enabled });
+        return null;
+    } catch (error) {
+        console.error('Failed to set job schedule:', error);
+        return String(error);
+    }
+}
+
+export interface TelemetryQueue {
+    installId: string;
+    appVersion: string;
+    os: string;
+    arch: string;
+    batches: { start: number; end: number; counters: Record<string, number> }[];
+}
+
+/**
+ * Anonymous usage counters waiting to be uploaded, exactly as they would be sent
+ */
+export async function getTelemetryQueue(): Promise<TelemetryQueue | null> {
+    try {
+        return await invoke<TelemetryQueue>('get_telemetry_queue');
+    } catch (error) {
+        console.error('Failed to get telemetry queue:', error);
+        return null;
+    }
+}
+
+/**
+ * Apply a change of the telemetry setting right away
+ */
+export async function refreshTelemetry(): Promise<void> {
+    try {
+        await invoke('refresh_telemetry');
+    } catch (error) {
+        console.error('Failed to refresh telemetry:', error);
+    }
+}
@@ -0,0 +1,50 @@
+/**
+ * Lock Service - Optional passphrase lock for the window (the proxy keeps running)
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface LockStatus {
+    enabled: boolean;
+    locked: boolean;
+}
+
+export async function getLockStatus(): Promise<LockStatus> {
+    try {
+        return await invoke<LockStatus>('get_lock_status');
+    } catch (error) {
+        console.error('Failed to get lock status:', error);
+        return { enabled: false, locked: false };
+    }
+}
+
+export async function unlock(passphrase: string): Promise<boolean> {
+    try {
+        return await invoke<boolean>('unlock', { passphrase });
+    } catch (error) {
+        console.error('Failed to unlock:', error);
+        return false;
+    }
+}
+
+export async function lockApp(): Promise<void> {
+    try {
+        await invoke('lock_app');
+    } catch (error) {
+        console.error('Failed to lock app:', error);
+    }
+}
+
+/**
+ * Set or change the passphrase, or remove it with null
+ * @param current - Required when a passphrase is already set
+ */
+export async function setLockPassphrase(current: string | null, passphrase: string | null): Promise<string | null> {
+    try {
+        await invoke('set_lock_passphrase', { current, passphrase });
+        return null;
+    } catch (error) {
+        console.error('Failed to set lock passphrase:', error);
+        return String(error);
+    }
+}
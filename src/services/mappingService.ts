@@ -0,0 +1,87 @@
+/**
+ * Mapping Service - Named model mapping presets, switchable from the tray
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Requested model id -> model it should be served by. A `tier:haiku`,
+ * `tier:sonnet` or `tier:opus` key covers every Claude model of that class;
+ * exact ids win over their tier's rule.
+ */
+export type Mapping = Record<string, string>;
+
+export interface MappingPresets {
+    presets: Record<string, Mapping>;
+    active: string | null;
+}
+
+export async function getMappingPresets(): Promise<MappingPresets | null> {
+    try {
+        return await invoke<MappingPresets>('get_mapping_presets');
+    } catch (error) {
+        console.error('Failed to get mapping presets:', error);
+        return null;
+    }
+}
+
+export async function saveMappingPreset(name: string, mapping: Mapping): Promise<MappingPresets | null> {
+    try {
+        return await invoke<MappingPresets>('save_mapping_preset', { name, mapping });
+    } catch (error) {
+        console.error('Failed to save mapping preset:', error);
+        return null;
+    }
+}
+
+export async function deleteMappingPreset(name: string): Promise<MappingPresets | null> {
+    try {
+        return await invoke<MappingPresets>('delete_mapping_preset', { name });
+    } catch (error) {
+        console.error('Failed to delete mapping preset:', error);
+        return null;
+    }
+}
+
+/** Switch presets, or pass null to clear the active preset's mappings */
+export async function applyMappingPreset(name: string | null): Promise<boolean> {
+    try {
+        await invoke('apply_mapping_preset', { name });
+        return true;
+    } catch (error) {
+        console.error('Failed to apply mapping preset:', error);
+        return false;
+    }
+}
+
+export interface UpstreamModel {
+    accounts: string[];  // accounts that currently report quota for it
+    firstSeen: number;
+    lastSeen: number;
+    retired: boolean;
+}
+
+export interface StaleMapping {
+    preset: string | null;  // null = the proxy's live config
+    model: string;
+    target: string;
+}
+
+export interface ModelCatalog {
+    syncedAt: number | null;
+    models: Record<string, UpstreamModel>;
+    staleMappings: StaleMapping[];
+}
+
+/**
+ * Models available per account, plus mappings whose target no account offers
+ * @param refresh - Sync with the proxy first instead of using the last sync
+ */
+export async function listUpstreamModels(refresh = false): Promise<ModelCatalog | null> {
+    try {
+        return await invoke<ModelCatalog>('list_upstream_models', { refresh });
+    } catch (error) {
+        console.error('Failed to list upstream models:', error);
+        return null;
+    }
+}
@@ -0,0 +1,42 @@
+/**
+ * Onboarding Service - First-run steps carried out by the Rust side
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+export type OnboardingStep = 'port' | 'signIn' | 'clientConfig' | 'testRequest' | 'done';
+
+export interface OnboardingState {
+    step: OnboardingStep;  // first step still to be completed
+    port: number | null;
+    account: string | null;
+    preset: 'claude' | 'gemini' | null;
+    testReply: string | null;
+}
+
+export interface OnboardingInput {
+    port?: number;
+    preset?: 'claude' | 'gemini';
+}
+
+export async function getOnboardingState(): Promise<OnboardingState | null> {
+    try {
+        return await invoke<OnboardingState>('get_onboarding_state');
+    } catch (error) {
+        console.error('Failed to get onboarding state:', error);
+        return null;
+    }
+}
+
+/**
+ * Run one onboarding step. Rejects with the reason the step failed, which the
+ * wizard shows as-is (port taken, sign-in not finished, test request error).
+ */
+export async function completeOnboardingStep(step: OnboardingStep, input?: OnboardingInput): Promise<OnboardingState> {
+    try {
+        return await invoke<OnboardingState>('complete_onboarding_step', { step, input });
+    } catch (error) {
+        console.error(`Onboarding step ${step} failed:`, error);
+        throw new Error(String(error));
+    }
+}
@@ -1,4 +1,5 @@
 import { Command } from '@tauri-apps/plugin-shell';
+import { invoke } from '@tauri-apps/api/core';
 
 const PACKAGE_NAME = 'antigravity-claude-proxy';
 const NPM_REGISTRY_URL = `https://registry.npmjs.org/${PACKAGE_NAME}/latest`;
@@ -177,28 +178,47 @@ export async function installPackage(onProgress?: (msg: string) => void): Promis
     }
 }
 
+export interface EngineVersionInfo {
+    installed: string | null;
+    latest: string | null;
+    previous: string | null;  // what the last update replaced, if anything
+    updateAvailable: boolean;
+}
+
+export async function getEngineVersion(): Promise<EngineVersionInfo | null> {
+    try {
+        return await invoke<EngineVersionInfo>('get_engine_version');
+    } catch (error) {
+        console.error('Failed to get engine version:', error);
+        return null;
+    }
+}
+
+/**
+ * Reinstall the version the last update replaced
+ */
+export async function rollbackPackage(): Promise<EngineVersionInfo | null> {
+    try {
+        return await invoke<EngineVersionInfo>('rollback_update');
+    } catch (error) {
+        console.error('Failed to roll back engine:', error);
+        return null;
+    }
+}
+
 /**
  * Update the package to latest version
+ * Done on the Rust side: the release is checksum/signature verified and the
+ * previous version is restored if the install fails
  */
 export async function updatePackage(onProgress?: (msg: string) => void): Promise<boolean> {
     try {
-        onProgress?.(`Running: npm update -g ${PACKAGE_NAME}`);
-
-        const cmd = Command.create('npm', ['update', '-g', PACKAGE_NAME]);
-        const output = await cmd.execute();
-
-        if (output.code === 0) {
-            onProgress?.('Update complete!');
-            return true;
-        } else {
-            onProgress?.(`Error: ${output.stderr}`);
-            return false;
-        }
-
-    } catch {
-        onProgress?.('Update failed');
+        onProgress?.(`Downloading and verifying ${PACKAGE_NAME}@latest`);
+        await invoke('update_engine');
+        onProgress?.('Update complete!');
+        return true;
+    } catch (e) {
+        onProgress?.(`Error: ${e}`);
         return false;
     }
 }
-
-
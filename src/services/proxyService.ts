@@ -1,65 +1,26 @@
-import { Command, Child } from '@tauri-apps/plugin-shell';
+import { Command } from '@tauri-apps/plugin-shell';
+import { invoke } from '@tauri-apps/api/core';
 import { fetch } from '@tauri-apps/plugin-http';
 import type { ProxyStatus, AccountLimitData, UsageStats, ClaudeConfig, ClaudeConfigResponse, Preset, PresetsResponse } from '../types';
 
-let activeProcess: Child | null = null;
+// Whether we started the proxy process (the Rust side holds the actual handle)
+let processStarted = false;
 
 const DEFAULT_PORT = 8080;
 /**
  * Start the proxy server
+ * The process is owned by the Rust side, which forwards its output to the app log
  * @param port - Port to run the proxy on
  * @param fallbackEnabled - If true, adds --fallback flag for model fallback on quota exhaust
  */
 export async function startProxy(port: number = DEFAULT_PORT, fallbackEnabled: boolean = false): Promise<boolean> {
     try {
-        if (activeProcess) await stopProxy(port);
-
-        const args = ['start'];
-        if (fallbackEnabled) args.push('--fallback');
-
-        const isWindows = navigator.platform.toLowerCase().includes('win');
-        const cmdName = isWindows ? 'start-proxy' : 'start-proxy-unix';
-
-        const cmd = Command.create(cmdName, args, {
-            env: { PORT: port.toString() }
-        });
-
-        // Capture outputs
-        let stdout = '';
-        let stderr = '';
-        let processDied = false;
-
-        cmd.stdout.on('data', line => {
-            stdout += line + '\n';
-        });
-
-        cmd.stderr.on('data', line => {
-            console.error(`[PROXY STDERR]: ${line} `);
-            stderr += line + '\n';
-        });
-
-        cmd.on('error', error => {
-            console.error(`[PROXY ERROR]: "${error}"`);
-            processDied = true;
-        });
-
-        cmd.on('close', data => {
-            if (data.code !== 0 && data.code !== null) {
-                processDied = true;
-            }
-            handleProcessExit(data.code, data.signal);
-        });
-
-        // Spawn the process
-        activeProcess = await cmd.spawn();
+        if (processStarted) await stopProxy(port);
 
         // Quick check - just verify spawn worked, don't wait for health
         // The polling in Dashboard will detect when it's ready
-        if (!activeProcess) {
-            console.error('[ProxyService] Failed to spawn process');
-            return false;
-        }
-
+        await invoke('start_proxy', { port, fallbackEnabled });
+        processStarted = true;
         return true;
     } catch (e: any) {
         console.error('[ProxyService] Failed to start proxy:', e);
@@ -70,19 +31,16 @@ export async function startProxy(port: number = DEFAULT_PORT, fallbackEnabled: b
     }
 }
 
-// Separate function to handle close event to avoid closure issues
-function handleProcessExit(_code: number | null, _signal: number | null) {
-    activeProcess = null;
-    // Don't show error toast - code 1 is normal for forced kills via taskkill
-}
-
 /**
  * Stop the proxy server
  */
 export async function stopProxy(port: number = DEFAULT_PORT): Promise<boolean> {
-    activeProcess = null;
+    processStarted = false;
 
     try {
+        await invoke('stop_proxy');
+
+        // The spawned wrapper may leave the node process behind, so also free the port
         const isWindows = navigator.platform.toLowerCase().includes('win');
 
         if (isWindows) {
@@ -106,6 +64,22 @@ export async function stopProxy(port: number = DEFAULT_PORT): Promise<boolean> {
     }
 }
 
+/**
+ * Apply a new port, bind address or fallback flag to the running proxy without
+ * cutting off requests in progress (starts it if it isn't running).
+ * Returns the error message on failure.
+ */
+export async function reconfigureProxy(port: number, fallbackEnabled: boolean): Promise<string | null> {
+    try {
+        await invoke('reconfigure_proxy', { port, fallbackEnabled });
+        processStarted = true;
+        return null;
+    } catch (e) {
+        console.error('[ProxyService] reconfigureProxy failed:', e);
+        return String(e);
+    }
+}
+
 /**
  * Get proxy health status
  */
@@ -680,3 +654,180 @@ export async function updateServerConfig(
     }
     return false;
 }
+
+export interface FirewallStatus {
+    supported: boolean;  // Windows only
+    present: boolean;
+    port: number | null;
+}
+
+/**
+ * State of the inbound Windows Firewall rule used for LAN access
+ */
+export async function getFirewallRule(): Promise<FirewallStatus | null> {
+    try {
+        return await invoke<FirewallStatus>('get_firewall_rule');
+    } catch (e) {
+        console.error('[ProxyService] getFirewallRule failed:', e);
+        return null;
+    }
+}
+
+/**
+ * Create or remove the firewall rule (shows a UAC prompt); returns the error message on failure
+ */
+export async function setFirewallRule(port: number | null): Promise<string | null> {
+    try {
+        if (port === null) await invoke('remove_firewall_rule');
+        else await invoke('add_firewall_rule', { port });
+        return null;
+    } catch (e) {
+        console.error('[ProxyService] setFirewallRule failed:', e);
+        return String(e);
+    }
+}
+
+/**
+ * Serve MCP on a loopback port, or stop with null; returns the error message on failure
+ */
+export async function setMcpPort(port: number | null): Promise<string | null> {
+    try {
+        await invoke('set_mcp_port', { port });
+        return null;
+    } catch (e) {
+        console.error('[ProxyService] setMcpPort failed:', e);
+        return String(e);
+    }
+}
+
+/**
+ * Bearer token MCP clients must send, or null on failure (e.g. while the app is locked)
+ */
+export async function getMcpToken(): Promise<string | null> {
+    try {
+        return await invoke<string>('get_mcp_token');
+    } catch (e) {
+        console.error('[ProxyService] getMcpToken failed:', e);
+        return null;
+    }
+}
+
+/**
+ * Replace the MCP token; clients configured with the old one stop working
+ */
+export async function resetMcpToken(): Promise<string | null> {
+    try {
+        return await invoke<string>('reset_mcp_token');
+    } catch (e) {
+        console.error('[ProxyService] resetMcpToken failed:', e);
+        return null;
+    }
+}
+
+export interface ProfileStatus {
+    name: string;
+    port: number;
+    fallbackEnabled: boolean;
+    running: boolean;
+    pid: number | null;
+    home: string;  // stands in for the home folder, so the profile has its own accounts
+}
+
+/**
+ * Extra engine instances on their own ports with separate account pools.
+ * Also started by launching the app with --profile <name>.
+ */
+export async function listProfiles(): Promise<ProfileStatus[]> {
+    try {
+        return await invoke<ProfileStatus[]>('list_profiles');
+    } catch (e) {
+        console.error('[ProxyService] listProfiles failed:', e);
+        return [];
+    }
+}
+
+/**
+ * Create or change a profile; returns the error message on failure
+ */
+export async function saveProfile(name: string, port: number, fallbackEnabled: boolean = false): Promise<string | null> {
+    try {
+        await invoke('save_profile', { name, port, fallbackEnabled });
+        return null;
+    } catch (e) {
+        console.error('[ProxyService] saveProfile failed:', e);
+        return String(e);
+    }
+}
+
+export async function deleteProfile(name: string): Promise<boolean> {
+    try {
+        await invoke('delete_profile', { name });
+        return true;
+    } catch (e) {
+        console.error('[ProxyService] deleteProfile failed:', e);
+        return false;
+    }
+}
+
+/**
+ * Start or stop a profile's engine; returns the error message on failure
+ */
+export async function setProfileRunning(name: string, running: boolean): Promise<string | null> {
+    try {
+        await invoke(running ? 'start_profile' : 'stop_profile', { name });
+        return null;
+    } catch (e) {
+        console.error('[ProxyService] setProfileRunning failed:', e);
+        return String(e);
+    }
+}
+
+export type EnginePriority = 'belowNormal' | 'normal' | 'high';
+
+/**
+ * Save the engine's process priority and apply it to the running engine.
+ * Returns the error message on failure (raising priority needs root on macOS/Linux).
+ */
+export async function setEnginePriority(priority: EnginePriority): Promise<string | null> {
+    try {
+        await invoke('set_engine_priority', { priority });
+        return null;
+    } catch (e) {
+        console.error('[ProxyService] setEnginePriority failed:', e);
+        return String(e);
+    }
+}
+
+export interface QuotaReset {
+    email: string;
+    model: string;
+    kind: 'quota' | 'rate_limit';
+    resetAt: number;  // unix ms
+}
+
+/**
+ * Upcoming quota and rate-limit resets per account and model, soonest first.
+ * Updates are pushed as the 'quota-resets' event.
+ */
+export async function getQuotaResets(): Promise<QuotaReset[]> {
+    try {
+        return await invoke<QuotaReset[]>('get_quota_resets');
+    } catch (e) {
+        console.error('[ProxyService] getQuotaResets failed:', e);
+        return [];
+    }
+}
+
+/**
+ * Route all traffic to one account, or back to automatic rotation with null.
+ * Other accounts are disabled while pinned and re-enabled afterwards.
+ */
+export async function pinAccount(email: string | null): Promise<boolean> {
+    try {
+        await invoke('pin_account', { email });
+        return true;
+    } catch (e) {
+        console.error('[ProxyService] pinAccount failed:', e);
+        return false;
+    }
+}
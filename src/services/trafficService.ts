@@ -0,0 +1,81 @@
+/**
+ * Traffic Service - Request metrics collected on the Rust side from proxy logs
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { UsageStats } from '../types';
+
+export interface LatencyPoint {
+    timestamp: number;
+    count: number;
+    p50Ms: number;
+    p95Ms: number;
+}
+
+export interface LatencySummary {
+    key: string;
+    count: number;
+    p50Ms: number;
+    p95Ms: number;
+    series: LatencyPoint[];
+}
+
+export interface LatencyStats {
+    endpoints: LatencySummary[];
+    models: LatencySummary[];
+    accounts: LatencySummary[];
+}
+
+export async function getLatencyStats(): Promise<LatencyStats | null> {
+    try {
+        return await invoke<LatencyStats>('get_latency_stats');
+    } catch (error) {
+        console.error('Failed to get latency stats:', error);
+        return null;
+    }
+}
+
+export type ErrorClass = 'auth' | 'rate_limit' | 'upstream_5xx' | 'network' | 'translation' | 'client_abort' | 'other';
+
+export interface ErrorCounters {
+    since: number;
+    total: number;
+    counts: Partial<Record<ErrorClass, number>>;
+}
+
+export async function getErrorCounters(): Promise<ErrorCounters | null> {
+    try {
+        return await invoke<ErrorCounters>('get_error_counters');
+    } catch (error) {
+        console.error('Failed to get error counters:', error);
+        return null;
+    }
+}
+
+export async function resetErrorCounters(): Promise<void> {
+    try {
+        await invoke('reset_error_counters');
+    } catch (error) {
+        console.error('Failed to reset error counters:', error);
+    }
+}
+
+export interface CostEstimate {
+    currency: string;
+    total: number;
+    byModel: Record<string, number>;
+    unpriced: string[];  // models without a price; left out of the total
+}
+
+/**
+ * Equivalent API list-price cost of the proxy's usage history, priced with the
+ * bundled, remote (signed) or locally overridden pricing table
+ */
+export async function estimateUsageCost(history: UsageStats): Promise<CostEstimate | null> {
+    try {
+        return await invoke<CostEstimate>('estimate_usage_cost', { history });
+    } catch (error) {
+        console.error('Failed to estimate usage cost:', error);
+        return null;
+    }
+}
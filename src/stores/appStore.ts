@@ -32,6 +32,8 @@ interface AppState {
     // Navigation
     currentPage: string;
     setCurrentPage: (page: string) => void;
+    logFocus: number | null;  // unix ms of a log entry the viewer should scroll to
+    setLogFocus: (timestamp: number | null) => void;
 
     // Config
     config: AppConfig;
@@ -117,6 +119,8 @@ export const useAppStore = create<AppState>((set) => ({
     // Navigation
     currentPage: 'dashboard',
     setCurrentPage: (page) => set({ currentPage: page }),
+    logFocus: null,
+    setLogFocus: (logFocus) => set({ logFocus }),
 
     // Config
     config: defaultConfig,